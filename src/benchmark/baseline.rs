@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::benchmark_result::BenchmarkResult;
+use super::relative_speed::Significance;
+use crate::export::read_json_results;
+use crate::options::SignificanceTest;
+use crate::util::units::{Scalar, Second};
+
+use anyhow::Result;
+
+/// Outcome of the comparison of a benchmark with its baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    /// Slower than the baseline by more than the threshold, and statistically significant
+    Regression,
+
+    /// Faster than the baseline by more than the threshold, and statistically significant
+    Improvement,
+
+    /// No significant change
+    Unchanged,
+}
+
+/// Comparison of a benchmark result with the corresponding result of a previous run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineComparison {
+    /// Mean run time of the baseline
+    pub baseline_mean: Second,
+
+    /// Relative change of the mean run time (0.05 = 5% slower than the baseline)
+    pub relative_change: Scalar,
+
+    /// p-value of the significance test between the current and the baseline samples. Not
+    /// available if one of them consists of a single run.
+    pub p_value: Option<Scalar>,
+
+    pub verdict: Verdict,
+}
+
+/// Results of a previous benchmark run that new results are compared against
+pub struct Baseline {
+    results: Vec<BenchmarkResult>,
+    threshold: Scalar,
+    significance_test: SignificanceTest,
+}
+
+impl Baseline {
+    /// Load the baseline from a JSON export
+    pub fn load(
+        filename: &str,
+        threshold: Scalar,
+        significance_test: SignificanceTest,
+    ) -> Result<Self> {
+        Ok(Baseline {
+            results: read_json_results(filename)?,
+            threshold,
+            significance_test,
+        })
+    }
+
+    /// Find the baseline result for the same command (name and parameters)
+    fn find(&self, result: &BenchmarkResult) -> Option<&BenchmarkResult> {
+        self.results
+            .iter()
+            .find(|b| b.command == result.command && b.parameters == result.parameters)
+    }
+
+    /// Compare the given result with its baseline. Returns `None` if the baseline does
+    /// not contain a matching result.
+    pub fn compare(&self, result: &BenchmarkResult) -> Option<BaselineComparison> {
+        let baseline = self.find(result)?;
+
+        let relative_change = if baseline.mean > 0.0 {
+            result.mean / baseline.mean - 1.0
+        } else {
+            0.0
+        };
+
+        let significance = Significance::compute(self.significance_test, result, baseline);
+
+        // Without enough samples for a significance test, the threshold alone decides
+        let significant = significance.map_or(true, |s| s.is_significant());
+
+        let verdict = if significant && relative_change > self.threshold {
+            Verdict::Regression
+        } else if significant && relative_change < -self.threshold {
+            Verdict::Improvement
+        } else {
+            Verdict::Unchanged
+        };
+
+        Some(BaselineComparison {
+            baseline_mean: baseline.mean,
+            relative_change,
+            p_value: significance.map(|s| s.p_value),
+            verdict,
+        })
+    }
+}
+
+#[cfg(test)]
+fn baseline_with(results: Vec<BenchmarkResult>) -> Baseline {
+    Baseline {
+        results,
+        threshold: 0.05,
+        significance_test: SignificanceTest::Welch,
+    }
+}
+
+#[cfg(test)]
+fn create_result(command: &str, times: Vec<Second>) -> BenchmarkResult {
+    BenchmarkResult {
+        command: command.into(),
+        mean: times.iter().sum::<Second>() / times.len() as Second,
+        times: Some(times),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_baseline_verdicts() {
+    let baseline = baseline_with(vec![
+        create_result("a", vec![1.0, 1.1, 0.9, 1.0]),
+        create_result("b", vec![1.0, 1.1, 0.9, 1.0]),
+        create_result("c", vec![1.0, 1.1, 0.9, 1.0]),
+        create_result("d", vec![1.0, 1.1, 0.9, 1.0]),
+    ]);
+
+    let verdict = |result| baseline.compare(&result).map(|c| c.verdict);
+
+    // 50% slower, significant
+    assert_eq!(
+        verdict(create_result("a", vec![1.5, 1.6, 1.4, 1.5])),
+        Some(Verdict::Regression)
+    );
+    // 50% faster, significant
+    assert_eq!(
+        verdict(create_result("b", vec![0.5, 0.6, 0.4, 0.5])),
+        Some(Verdict::Improvement)
+    );
+    // 3% slower, below the threshold
+    assert_eq!(
+        verdict(create_result("c", vec![1.03, 1.13, 0.93, 1.03])),
+        Some(Verdict::Unchanged)
+    );
+    // 10% slower, but not significant
+    assert_eq!(
+        verdict(create_result("d", vec![0.6, 1.6, 0.7, 1.5])),
+        Some(Verdict::Unchanged)
+    );
+    // Not part of the baseline
+    assert_eq!(verdict(create_result("e", vec![1.0, 1.0])), None);
+}
+
+#[test]
+fn test_baseline_matches_parameters() {
+    let with_parameter = |value: &str| {
+        let mut result = create_result("sleep {t}", vec![1.0, 1.0]);
+        result.parameters.insert("t".into(), value.into());
+        result
+    };
+
+    let mut baseline_result = with_parameter("1");
+    baseline_result.mean = 0.5;
+    let baseline = baseline_with(vec![with_parameter("2"), baseline_result]);
+
+    let comparison = baseline.compare(&with_parameter("1")).unwrap();
+    assert_eq!(comparison.baseline_mean, 0.5);
+    assert!(baseline.compare(&with_parameter("3")).is_none());
+}
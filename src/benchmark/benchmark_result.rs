@@ -1,22 +1,195 @@
 use std::collections::BTreeMap;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::util::units::Second;
+use super::baseline::BaselineComparison;
+use crate::timer::ResourceUsage;
+use crate::util::statistics::{bootstrap_mean_median, percentile};
+use crate::util::units::{Scalar, Second};
+
+/// A confidence interval
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ConfidenceInterval {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl From<(f64, f64)> for ConfidenceInterval {
+    fn from((lower, upper): (f64, f64)) -> Self {
+        ConfidenceInterval { lower, upper }
+    }
+}
+
+/// Bootstrap confidence intervals of the mean and the median run time
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfidenceIntervals {
+    /// Confidence level (0.95 = 95%)
+    pub level: f64,
+
+    /// Number of bootstrap resamples
+    pub resamples: u64,
+
+    pub mean: ConfidenceInterval,
+
+    pub median: ConfidenceInterval,
+}
+
+impl ConfidenceIntervals {
+    /// Compute the confidence intervals from the given run times. Returns `None` if there
+    /// are fewer than two runs or if no resamples are requested.
+    pub fn from_times(times: &[Second], level: f64, resamples: u64) -> Option<Self> {
+        let (mean, median) = bootstrap_mean_median(times, level, resamples)?;
+
+        Some(ConfidenceIntervals {
+            level,
+            resamples,
+            mean: mean.into(),
+            median: median.into(),
+        })
+    }
+}
+
+/// Precision that has been achieved with `--target-precision`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PrecisionOutcome {
+    /// Requested half width of the confidence interval, relative to the statistic
+    pub target: Scalar,
+
+    /// Achieved half width of the confidence interval. Not available if fewer than two
+    /// runs have been performed
+    pub achieved: Option<Scalar>,
+}
+
+impl PrecisionOutcome {
+    /// Whether the target precision has been reached within the run and time budget
+    pub fn is_reached(&self) -> bool {
+        self.achieved
+            .is_some_and(|achieved| achieved <= self.target)
+    }
+}
+
+/// Percentiles and other statistics of the run times that go beyond mean, median and stddev
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExtendedStatistics {
+    pub p5: Second,
+    pub p25: Second,
+    pub p75: Second,
+    pub p95: Second,
+    pub p99: Second,
+
+    /// Interquartile range (P75 - P25)
+    pub iqr: Second,
+
+    /// Standard deviation relative to the mean. Not available if only one run has been
+    /// performed or if the mean is zero
+    pub coefficient_of_variation: Option<Scalar>,
+}
+
+impl ExtendedStatistics {
+    /// Compute the statistics from the given run times. Returns `None` if there are no times.
+    pub fn from_times(times: &[Second], mean: Second, stddev: Option<Second>) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+
+        let mut sorted = times.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let p25 = percentile(&sorted, 25.0);
+        let p75 = percentile(&sorted, 75.0);
+
+        Some(ExtendedStatistics {
+            p5: percentile(&sorted, 5.0),
+            p25,
+            p75,
+            p95: percentile(&sorted, 95.0),
+            p99: percentile(&sorted, 99.0),
+            iqr: p75 - p25,
+            coefficient_of_variation: stddev.filter(|_| mean != 0.0).map(|s| s / mean),
+        })
+    }
+}
+
+/// Summary of the resource usage over all runs of a single command. Apart from the peak
+/// memory usage, all values are averages per run.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResourceUsageSummary {
+    /// Mean of the peak resident set sizes, in bytes
+    pub max_rss_mean: f64,
+
+    /// Largest peak resident set size of all runs, in bytes
+    pub max_rss_max: u64,
+
+    /// Minor page faults (serviced without any I/O activity)
+    pub minor_page_faults: f64,
+
+    /// Major page faults (serviced with I/O activity)
+    pub major_page_faults: f64,
+
+    /// Voluntary context switches
+    pub voluntary_context_switches: f64,
+
+    /// Involuntary context switches
+    pub involuntary_context_switches: f64,
+
+    /// Block input operations
+    pub block_input_operations: f64,
+
+    /// Block output operations
+    pub block_output_operations: f64,
+}
+
+fn none_timed_out(timed_out: &[bool]) -> bool {
+    !timed_out.contains(&true)
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl ResourceUsageSummary {
+    /// Summarize the resource usage of all runs. Returns `None` if the resource usage
+    /// has not been measured (e.g. on Windows).
+    pub fn from_runs(runs: &[ResourceUsage]) -> Option<Self> {
+        if runs.iter().all(|r| r.max_rss_byte == 0) {
+            return None;
+        }
+
+        let mean_of = |f: fn(&ResourceUsage) -> u64| {
+            runs.iter().map(|r| f(r) as f64).sum::<f64>() / runs.len() as f64
+        };
+
+        Some(ResourceUsageSummary {
+            max_rss_mean: mean_of(|r| r.max_rss_byte),
+            max_rss_max: runs.iter().map(|r| r.max_rss_byte).max().unwrap_or(0),
+            minor_page_faults: mean_of(|r| r.minor_page_faults),
+            major_page_faults: mean_of(|r| r.major_page_faults),
+            voluntary_context_switches: mean_of(|r| r.voluntary_context_switches),
+            involuntary_context_switches: mean_of(|r| r.involuntary_context_switches),
+            block_input_operations: mean_of(|r| r.block_input_operations),
+            block_output_operations: mean_of(|r| r.block_output_operations),
+        })
+    }
+}
 
 /// Set of values that will be exported.
 // NOTE: `serde` is used for JSON serialization, but not for CSV serialization due to the
 // `parameters` map. Update `src/hyperfine/export/csv.rs` with new fields, as appropriate.
-#[derive(Debug, Default, Clone, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BenchmarkResult {
     /// The full command line of the program that is being benchmarked
     pub command: String,
 
     /// The full command line of the program that is being benchmarked, possibly including a list of
     /// parameters that were not used in the command line template.
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub command_with_unused_parameters: String,
 
+    /// The expanded command line. Only exported if it differs from `command`, which is the
+    /// name given with '--command-name' in that case.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_line: Option<String>,
+
     /// The average run time
     pub mean: Second,
 
@@ -38,14 +211,101 @@ pub struct BenchmarkResult {
     /// Maximum of all measured times
     pub max: Second,
 
+    /// Percentiles, interquartile range and coefficient of variation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_statistics: Option<ExtendedStatistics>,
+
+    /// Bootstrap confidence intervals of the mean and the median. Not available if only
+    /// one run has been performed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence_intervals: Option<ConfidenceIntervals>,
+
+    /// Precision of the mean or median with `--target-precision`, if used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<PrecisionOutcome>,
+
     /// All run time measurements
     #[serde(skip_serializing_if = "Option::is_none")]
     pub times: Option<Vec<Second>>,
 
+    /// Time spent in user mode, for each run (in the same order as `times`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub times_user: Option<Vec<Second>>,
+
+    /// Time spent in kernel mode, for each run (in the same order as `times`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub times_system: Option<Vec<Second>>,
+
+    /// Start of each run in seconds since the Unix epoch (in the same order as `times`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<Vec<f64>>,
+
+    /// Peak memory usage, page faults, context switches and block I/O. Not available on Windows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsageSummary>,
+
     /// Exit codes of all command invocations
     pub exit_codes: Vec<Option<i32>>,
 
+    /// Whether or not the individual command invocations have been terminated because they
+    /// exceeded the timeout. Only exported if at least one of them timed out.
+    #[serde(default, skip_serializing_if = "none_timed_out")]
+    pub timed_out: Vec<bool>,
+
+    /// Whether the benchmark has been interrupted (Ctrl-C) before all runs were performed.
+    /// Only exported if set.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub incomplete: bool,
+
+    /// Comparison with the corresponding result of the `--baseline` file, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineComparison>,
+
     /// Parameter values for this benchmark
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub parameters: BTreeMap<String, String>,
 }
+
+impl BenchmarkResult {
+    /// The expanded command line of the benchmarked command
+    pub fn command_line(&self) -> &str {
+        self.command_line.as_deref().unwrap_or(&self.command)
+    }
+}
+
+#[test]
+fn test_extended_statistics() {
+    let times: Vec<Second> = (1..=101).map(|t| t as Second).collect();
+    let statistics = ExtendedStatistics::from_times(&times, 51.0, Some(5.1)).unwrap();
+
+    assert_eq!(statistics.p5, 6.0);
+    assert_eq!(statistics.p25, 26.0);
+    assert_eq!(statistics.p75, 76.0);
+    assert_eq!(statistics.p95, 96.0);
+    assert_eq!(statistics.p99, 100.0);
+    assert_eq!(statistics.iqr, 50.0);
+    approx::assert_relative_eq!(statistics.coefficient_of_variation.unwrap(), 0.1);
+
+    let single = ExtendedStatistics::from_times(&[2.0], 2.0, None).unwrap();
+    assert_eq!(single.p5, 2.0);
+    assert_eq!(single.coefficient_of_variation, None);
+
+    assert!(ExtendedStatistics::from_times(&[], 0.0, None).is_none());
+}
+
+#[test]
+fn test_resource_usage_summary() {
+    let run = |max_rss_byte, minor_page_faults| ResourceUsage {
+        max_rss_byte,
+        minor_page_faults,
+        ..Default::default()
+    };
+
+    let summary = ResourceUsageSummary::from_runs(&[run(1000, 10), run(3000, 20)]).unwrap();
+    assert_eq!(summary.max_rss_mean, 2000.0);
+    assert_eq!(summary.max_rss_max, 3000);
+    assert_eq!(summary.minor_page_faults, 15.0);
+    assert_eq!(summary.major_page_faults, 0.0);
+
+    assert!(ResourceUsageSummary::from_runs(&[ResourceUsage::default()]).is_none());
+}
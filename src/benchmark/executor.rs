@@ -1,19 +1,19 @@
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
+use std::path::Path;
 use std::process::ExitStatus;
 
 use crate::command::Command;
-use crate::options::{
-    CmdFailureAction, CommandInputPolicy, CommandOutputPolicy, Options, OutputStyleOption, Shell,
-};
+use crate::options::{CmdFailureAction, Options, OutputStyleOption, Shell};
+use crate::output::format::format_duration;
 use crate::output::progress_bar::get_progress_bar;
-use crate::timer::{execute_and_measure, TimerResult};
+use crate::timer::{execute_and_measure, ResourceUsage, TimerResult};
 use crate::util::randomized_environment_offset;
 use crate::util::units::Second;
 
 use super::timing_result::TimingResult;
 
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, ensure, Context, Result};
 use statistical::mean;
 
 pub trait Executor {
@@ -39,23 +39,46 @@ pub trait Executor {
 
 fn run_command_and_measure_common(
     mut command: std::process::Command,
+    hyperfine_command: &Command<'_>,
     command_failure_action: CmdFailureAction,
-    command_input_policy: &CommandInputPolicy,
-    command_output_policy: &CommandOutputPolicy,
-    command_name: &str,
+    options: &Options,
 ) -> Result<TimerResult> {
-    let stdin = command_input_policy.get_stdin()?;
-    let (stdout, stderr) = command_output_policy.get_stdout_stderr()?;
+    let command_name = hyperfine_command.get_command_line();
+    let timeout = options.timeout;
+
+    let stdin = options.command_input_policy.get_stdin()?;
+    let (stdout, stderr) = options.command_output_policy.get_stdout_stderr()?;
     command.stdin(stdin).stdout(stdout).stderr(stderr);
 
+    if options.clear_environment {
+        command.env_clear();
+    }
+    command.envs(hyperfine_command.get_environment()?);
+    if let Some(directory) = hyperfine_command.get_working_directory() {
+        ensure!(
+            Path::new(&directory).is_dir(),
+            "The working directory '{directory}' (see '--cwd') does not exist"
+        );
+        command.current_dir(directory);
+    }
+
     command.env(
         "HYPERFINE_RANDOMIZED_ENVIRONMENT_OFFSET",
         randomized_environment_offset::value(),
     );
 
-    let result = execute_and_measure(command)
+    let result = execute_and_measure(command, timeout)
         .with_context(|| format!("Failed to run command '{command_name}'"))?;
 
+    if command_failure_action == CmdFailureAction::RaiseError && result.timed_out {
+        bail!(
+            "Command '{}' has been terminated after exceeding the timeout of {}. \
+            Use the '-i'/'--ignore-failure' option if you want to ignore this.",
+            command_name,
+            format_duration(timeout.unwrap_or_default(), None)
+        );
+    }
+
     if command_failure_action == CmdFailureAction::RaiseError && !result.status.success() {
         bail!(
             "{}. Use the '-i'/'--ignore-failure' option if you want to ignore this. \
@@ -88,10 +111,9 @@ impl<'a> Executor for RawExecutor<'a> {
     ) -> Result<(TimingResult, ExitStatus)> {
         let result = run_command_and_measure_common(
             command.get_command()?,
+            command,
             command_failure_action.unwrap_or(self.options.command_failure_action),
-            &self.options.command_input_policy,
-            &self.options.command_output_policy,
-            &command.get_command_line(),
+            self.options,
         )?;
 
         Ok((
@@ -99,6 +121,8 @@ impl<'a> Executor for RawExecutor<'a> {
                 time_real: result.time_real,
                 time_user: result.time_user,
                 time_system: result.time_system,
+                resource_usage: result.resource_usage,
+                timed_out: result.timed_out,
             },
             result.status,
         ))
@@ -149,10 +173,9 @@ impl<'a> Executor for ShellExecutor<'a> {
 
         let mut result = run_command_and_measure_common(
             command_builder,
+            command,
             command_failure_action.unwrap_or(self.options.command_failure_action),
-            &self.options.command_input_policy,
-            &self.options.command_output_policy,
-            &command.get_command_line(),
+            self.options,
         )?;
 
         // Subtract shell spawning time
@@ -167,6 +190,8 @@ impl<'a> Executor for ShellExecutor<'a> {
                 time_real: result.time_real,
                 time_user: result.time_user,
                 time_system: result.time_system,
+                resource_usage: result.resource_usage,
+                timed_out: result.timed_out,
             },
             result.status,
         ))
@@ -226,6 +251,8 @@ impl<'a> Executor for ShellExecutor<'a> {
             time_real: mean(&times_real),
             time_user: mean(&times_user),
             time_system: mean(&times_system),
+            resource_usage: ResourceUsage::default(),
+            timed_out: false,
         });
 
         Ok(())
@@ -279,6 +306,8 @@ impl Executor for MockExecutor {
                 time_real: Self::extract_time(command.get_command_line()),
                 time_user: 0.0,
                 time_system: 0.0,
+                resource_usage: ResourceUsage::default(),
+                timed_out: false,
             },
             status,
         ))
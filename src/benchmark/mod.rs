@@ -1,3 +1,4 @@
+pub mod baseline;
 pub mod benchmark_result;
 pub mod executor;
 pub mod relative_speed;
@@ -5,18 +6,34 @@ pub mod scheduler;
 pub mod timing_result;
 
 use std::cmp;
+use std::process::ExitStatus;
+use std::time::Instant;
 
 use crate::command::Command;
-use crate::options::{CmdFailureAction, ExecutorKind, Options, OutputStyleOption};
+use crate::export::{Event, EventLog};
+use crate::options::{
+    value_for_command, CmdFailureAction, ExecutorKind, Options, OutputStyleOption,
+    PrecisionStatistic, TargetPrecision,
+};
 use crate::outlier_detection::{modified_zscores, OUTLIER_THRESHOLD};
-use crate::output::format::{format_duration, format_duration_unit};
+use crate::output::format::{
+    format_bytes, format_duration, format_duration_unit, format_percentage,
+    format_percentage_precise,
+};
 use crate::output::progress_bar::get_progress_bar;
 use crate::output::warnings::{OutlierWarningOptions, Warnings};
 use crate::parameter::ParameterNameAndValue;
+use crate::timer::ResourceUsage;
 use crate::util::exit_code::extract_exit_code;
+use crate::util::interrupt;
 use crate::util::min_max::{max, min};
+use crate::util::statistics::{relative_mean_ci_half_width, relative_median_ci_half_width};
+use crate::util::timestamp::unix_timestamp;
 use crate::util::units::Second;
-use benchmark_result::BenchmarkResult;
+use benchmark_result::{
+    BenchmarkResult, ConfidenceIntervals, ExtendedStatistics, PrecisionOutcome,
+    ResourceUsageSummary,
+};
 use timing_result::TimingResult;
 
 use anyhow::{anyhow, Result};
@@ -28,11 +45,75 @@ use self::executor::Executor;
 /// Threshold for warning about fast execution time
 pub const MIN_EXECUTION_TIME: Second = 5e-3;
 
+/// Measurements of all timing runs of a single command
+#[derive(Debug, Default)]
+struct Measurements {
+    times_real: Vec<Second>,
+    times_user: Vec<Second>,
+    times_system: Vec<Second>,
+    timestamps: Vec<f64>,
+    resource_usages: Vec<ResourceUsage>,
+    exit_codes: Vec<Option<i32>>,
+    timed_out: Vec<bool>,
+    any_failed: bool,
+}
+
+impl Measurements {
+    fn push(&mut self, run: &TimedRun) {
+        let TimedRun {
+            timestamp,
+            result,
+            status,
+            ..
+        } = run;
+
+        self.times_real.push(result.time_real);
+        self.times_user.push(result.time_user);
+        self.times_system.push(result.time_system);
+        self.timestamps.push(*timestamp);
+        self.resource_usages.push(result.resource_usage);
+        self.exit_codes.push(extract_exit_code(*status));
+        self.timed_out.push(result.timed_out);
+        self.any_failed = self.any_failed || !status.success();
+    }
+}
+
+/// A single run of the benchmarked command, with its `--prepare` and `--conclude` commands
+struct TimedRun {
+    /// Start of the benchmarked command (seconds since the Unix epoch)
+    timestamp: f64,
+    preparation: Option<TimingResult>,
+    result: TimingResult,
+    status: ExitStatus,
+    conclusion: Option<TimingResult>,
+}
+
+/// A run of the benchmarked command, numbered from 1
+#[derive(Debug, Clone, Copy)]
+enum Run {
+    Warmup(u64),
+    Timing(u64),
+}
+
 pub struct Benchmark<'a> {
     number: usize,
-    command: &'a Command<'a>,
+    command: Command<'a>,
     options: &'a Options,
     executor: &'a dyn Executor,
+    events: &'a EventLog,
+    preparation_command: Option<Command<'a>>,
+    conclusion_command: Option<Command<'a>>,
+    measurements: Measurements,
+
+    /// Environment variables and working directory for all commands of this benchmark
+    environment: Option<&'a str>,
+    working_directory: Option<&'a str>,
+
+    /// Current estimate for the total number of timing runs
+    planned_runs: u64,
+
+    /// Total wall clock time spent on timing runs (including `--prepare` and `--conclude`)
+    time_spent: Second,
 }
 
 impl<'a> Benchmark<'a> {
@@ -41,12 +122,34 @@ impl<'a> Benchmark<'a> {
         command: &'a Command<'a>,
         options: &'a Options,
         executor: &'a dyn Executor,
+        events: &'a EventLog,
     ) -> Self {
+        // The --prepare, --conclude, --env and --cwd options can be given once for all
+        // commands, or once for each of them.
+        let environment = value_for_command(&options.environment, number);
+        let working_directory = value_for_command(&options.working_directory, number);
+        let select = |values: &'a Option<Vec<String>>| {
+            value_for_command(values, number).map(|value| {
+                Command::new_parametrized(None, value, command.get_parameters().iter().cloned())
+                    .with_environment(environment, working_directory)
+            })
+        };
+
         Benchmark {
             number,
-            command,
+            command: command
+                .clone()
+                .with_environment(environment, working_directory),
             options,
             executor,
+            events,
+            preparation_command: select(&options.preparation_command),
+            conclusion_command: select(&options.conclusion_command),
+            measurements: Measurements::default(),
+            environment,
+            working_directory,
+            planned_runs: 0,
+            time_spent: 0.0,
         }
     }
 
@@ -67,11 +170,10 @@ impl<'a> Benchmark<'a> {
         &self,
         parameters: impl IntoIterator<Item = ParameterNameAndValue<'a>>,
     ) -> Result<TimingResult> {
-        let command = self
-            .options
-            .setup_command
-            .as_ref()
-            .map(|setup_command| Command::new_parametrized(None, setup_command, parameters));
+        let command = self.options.setup_command.as_ref().map(|setup_command| {
+            Command::new_parametrized(None, setup_command, parameters)
+                .with_environment(self.environment, self.working_directory)
+        });
 
         let error_output = "The setup command terminated with a non-zero exit code. \
                             Append ' || true' to the command if you are sure that this can be ignored.";
@@ -91,7 +193,10 @@ impl<'a> Benchmark<'a> {
             .options
             .cleanup_command
             .as_ref()
-            .map(|cleanup_command| Command::new_parametrized(None, cleanup_command, parameters));
+            .map(|cleanup_command| {
+                Command::new_parametrized(None, cleanup_command, parameters)
+                    .with_environment(self.environment, self.working_directory)
+            });
 
         let error_output = "The cleanup command terminated with a non-zero exit code. \
                             Append ' || true' to the command if you are sure that this can be ignored.";
@@ -118,120 +223,234 @@ impl<'a> Benchmark<'a> {
         self.run_intermediate_command(command, error_output)
     }
 
-    /// Run the benchmark for a single command
-    pub fn run(&self) -> Result<BenchmarkResult> {
-        if self.options.output_style != OutputStyleOption::Disabled {
-            println!(
-                "{}{}: {}",
-                "Benchmark ".bold(),
-                (self.number + 1).to_string().bold(),
-                self.command.get_name_with_unused_parameters(),
-            );
+    /// Run the `--prepare` command for this benchmark, if any
+    fn run_preparation_command_if_given(&self) -> Result<Option<TimingResult>> {
+        self.preparation_command
+            .as_ref()
+            .map(|cmd| self.run_preparation_command(cmd))
+            .transpose()
+    }
+
+    /// Run the `--conclude` command for this benchmark, if any
+    fn run_conclusion_command_if_given(&self) -> Result<Option<TimingResult>> {
+        self.conclusion_command
+            .as_ref()
+            .map(|cmd| self.run_conclusion_command(cmd))
+            .transpose()
+    }
+
+    /// Run the benchmark for a single command. Returns `None` if hyperfine has been
+    /// interrupted before the first timing run.
+    pub fn run(&mut self) -> Result<Option<BenchmarkResult>> {
+        self.print_header();
+
+        let result = self.measure();
+
+        // Errors of commands that have been terminated by the interrupt are expected
+        if !interrupt::is_interrupted() {
+            result?;
         }
 
-        let mut times_real: Vec<Second> = vec![];
-        let mut times_user: Vec<Second> = vec![];
-        let mut times_system: Vec<Second> = vec![];
-        let mut exit_codes: Vec<Option<i32>> = vec![];
-        let mut all_succeeded = true;
+        if self.num_runs() == 0 {
+            return Ok(None);
+        }
 
-        let preparation_command = self.options.preparation_command.as_ref().map(|values| {
-            let preparation_command = if values.len() == 1 {
-                &values[0]
-            } else {
-                &values[self.number]
-            };
-            Command::new_parametrized(
-                None,
-                preparation_command,
-                self.command.get_parameters().iter().cloned(),
-            )
-        });
-        let run_preparation_command = || {
-            preparation_command
-                .as_ref()
-                .map(|cmd| self.run_preparation_command(cmd))
-                .transpose()
-        };
+        self.finish().map(Some)
+    }
 
-        let conclusion_command = self.options.conclusion_command.as_ref().map(|values| {
-            let conclusion_command = if values.len() == 1 {
-                &values[0]
-            } else {
-                &values[self.number]
-            };
-            Command::new_parametrized(
-                None,
-                conclusion_command,
-                self.command.get_parameters().iter().cloned(),
-            )
-        });
-        let run_conclusion_command = || {
-            conclusion_command
-                .as_ref()
-                .map(|cmd| self.run_conclusion_command(cmd))
-                .transpose()
+    /// Run the setup command, the warmup runs and all timing runs
+    fn measure(&mut self) -> Result<()> {
+        self.setup()?;
+        if interrupt::is_interrupted() {
+            return Ok(());
+        }
+
+        self.warmup()?;
+        if interrupt::is_interrupted() {
+            return Ok(());
+        }
+
+        // Set up progress bar (and spinner for initial measurement)
+        let progress_bar = if self.options.output_style != OutputStyleOption::Disabled {
+            Some(get_progress_bar(
+                self.options.run_bounds.min,
+                "Initial time measurement",
+                self.options.output_style,
+            ))
+        } else {
+            None
         };
 
-        self.run_setup_command(self.command.get_parameters().iter().cloned())?;
+        self.initial_run()?;
 
-        // Warmup phase
-        if self.options.warmup_count > 0 {
-            let progress_bar = if self.options.output_style != OutputStyleOption::Disabled {
-                Some(get_progress_bar(
-                    self.options.warmup_count,
-                    "Performing warmup runs",
-                    self.options.output_style,
-                ))
-            } else {
-                None
-            };
+        // Re-configure the progress bar
+        if let Some(bar) = progress_bar.as_ref() {
+            bar.set_length(self.planned_runs())
+        }
+        if let Some(bar) = progress_bar.as_ref() {
+            bar.inc(1)
+        }
 
-            for _ in 0..self.options.warmup_count {
-                let _ = run_preparation_command()?;
-                let _ = self.executor.run_command_and_measure(self.command, None)?;
-                let _ = run_conclusion_command()?;
-                if let Some(bar) = progress_bar.as_ref() {
-                    bar.inc(1)
-                }
+        // Gather statistics (perform the actual benchmark)
+        while !self.is_complete() && !interrupt::is_interrupted() {
+            if let Some(bar) = progress_bar.as_ref() {
+                bar.set_message(self.current_estimate())
             }
+
+            self.timing_run()?;
+
             if let Some(bar) = progress_bar.as_ref() {
-                bar.finish_and_clear()
+                bar.set_length(self.planned_runs());
+                bar.inc(1)
             }
         }
 
-        // Set up progress bar (and spinner for initial measurement)
+        if let Some(bar) = progress_bar.as_ref() {
+            bar.finish_and_clear()
+        }
+
+        Ok(())
+    }
+
+    /// Position of the command in the list of benchmarked commands
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// Print the name of the benchmark
+    pub fn print_header(&self) {
+        if self.options.output_style != OutputStyleOption::Disabled {
+            println!(
+                "{}{}: {}",
+                "Benchmark ".bold(),
+                (self.number + 1).to_string().bold(),
+                self.command.get_name_with_unused_parameters(),
+            );
+        }
+    }
+
+    /// Run the `--setup` command
+    pub fn setup(&self) -> Result<()> {
+        self.events.emit(Event::BenchmarkStarted {
+            benchmark: self.number + 1,
+            command: &self.command.get_name(),
+            parameters: self
+                .command
+                .get_parameters()
+                .iter()
+                .map(|(name, value)| (*name, value.to_string()))
+                .collect(),
+        })?;
+
+        let result = self.run_setup_command(self.command.get_parameters().iter().cloned())?;
+        if self.options.setup_command.is_some() {
+            self.events.emit(Event::Setup {
+                benchmark: self.number + 1,
+                time_real: result.time_real,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Perform the warmup runs
+    pub fn warmup(&self) -> Result<()> {
+        if self.options.warmup_count == 0 {
+            return Ok(());
+        }
+
         let progress_bar = if self.options.output_style != OutputStyleOption::Disabled {
             Some(get_progress_bar(
-                self.options.run_bounds.min,
-                "Initial time measurement",
+                self.options.warmup_count,
+                "Performing warmup runs",
                 self.options.output_style,
             ))
         } else {
             None
         };
 
-        let preparation_result = run_preparation_command()?;
-        let preparation_overhead =
-            preparation_result.map_or(0.0, |res| res.time_real + self.executor.time_overhead());
+        for run in 1..=self.options.warmup_count {
+            let result = self.timed_command(Run::Warmup(run));
+            if interrupt::is_interrupted() {
+                break;
+            }
+            result?;
 
-        // Initial timing run
-        let (res, status) = self.executor.run_command_and_measure(self.command, None)?;
-        let success = status.success();
+            if let Some(bar) = progress_bar.as_ref() {
+                bar.inc(1)
+            }
+        }
+        if let Some(bar) = progress_bar.as_ref() {
+            bar.finish_and_clear()
+        }
+
+        Ok(())
+    }
+
+    /// Run the benchmarked command, surrounded by the `--prepare` and `--conclude` commands.
+    /// If hyperfine has been interrupted in the meantime, the result must be discarded.
+    fn timed_command(&self, run: Run) -> Result<TimedRun> {
+        let preparation = self.run_preparation_command_if_given()?;
+        if let Some(preparation) = &preparation {
+            self.events.emit(Event::Prepare {
+                benchmark: self.number + 1,
+                time_real: preparation.time_real,
+            })?;
+        }
+
+        let timestamp = unix_timestamp();
+        let (result, status) = self.executor.run_command_and_measure(&self.command, None)?;
+        self.events.emit(self.run_event(run, &result, status))?;
+
+        let conclusion = self.run_conclusion_command_if_given()?;
+        if let Some(conclusion) = &conclusion {
+            self.events.emit(Event::Conclude {
+                benchmark: self.number + 1,
+                time_real: conclusion.time_real,
+            })?;
+        }
+
+        Ok(TimedRun {
+            timestamp,
+            preparation,
+            result,
+            status,
+            conclusion,
+        })
+    }
+
+    /// Perform the first timing run and determine the total number of timing runs
+    /// (including this first one) from its result.
+    pub fn initial_run(&mut self) -> Result<()> {
+        let start = Instant::now();
+
+        let result = self.timed_command(Run::Timing(self.num_runs() + 1));
+        if interrupt::is_interrupted() {
+            return Ok(());
+        }
+        let run = result?;
 
-        let conclusion_result = run_conclusion_command()?;
-        let conclusion_overhead =
-            conclusion_result.map_or(0.0, |res| res.time_real + self.executor.time_overhead());
+        let preparation_overhead = run
+            .preparation
+            .map_or(0.0, |res| res.time_real + self.executor.time_overhead());
+        let conclusion_overhead = run
+            .conclusion
+            .map_or(0.0, |res| res.time_real + self.executor.time_overhead());
 
         // Determine number of benchmark runs
         let runs_in_min_time = (self.options.min_benchmarking_time
-            / (res.time_real
+            / (run.result.time_real
                 + self.executor.time_overhead()
                 + preparation_overhead
                 + conclusion_overhead)) as u64;
 
         let count = {
-            let min = cmp::max(runs_in_min_time, self.options.run_bounds.min);
+            let min = if self.options.target_precision.is_some() {
+                // In adaptive mode, the number of runs is determined by the precision
+                cmp::max(self.options.run_bounds.min, 2)
+            } else {
+                cmp::max(runs_in_min_time, self.options.run_bounds.min)
+            };
 
             self.options
                 .run_bounds
@@ -241,78 +460,202 @@ impl<'a> Benchmark<'a> {
                 .unwrap_or(min)
         };
 
-        let count_remaining = count - 1;
+        self.measurements.push(&run);
+        self.planned_runs = count;
+        self.time_spent += start.elapsed().as_secs_f64();
 
-        // Save the first result
-        times_real.push(res.time_real);
-        times_user.push(res.time_user);
-        times_system.push(res.time_system);
-        exit_codes.push(extract_exit_code(status));
+        Ok(())
+    }
 
-        all_succeeded = all_succeeded && success;
+    /// Perform a single timing run, including the `--prepare` and `--conclude` commands
+    pub fn timing_run(&mut self) -> Result<()> {
+        let start = Instant::now();
 
-        // Re-configure the progress bar
-        if let Some(bar) = progress_bar.as_ref() {
-            bar.set_length(count)
+        let result = self.timed_command(Run::Timing(self.num_runs() + 1));
+        if interrupt::is_interrupted() {
+            return Ok(());
         }
-        if let Some(bar) = progress_bar.as_ref() {
-            bar.inc(1)
+        self.measurements.push(&result?);
+
+        self.time_spent += start.elapsed().as_secs_f64();
+
+        if let Some(target) = self.options.target_precision {
+            self.planned_runs = self.adaptive_run_estimate(&target);
         }
 
-        // Gather statistics (perform the actual benchmark)
-        for _ in 0..count_remaining {
-            run_preparation_command()?;
+        Ok(())
+    }
 
-            let msg = {
-                let mean = format_duration(mean(&times_real), self.options.time_unit);
-                format!("Current estimate: {}", mean.to_string().green())
-            };
+    /// Event for the log that reports the result of a warmup or timing run
+    fn run_event(&self, run: Run, result: &TimingResult, status: ExitStatus) -> Event<'static> {
+        let benchmark = self.number + 1;
+        let exit_code = extract_exit_code(status);
+
+        match run {
+            Run::Warmup(run) => Event::WarmupRun {
+                benchmark,
+                run,
+                time_real: result.time_real,
+                exit_code,
+            },
+            Run::Timing(run) => Event::TimingRun {
+                benchmark,
+                run,
+                time_real: result.time_real,
+                time_user: result.time_user,
+                time_system: result.time_system,
+                exit_code,
+                timed_out: result.timed_out,
+            },
+        }
+    }
 
-            if let Some(bar) = progress_bar.as_ref() {
-                bar.set_message(msg.to_owned())
-            }
+    /// Number of timing runs that have been performed so far
+    pub fn num_runs(&self) -> u64 {
+        self.measurements.times_real.len() as u64
+    }
 
-            let (res, status) = self.executor.run_command_and_measure(self.command, None)?;
-            let success = status.success();
+    /// Current estimate for the total number of timing runs
+    pub fn planned_runs(&self) -> u64 {
+        if self.is_complete() {
+            self.num_runs()
+        } else {
+            cmp::max(self.planned_runs, self.num_runs() + 1)
+        }
+    }
 
-            times_real.push(res.time_real);
-            times_user.push(res.time_user);
-            times_system.push(res.time_system);
-            exit_codes.push(extract_exit_code(status));
+    /// Relative half width of the confidence interval of the statistic that is used for
+    /// `--target-precision`, based on all runs so far
+    fn achieved_precision(&self, statistic: PrecisionStatistic) -> Option<f64> {
+        let times = &self.measurements.times_real;
+        let level = self.options.confidence_level;
+        match statistic {
+            PrecisionStatistic::Mean => relative_mean_ci_half_width(times, level),
+            PrecisionStatistic::Median => relative_median_ci_half_width(times, level),
+        }
+    }
 
-            all_succeeded = all_succeeded && success;
+    /// Estimate the total number of runs needed to reach the target precision. The width of
+    /// the confidence interval shrinks with the square root of the number of runs.
+    fn adaptive_run_estimate(&self, target: &TargetPrecision) -> u64 {
+        let n = self.num_runs();
 
-            if let Some(bar) = progress_bar.as_ref() {
-                bar.inc(1)
+        let needed = match self.achieved_precision(target.statistic) {
+            Some(precision) => {
+                (n as f64 * (precision / target.relative_half_width).powi(2)).ceil() as u64
             }
+            None => n + 1,
+        };
+        let needed = cmp::max(needed, cmp::max(self.options.run_bounds.min, 2));
 
-            run_conclusion_command()?;
+        let average_time = self.time_spent / n as f64;
+        let affordable = if average_time > 0.0 {
+            n + ((target.time_budget - self.time_spent).max(0.0) / average_time).ceil() as u64
+        } else {
+            needed
+        };
+
+        let mut estimate = cmp::min(needed, affordable);
+        if let Some(max) = self.options.run_bounds.max {
+            estimate = cmp::min(estimate, max);
         }
+        estimate
+    }
 
-        if let Some(bar) = progress_bar.as_ref() {
-            bar.finish_and_clear()
+    /// Whether all timing runs have been performed
+    pub fn is_complete(&self) -> bool {
+        let n = self.num_runs();
+
+        let target = match self.options.target_precision {
+            Some(target) => target,
+            None => return n >= self.planned_runs,
+        };
+
+        // --max-runs is a hard limit, while --min-runs takes precedence over the time budget
+        if self.options.run_bounds.max.is_some_and(|max| n >= max) {
+            return true;
+        }
+        if n < cmp::max(self.options.run_bounds.min, 2) {
+            return false;
         }
+        if self.time_spent >= target.time_budget {
+            return true;
+        }
+
+        self.achieved_precision(target.statistic)
+            .is_some_and(|precision| precision <= target.relative_half_width)
+    }
+
+    /// Progress bar message showing the mean of all runs so far
+    pub fn current_estimate(&self) -> String {
+        let mean = format_duration(mean(&self.measurements.times_real), self.options.time_unit);
+
+        match self
+            .options
+            .target_precision
+            .and_then(|target| self.achieved_precision(target.statistic))
+        {
+            Some(precision) => format!(
+                "Current estimate: {} ± {:.1}%",
+                mean.to_string().green(),
+                precision * 100.0
+            ),
+            None => format!("Current estimate: {}", mean.to_string().green()),
+        }
+    }
+
+    /// Compute and print the statistics of all timing runs, and run the `--cleanup` command
+    pub fn finish(&self) -> Result<BenchmarkResult> {
+        let Measurements {
+            times_real,
+            times_user,
+            times_system,
+            timestamps,
+            resource_usages,
+            exit_codes,
+            timed_out,
+            any_failed,
+        } = &self.measurements;
 
         // Compute statistical quantities
         let t_num = times_real.len();
-        let t_mean = mean(&times_real);
+        let t_mean = mean(times_real);
         let t_stddev = if times_real.len() > 1 {
-            Some(standard_deviation(&times_real, Some(t_mean)))
+            Some(standard_deviation(times_real, Some(t_mean)))
         } else {
             None
         };
-        let t_median = median(&times_real);
-        let t_min = min(&times_real);
-        let t_max = max(&times_real);
+        let t_median = median(times_real);
+        let t_min = min(times_real);
+        let t_max = max(times_real);
+
+        let user_mean = mean(times_user);
+        let system_mean = mean(times_system);
+
+        let resource_usage = ResourceUsageSummary::from_runs(resource_usages);
 
-        let user_mean = mean(&times_user);
-        let system_mean = mean(&times_system);
+        let extended_statistics = ExtendedStatistics::from_times(times_real, t_mean, t_stddev);
+
+        let confidence_intervals = ConfidenceIntervals::from_times(
+            times_real,
+            self.options.confidence_level,
+            self.options.bootstrap_resamples,
+        );
 
         // Formatting and console output
         let (mean_str, time_unit) = format_duration_unit(t_mean, self.options.time_unit);
         let min_str = format_duration(t_min, Some(time_unit));
         let max_str = format_duration(t_max, Some(time_unit));
-        let num_str = format!("{t_num} runs");
+        let achieved_precision = self
+            .options
+            .target_precision
+            .map(|target| (target, self.achieved_precision(target.statistic)));
+        let num_str = match achieved_precision {
+            Some((_, Some(precision))) => {
+                format!("{t_num} runs, ±{:.1}%", precision * 100.0)
+            }
+            _ => format!("{t_num} runs"),
+        };
 
         let user_str = format_duration(user_mean, Some(time_unit));
         let system_str = format_duration(system_mean, Some(time_unit));
@@ -349,6 +692,62 @@ impl<'a> Benchmark<'a> {
                     num_str.dimmed()
                 );
             }
+
+            if let Some(ci) = &confidence_intervals {
+                let level_str = format_percentage(ci.level);
+                let padding = " ".repeat(10_usize.saturating_sub(level_str.len()).max(1));
+                println!(
+                    "  {} CI ({}):{}{:>8} … {:>8}    [Median: {} … {}]",
+                    level_str,
+                    "mean".green(),
+                    padding,
+                    format_duration(ci.mean.lower, Some(time_unit)).green(),
+                    format_duration(ci.mean.upper, Some(time_unit)).green(),
+                    format_duration(ci.median.lower, Some(time_unit)).blue(),
+                    format_duration(ci.median.upper, Some(time_unit)).blue(),
+                );
+            }
+
+            if let Some(statistics) = extended_statistics
+                .as_ref()
+                .filter(|_| self.options.show_percentiles)
+            {
+                println!(
+                    "  P5 … P95:            {:>8} … {:>8}    [P99: {}]",
+                    format_duration(statistics.p5, Some(time_unit)).cyan(),
+                    format_duration(statistics.p95, Some(time_unit)).purple(),
+                    format_duration(statistics.p99, Some(time_unit)).purple(),
+                );
+                println!(
+                    "  P25 … P75:           {:>8} … {:>8}    [IQR: {}, CoV: {}]",
+                    format_duration(statistics.p25, Some(time_unit)).cyan(),
+                    format_duration(statistics.p75, Some(time_unit)).purple(),
+                    format_duration(statistics.iqr, Some(time_unit)),
+                    statistics
+                        .coefficient_of_variation
+                        .map(format_percentage_precise)
+                        .unwrap_or_else(|| "-".into()),
+                );
+            }
+
+            if let Some(usage) = &resource_usage {
+                println!(
+                    "  Memory ({} … {}): {:>8} … {:>8}    [Page faults: {:.0} minor, {:.0} major]",
+                    "mean".green(),
+                    "max".purple(),
+                    format_bytes(usage.max_rss_mean).green(),
+                    format_bytes(usage.max_rss_max as f64).purple(),
+                    usage.minor_page_faults,
+                    usage.major_page_faults,
+                );
+                println!(
+                    "  Context switches:    {:>8.0} voluntary, {:.0} involuntary    [Block I/O: {:.0} in, {:.0} out]",
+                    usage.voluntary_context_switches,
+                    usage.involuntary_context_switches,
+                    usage.block_input_operations,
+                    usage.block_output_operations,
+                );
+            }
         }
 
         // Warnings
@@ -362,12 +761,34 @@ impl<'a> Benchmark<'a> {
         }
 
         // Check program exit codes
-        if !all_succeeded {
+        let num_timed_out = timed_out.iter().filter(|&&t| t).count();
+        if num_timed_out > 0 {
+            warnings.push(Warnings::TimedOut(num_timed_out));
+        } else if *any_failed {
             warnings.push(Warnings::NonZeroExitCode);
         }
 
+        // Check whether all runs have been performed
+        if !self.is_complete() {
+            warnings.push(Warnings::Interrupted(t_num as u64, self.planned_runs()));
+        }
+
+        // Check whether the target precision has been reached
+        let precision = achieved_precision.map(|(target, achieved)| PrecisionOutcome {
+            target: target.relative_half_width,
+            achieved,
+        });
+        if let Some(precision) = precision.filter(|_| self.is_complete()) {
+            if !precision.is_reached() {
+                warnings.push(Warnings::TargetPrecisionNotReached(
+                    precision.achieved,
+                    precision.target,
+                ));
+            }
+        }
+
         // Run outlier detection
-        let scores = modified_zscores(&times_real);
+        let scores = modified_zscores(times_real);
 
         let outlier_warning_options = OutlierWarningOptions {
             warmup_in_use: self.options.warmup_count > 0,
@@ -401,11 +822,20 @@ impl<'a> Benchmark<'a> {
             println!(" ");
         }
 
-        self.run_cleanup_command(self.command.get_parameters().iter().cloned())?;
+        let cleanup_result =
+            self.run_cleanup_command(self.command.get_parameters().iter().cloned())?;
+        if self.options.cleanup_command.is_some() {
+            self.events.emit(Event::Cleanup {
+                benchmark: self.number + 1,
+                time_real: cleanup_result.time_real,
+            })?;
+        }
 
-        Ok(BenchmarkResult {
+        let command_line = self.command.get_command_line();
+        let result = BenchmarkResult {
             command: self.command.get_name(),
             command_with_unused_parameters: self.command.get_name_with_unused_parameters(),
+            command_line: Some(command_line).filter(|line| *line != self.command.get_name()),
             mean: t_mean,
             stddev: t_stddev,
             median: t_median,
@@ -413,14 +843,31 @@ impl<'a> Benchmark<'a> {
             system: system_mean,
             min: t_min,
             max: t_max,
-            times: Some(times_real),
-            exit_codes,
+            extended_statistics,
+            confidence_intervals,
+            precision,
+            times: Some(times_real.clone()),
+            times_user: Some(times_user.clone()),
+            times_system: Some(times_system.clone()),
+            timestamps: Some(timestamps.clone()),
+            resource_usage,
+            exit_codes: exit_codes.clone(),
+            timed_out: timed_out.clone(),
+            incomplete: !self.is_complete(),
+            baseline: None,
             parameters: self
                 .command
                 .get_parameters()
                 .iter()
                 .map(|(name, value)| (name.to_string(), value.to_string()))
                 .collect(),
-        })
+        };
+
+        self.events.emit(Event::BenchmarkFinished {
+            benchmark: self.number + 1,
+            result: &result,
+        })?;
+
+        Ok(result)
     }
 }
@@ -1,16 +1,71 @@
 use std::cmp::Ordering;
 
-use super::benchmark_result::BenchmarkResult;
-use crate::{options::SortOrder, util::units::Scalar};
+use super::benchmark_result::{BenchmarkResult, ConfidenceInterval};
+use crate::options::{SignificanceTest, SortOrder};
+use crate::util::statistics::{bootstrap_ratio_of_means, mann_whitney_u_test, welch_t_test};
+use crate::util::units::Scalar;
+
+/// Differences with a p-value below this level are considered statistically significant
+pub const SIGNIFICANCE_LEVEL: Scalar = 0.05;
+
+/// Result of a significance test between a benchmark and the reference
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Significance {
+    pub test: SignificanceTest,
+    pub p_value: Scalar,
+}
+
+impl Significance {
+    /// Compare the timing samples of both results. Returns `None` if there are not enough samples.
+    pub fn compute(
+        test: SignificanceTest,
+        result: &BenchmarkResult,
+        reference: &BenchmarkResult,
+    ) -> Option<Self> {
+        let xs = result.times.as_ref()?;
+        let ys = reference.times.as_ref()?;
+
+        let p_value = match test {
+            SignificanceTest::Welch => welch_t_test(xs, ys),
+            SignificanceTest::MannWhitney => mann_whitney_u_test(xs, ys),
+        }?;
+
+        Some(Significance { test, p_value })
+    }
+
+    pub fn is_significant(&self) -> bool {
+        self.p_value < SIGNIFICANCE_LEVEL
+    }
+}
+
+/// Bootstrap confidence interval of `result.mean / reference.mean`
+fn ratio_confidence_interval(
+    result: &BenchmarkResult,
+    reference: &BenchmarkResult,
+) -> Option<ConfidenceInterval> {
+    let ci = result.confidence_intervals.as_ref()?;
+    bootstrap_ratio_of_means(
+        result.times.as_ref()?,
+        reference.times.as_ref()?,
+        ci.level,
+        ci.resamples,
+    )
+    .map(ConfidenceInterval::from)
+}
 
 #[derive(Debug)]
 pub struct BenchmarkResultWithRelativeSpeed<'a> {
     pub result: &'a BenchmarkResult,
     pub relative_speed: Scalar,
     pub relative_speed_stddev: Option<Scalar>,
+    /// Bootstrap confidence interval of the relative speed (same level and number of
+    /// resamples as the confidence intervals of the individual results)
+    pub relative_speed_ci: Option<ConfidenceInterval>,
     pub is_reference: bool,
     // Less means faster
     pub relative_ordering: Ordering,
+    /// Significance of the difference to the reference (`None` for the reference itself)
+    pub significance: Option<Significance>,
 }
 
 pub fn compare_mean_time(l: &BenchmarkResult, r: &BenchmarkResult) -> Ordering {
@@ -28,20 +83,28 @@ fn compute_relative_speeds<'a>(
     results: &'a [BenchmarkResult],
     reference: &'a BenchmarkResult,
     sort_order: SortOrder,
+    significance_test: SignificanceTest,
 ) -> Vec<BenchmarkResultWithRelativeSpeed<'a>> {
     let mut results: Vec<_> = results
         .iter()
         .map(|result| {
             let is_reference = result == reference;
             let relative_ordering = compare_mean_time(result, reference);
+            let significance = if is_reference {
+                None
+            } else {
+                Significance::compute(significance_test, result, reference)
+            };
 
             if result.mean == 0.0 {
                 return BenchmarkResultWithRelativeSpeed {
                     result,
                     relative_speed: if is_reference { 1.0 } else { f64::INFINITY },
                     relative_speed_stddev: None,
+                    relative_speed_ci: None,
                     is_reference,
                     relative_ordering,
+                    significance,
                 };
             }
 
@@ -63,12 +126,26 @@ fn compute_relative_speeds<'a>(
                 _ => None,
             };
 
+            let ratio_ci = if is_reference {
+                None
+            } else {
+                ratio_confidence_interval(result, reference).map(|ci| match relative_ordering {
+                    Ordering::Less => ConfidenceInterval {
+                        lower: 1.0 / ci.upper,
+                        upper: 1.0 / ci.lower,
+                    },
+                    _ => ci,
+                })
+            };
+
             BenchmarkResultWithRelativeSpeed {
                 result,
                 relative_speed: ratio,
                 relative_speed_stddev: ratio_stddev,
+                relative_speed_ci: ratio_ci,
                 is_reference,
                 relative_ordering,
+                significance,
             }
         })
         .collect();
@@ -83,21 +160,38 @@ fn compute_relative_speeds<'a>(
     results
 }
 
+/// The result that all other results are compared with: the reference command, which is
+/// always benchmarked first (see `--reference`), or the fastest command otherwise
+pub fn reference_of(results: &[BenchmarkResult], has_reference_command: bool) -> &BenchmarkResult {
+    if has_reference_command {
+        &results[0]
+    } else {
+        fastest_of(results)
+    }
+}
+
 pub fn compute_with_check_from_reference<'a>(
     results: &'a [BenchmarkResult],
     reference: &'a BenchmarkResult,
     sort_order: SortOrder,
+    significance_test: SignificanceTest,
 ) -> Option<Vec<BenchmarkResultWithRelativeSpeed<'a>>> {
     if fastest_of(results).mean == 0.0 || reference.mean == 0.0 {
         return None;
     }
 
-    Some(compute_relative_speeds(results, reference, sort_order))
+    Some(compute_relative_speeds(
+        results,
+        reference,
+        sort_order,
+        significance_test,
+    ))
 }
 
 pub fn compute_with_check(
     results: &[BenchmarkResult],
     sort_order: SortOrder,
+    significance_test: SignificanceTest,
 ) -> Option<Vec<BenchmarkResultWithRelativeSpeed>> {
     let fastest = fastest_of(results);
 
@@ -105,26 +199,53 @@ pub fn compute_with_check(
         return None;
     }
 
-    Some(compute_relative_speeds(results, fastest, sort_order))
+    Some(compute_relative_speeds(
+        results,
+        fastest,
+        sort_order,
+        significance_test,
+    ))
 }
 
 /// Same as compute_with_check, potentially resulting in relative speeds of infinity
 pub fn compute(
     results: &[BenchmarkResult],
     sort_order: SortOrder,
+    significance_test: SignificanceTest,
 ) -> Vec<BenchmarkResultWithRelativeSpeed> {
     let fastest = fastest_of(results);
 
-    compute_relative_speeds(results, fastest, sort_order)
+    compute_relative_speeds(results, fastest, sort_order, significance_test)
+}
+
+/// Same as compute_with_check_from_reference, potentially resulting in relative speeds of
+/// infinity
+pub fn compute_from_reference<'a>(
+    results: &'a [BenchmarkResult],
+    reference: &'a BenchmarkResult,
+    sort_order: SortOrder,
+    significance_test: SignificanceTest,
+) -> Vec<BenchmarkResultWithRelativeSpeed<'a>> {
+    compute_relative_speeds(results, reference, sort_order, significance_test)
 }
 
 #[cfg(test)]
 fn create_result(name: &str, mean: Scalar) -> BenchmarkResult {
+    create_result_with_times(name, mean, None)
+}
+
+#[cfg(test)]
+fn create_result_with_times(
+    name: &str,
+    mean: Scalar,
+    times: Option<Vec<Scalar>>,
+) -> BenchmarkResult {
     use std::collections::BTreeMap;
 
     BenchmarkResult {
         command: name.into(),
         command_with_unused_parameters: name.into(),
+        command_line: None,
         mean,
         stddev: Some(1.0),
         median: mean,
@@ -132,8 +253,18 @@ fn create_result(name: &str, mean: Scalar) -> BenchmarkResult {
         system: 0.0,
         min: mean,
         max: mean,
-        times: None,
+        extended_statistics: None,
+        confidence_intervals: None,
+        precision: None,
+        times,
+        times_user: None,
+        times_system: None,
+        timestamps: None,
+        resource_usage: None,
         exit_codes: Vec::new(),
+        timed_out: vec![],
+        incomplete: false,
+        baseline: None,
         parameters: BTreeMap::new(),
     }
 }
@@ -148,7 +279,8 @@ fn test_compute_relative_speed() {
         create_result("cmd3", 5.0),
     ];
 
-    let annotated_results = compute_with_check(&results, SortOrder::Command).unwrap();
+    let annotated_results =
+        compute_with_check(&results, SortOrder::Command, SignificanceTest::Welch).unwrap();
 
     assert_relative_eq!(1.5, annotated_results[0].relative_speed);
     assert_relative_eq!(1.0, annotated_results[1].relative_speed);
@@ -162,8 +294,13 @@ fn test_compute_relative_speed_with_reference() {
     let results = vec![create_result("cmd2", 2.0), create_result("cmd3", 5.0)];
     let reference = create_result("cmd2", 4.0);
 
-    let annotated_results =
-        compute_with_check_from_reference(&results, &reference, SortOrder::Command).unwrap();
+    let annotated_results = compute_with_check_from_reference(
+        &results,
+        &reference,
+        SortOrder::Command,
+        SignificanceTest::Welch,
+    )
+    .unwrap();
 
     assert_relative_eq!(2.0, annotated_results[0].relative_speed);
     assert_relative_eq!(1.25, annotated_results[1].relative_speed);
@@ -173,7 +310,64 @@ fn test_compute_relative_speed_with_reference() {
 fn test_compute_relative_speed_for_zero_times() {
     let results = vec![create_result("cmd1", 1.0), create_result("cmd2", 0.0)];
 
-    let annotated_results = compute_with_check(&results, SortOrder::Command);
+    let annotated_results =
+        compute_with_check(&results, SortOrder::Command, SignificanceTest::Welch);
 
     assert!(annotated_results.is_none());
 }
+
+#[test]
+fn test_compute_significance() {
+    let results = vec![
+        create_result_with_times("cmd1", 2.0, Some(vec![1.9, 2.0, 2.1, 2.0])),
+        create_result_with_times("cmd2", 2.01, Some(vec![1.91, 2.01, 2.11, 2.01])),
+        create_result_with_times("cmd3", 3.0, Some(vec![2.9, 3.0, 3.1, 3.0])),
+        create_result("cmd4", 4.0),
+    ];
+
+    for test in [SignificanceTest::Welch, SignificanceTest::MannWhitney] {
+        let annotated_results = compute_with_check(&results, SortOrder::Command, test).unwrap();
+
+        assert!(annotated_results[0].significance.is_none());
+        assert!(!annotated_results[1].significance.unwrap().is_significant());
+        assert!(annotated_results[2].significance.unwrap().is_significant());
+        assert!(annotated_results[3].significance.is_none());
+    }
+}
+
+#[test]
+fn test_compute_relative_speed_confidence_interval() {
+    use super::benchmark_result::ConfidenceIntervals;
+
+    let with_intervals = |name, times: Vec<Scalar>| {
+        let mean = times.iter().sum::<Scalar>() / times.len() as Scalar;
+        let mut result = create_result_with_times(name, mean, Some(times.clone()));
+        result.confidence_intervals = ConfidenceIntervals::from_times(&times, 0.95, 1000);
+        result
+    };
+
+    let results = vec![
+        with_intervals("cmd1", vec![2.0, 2.1, 1.9, 2.0]),
+        with_intervals("cmd2", vec![1.0, 1.1, 0.9, 1.0]),
+        create_result("cmd3", 3.0),
+    ];
+
+    let annotated_results =
+        compute_with_check(&results, SortOrder::Command, SignificanceTest::Welch).unwrap();
+
+    let ci = annotated_results[0].relative_speed_ci.unwrap();
+    assert!(ci.lower < 2.0 && ci.upper > 2.0);
+    assert!(annotated_results[1].relative_speed_ci.is_none());
+    assert!(annotated_results[2].relative_speed_ci.is_none());
+
+    // Intervals are inverted if the command is faster than the reference
+    let annotated_results = compute_with_check_from_reference(
+        &results,
+        &results[0],
+        SortOrder::Command,
+        SignificanceTest::Welch,
+    )
+    .unwrap();
+    let inverted = annotated_results[1].relative_speed_ci.unwrap();
+    assert!(inverted.lower < 2.0 && inverted.upper > 2.0);
+}
@@ -1,20 +1,31 @@
+use super::baseline::{Baseline, Verdict};
 use super::benchmark_result::BenchmarkResult;
 use super::executor::{Executor, MockExecutor, RawExecutor, ShellExecutor};
+use super::relative_speed::BenchmarkResultWithRelativeSpeed;
 use super::{relative_speed, Benchmark};
 use colored::*;
 use std::cmp::Ordering;
 
 use crate::command::{Command, Commands};
-use crate::export::ExportManager;
-use crate::options::{ExecutorKind, Options, OutputStyleOption, SortOrder};
+use crate::export::{Event, ExportManager};
+use crate::options::{ExecutionOrder, ExecutorKind, Options, OutputStyleOption, SortOrder};
+use crate::output::format::{
+    format_duration, format_duration_unit, format_p_value, format_percentage,
+};
+use crate::output::progress_bar::get_progress_bar;
+use crate::util::interrupt;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use indicatif::ProgressBar;
+use rand::seq::SliceRandom;
 
 pub struct Scheduler<'a> {
     commands: &'a Commands<'a>,
     options: &'a Options,
     export_manager: &'a ExportManager,
     results: Vec<BenchmarkResult>,
+    baseline: Option<Baseline>,
+    resumed_results: Vec<BenchmarkResult>,
 }
 
 impl<'a> Scheduler<'a> {
@@ -28,9 +39,64 @@ impl<'a> Scheduler<'a> {
             options,
             export_manager,
             results: vec![],
+            baseline: None,
+            resumed_results: vec![],
         }
     }
 
+    /// Reuse the given results of an interrupted session (see `--resume`) instead of
+    /// benchmarking the corresponding commands again
+    pub fn resume_from(&mut self, results: Vec<BenchmarkResult>) {
+        self.resumed_results = results.into_iter().filter(|r| !r.incomplete).collect();
+    }
+
+    /// Compare the new results with the given baseline (see `--baseline`)
+    pub fn compare_with(&mut self, baseline: Baseline) {
+        self.baseline = Some(baseline);
+    }
+
+    /// Take the result for the given command (command line and parameters) from the resumed
+    /// results
+    fn take_resumed_result(&mut self, cmd: &Command) -> Option<BenchmarkResult> {
+        let command_line = cmd.get_command_line();
+        let parameters = cmd.get_parameters();
+
+        let index = self.resumed_results.iter().position(|result| {
+            result.command_line() == command_line
+                && result.parameters.len() == parameters.len()
+                && parameters.iter().all(|(param_name, value)| {
+                    result.parameters.get(*param_name) == Some(&value.to_string())
+                })
+        })?;
+
+        // The command might have been renamed with '--command-name' in the meantime
+        let mut result = self.resumed_results.remove(index);
+        result.command = cmd.get_name();
+        result.command_with_unused_parameters = cmd.get_name_with_unused_parameters();
+        result.command_line = Some(command_line).filter(|line| *line != result.command);
+        Some(result)
+    }
+
+    /// Print a note that a command is skipped because its result has been resumed
+    fn print_resumed(&self, benchmark: &Benchmark, result: &BenchmarkResult) {
+        if self.options.output_style == OutputStyleOption::Disabled {
+            return;
+        }
+
+        benchmark.print_header();
+        println!(
+            "  {}",
+            format!(
+                "Skipped, result taken from '{}' (mean: {}, {} runs)",
+                self.options.resume_file.as_deref().unwrap_or_default(),
+                format_duration(result.mean, self.options.time_unit),
+                result.times.as_ref().map_or(0, |times| times.len())
+            )
+            .dimmed()
+        );
+        println!(" ");
+    }
+
     pub fn run_benchmarks(&mut self) -> Result<()> {
         let mut executor: Box<dyn Executor> = match self.options.executor_kind {
             ExecutorKind::Raw => Box::new(RawExecutor::new(self.options)),
@@ -45,23 +111,192 @@ impl<'a> Scheduler<'a> {
             .map(|cmd| Command::new(None, cmd));
 
         executor.calibrate()?;
+        self.export_manager.events().emit(Event::Calibration {
+            time_overhead: executor.time_overhead(),
+        })?;
 
-        for (number, cmd) in reference.iter().chain(self.commands.iter()).enumerate() {
-            self.results
-                .push(Benchmark::new(number, cmd, self.options, &*executor).run()?);
+        let commands = reference.iter().chain(self.commands.iter());
 
-            // We export results after each individual benchmark, because
-            // we would risk losing them if a later benchmark fails.
-            self.export_manager.write_results(
-                &self.results,
-                self.options.sort_order_exports,
-                true,
-            )?;
+        if self.options.execution_order != ExecutionOrder::Sequential {
+            return self.run_benchmarks_interleaved(commands, &*executor);
+        }
+
+        for (number, cmd) in commands.enumerate() {
+            if interrupt::is_interrupted() {
+                break;
+            }
+
+            let mut benchmark = Benchmark::new(
+                number,
+                cmd,
+                self.options,
+                &*executor,
+                self.export_manager.events(),
+            );
+
+            if let Some(result) = self.take_resumed_result(cmd) {
+                self.print_resumed(&benchmark, &result);
+                self.add_result(result)?;
+            } else if let Some(result) = benchmark.run()? {
+                self.add_result(result)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Compare a new result with the baseline (if any), and add it to the list of results
+    fn add_result(&mut self, mut result: BenchmarkResult) -> Result<()> {
+        result.baseline = self
+            .baseline
+            .as_ref()
+            .and_then(|baseline| baseline.compare(&result));
+        self.results.push(result);
+
+        // We export results after each individual benchmark, because
+        // we would risk losing them if a later benchmark fails.
+        self.export_manager
+            .write_results(&self.results, self.options.sort_order_exports, true)
+    }
+
+    /// Run the benchmarks such that the timing runs of the different commands alternate,
+    /// either in round-robin or in random order. Setup, warmup and the initial timing run
+    /// (which determines the number of runs) are still performed for one command after
+    /// the other.
+    fn run_benchmarks_interleaved<'b>(
+        &mut self,
+        commands: impl Iterator<Item = &'b Command<'b>>,
+        executor: &'b dyn Executor,
+    ) -> Result<()>
+    where
+        'a: 'b,
+    {
+        let mut benchmarks = vec![];
+        let mut results = vec![];
+
+        for (number, cmd) in commands.enumerate() {
+            let mut benchmark = Benchmark::new(
+                number,
+                cmd,
+                self.options,
+                executor,
+                self.export_manager.events(),
+            );
+
+            if let Some(result) = self.take_resumed_result(cmd) {
+                self.print_resumed(&benchmark, &result);
+                results.push((number, result));
+                continue;
+            }
+
+            let result = benchmark.setup();
+            if interrupt::is_interrupted() {
+                break;
+            }
+            result?;
+            benchmark.warmup()?;
+            if interrupt::is_interrupted() {
+                break;
+            }
+
+            let progress_bar = self.get_progress_bar(1, "Initial time measurement");
+            benchmark.initial_run()?;
+            if let Some(bar) = progress_bar.as_ref() {
+                bar.finish_and_clear()
+            }
+
+            benchmarks.push(benchmark);
+        }
+
+        let planned_runs =
+            |benchmarks: &[Benchmark]| -> u64 { benchmarks.iter().map(|b| b.planned_runs()).sum() };
+
+        let progress_bar =
+            self.get_progress_bar(planned_runs(&benchmarks), "Performing interleaved runs");
+        if let Some(bar) = progress_bar.as_ref() {
+            bar.inc(benchmarks.len() as u64)
+        }
+
+        // Schedule all remaining timing runs, one round after the other. The number of runs
+        // can only grow with '--target-precision', in which case the runs that turn out to be
+        // needed in addition are scheduled once the current schedule has been performed.
+        while !interrupt::is_interrupted() {
+            let remaining: Vec<u64> = benchmarks
+                .iter()
+                .map(|b| b.planned_runs() - b.num_runs())
+                .collect();
+            let max_remaining = remaining.iter().copied().max().unwrap_or(0);
+            let mut schedule: Vec<usize> = (0..max_remaining)
+                .flat_map(|round| {
+                    remaining
+                        .iter()
+                        .enumerate()
+                        .filter(move |(_, &count)| round < count)
+                        .map(|(index, _)| index)
+                })
+                .collect();
+            if schedule.is_empty() {
+                break;
+            }
+
+            if self.options.execution_order == ExecutionOrder::Random {
+                schedule.shuffle(&mut rand::thread_rng());
+            }
+
+            for index in schedule {
+                if interrupt::is_interrupted() {
+                    break;
+                }
+
+                let benchmark = &mut benchmarks[index];
+                if benchmark.is_complete() {
+                    continue;
+                }
+
+                if let Some(bar) = progress_bar.as_ref() {
+                    bar.set_message(format!(
+                        "Benchmark {}: {}",
+                        index + 1,
+                        benchmark.current_estimate()
+                    ))
+                }
+
+                benchmark.timing_run()?;
+
+                if let Some(bar) = progress_bar.as_ref() {
+                    bar.set_length(planned_runs(&benchmarks));
+                    bar.inc(1)
+                }
+            }
+        }
+
+        if let Some(bar) = progress_bar.as_ref() {
+            bar.finish_and_clear()
+        }
+
+        // Benchmarks that have been interrupted during the initial run have no results
+        for benchmark in benchmarks.iter().filter(|b| b.num_runs() > 0) {
+            benchmark.print_header();
+            results.push((benchmark.number(), benchmark.finish()?));
+        }
+
+        // Keep the order of the commands, including the resumed ones
+        results.sort_by_key(|(number, _)| *number);
+        for (_, result) in results {
+            self.add_result(result)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_progress_bar(&self, length: u64, msg: &str) -> Option<ProgressBar> {
+        if self.options.output_style != OutputStyleOption::Disabled {
+            Some(get_progress_bar(length, msg, self.options.output_style))
+        } else {
+            None
+        }
+    }
+
     pub fn print_relative_speed_comparison(&self) {
         if self.options.output_style == OutputStyleOption::Disabled {
             return;
@@ -71,17 +306,14 @@ impl<'a> Scheduler<'a> {
             return;
         }
 
-        let reference = self
-            .options
-            .reference_command
-            .as_ref()
-            .map(|_| &self.results[0])
-            .unwrap_or_else(|| relative_speed::fastest_of(&self.results));
+        let reference =
+            relative_speed::reference_of(&self.results, self.options.reference_command.is_some());
 
         if let Some(annotated_results) = relative_speed::compute_with_check_from_reference(
             &self.results,
             reference,
             self.options.sort_order_speed_comparison,
+            self.options.significance_test,
         ) {
             match self.options.sort_order_speed_comparison {
                 SortOrder::MeanTime => {
@@ -119,9 +351,10 @@ impl<'a> Scheduler<'a> {
                             ),
                         };
                         println!(
-                            "{} {}",
+                            "{} {}{}",
                             comparator,
-                            &item.result.command_with_unused_parameters.magenta()
+                            &item.result.command_with_unused_parameters.magenta(),
+                            comparison_annotation(item)
                         );
                     }
                 }
@@ -130,7 +363,7 @@ impl<'a> Scheduler<'a> {
 
                     for item in annotated_results {
                         println!(
-                            "  {}{}  {}",
+                            "  {}{}  {}{}",
                             format!("{:10.2}", item.relative_speed).bold().green(),
                             if item.is_reference {
                                 "        ".into()
@@ -140,6 +373,7 @@ impl<'a> Scheduler<'a> {
                                 "        ".into()
                             },
                             &item.result.command_with_unused_parameters,
+                            comparison_annotation(&item)
                         );
                     }
                 }
@@ -157,8 +391,125 @@ impl<'a> Scheduler<'a> {
         }
     }
 
+    pub fn print_baseline_comparison(&self) {
+        if self.options.output_style == OutputStyleOption::Disabled || self.baseline.is_none() {
+            return;
+        }
+
+        if self.results.len() > 1 {
+            // Separate from the relative speed comparison
+            println!(" ");
+        }
+        println!("{}", "Comparison with baseline".bold());
+
+        for result in &self.results {
+            let comparison = match &result.baseline {
+                Some(comparison) => comparison,
+                None => {
+                    println!(
+                        "  {}: {}",
+                        result.command_with_unused_parameters,
+                        "not found in baseline".dimmed()
+                    );
+                    continue;
+                }
+            };
+
+            let (baseline_str, unit) =
+                format_duration_unit(comparison.baseline_mean, self.options.time_unit);
+            let current_str = format_duration(result.mean, Some(unit));
+            let change_str = format!("{:+.1}%", comparison.relative_change * 100.0);
+            let verdict_str = match comparison.verdict {
+                Verdict::Regression => change_str.red().bold(),
+                Verdict::Improvement => change_str.green().bold(),
+                Verdict::Unchanged => change_str.normal(),
+            };
+            let p_value_str = comparison
+                .p_value
+                .map(|p| format!(", {}", format_p_value(p)))
+                .unwrap_or_default();
+            let verdict_label = match comparison.verdict {
+                Verdict::Regression => "regression".red(),
+                Verdict::Improvement => "improvement".green(),
+                Verdict::Unchanged => "no significant change".dimmed(),
+            };
+
+            println!(
+                "  {:>8}  {:>9} → {:>9}    {} ({}{})",
+                verdict_str,
+                baseline_str,
+                current_str,
+                result.command_with_unused_parameters,
+                verdict_label,
+                p_value_str,
+            );
+        }
+
+        println!(" ");
+    }
+
+    /// Fail if any of the commands is significantly slower than its baseline
+    pub fn check_for_regressions(&self) -> Result<()> {
+        let regressions: Vec<_> = self
+            .results
+            .iter()
+            .filter(|result| {
+                result
+                    .baseline
+                    .as_ref()
+                    .is_some_and(|c| c.verdict == Verdict::Regression)
+            })
+            .map(|result| format!("'{}'", result.command_with_unused_parameters))
+            .collect();
+
+        if !regressions.is_empty() {
+            bail!(
+                "Performance regression compared to the baseline (threshold: {}): {}",
+                format_percentage(self.options.regression_threshold),
+                regressions.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn final_export(&self) -> Result<()> {
         self.export_manager
             .write_results(&self.results, self.options.sort_order_exports, false)
     }
 }
+
+/// Annotation for the speed comparison that shows the confidence interval of the relative
+/// speed and whether the difference to the reference is statistically significant
+fn comparison_annotation(item: &BenchmarkResultWithRelativeSpeed) -> String {
+    let mut parts = vec![];
+
+    if let (Some(ci), Some(intervals)) = (
+        item.relative_speed_ci,
+        item.result.confidence_intervals.as_ref(),
+    ) {
+        parts.push(format!(
+            "{} CI: {:.2} … {:.2}",
+            format_percentage(intervals.level),
+            ci.lower,
+            ci.upper
+        ));
+    }
+
+    match item.significance {
+        Some(significance) if significance.is_significant() => {
+            parts.push(format_p_value(significance.p_value));
+        }
+        Some(significance) => {
+            parts.push("not significant".yellow().to_string());
+            parts.push(format_p_value(significance.p_value));
+        }
+        None => {}
+    }
+
+    if parts.is_empty() {
+        "".into()
+    } else {
+        format!(" ({})", parts.join(", "))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::timer::ResourceUsage;
 use crate::util::units::Second;
 
 /// Results from timing a single command
@@ -11,4 +12,10 @@ pub struct TimingResult {
 
     /// Time spent in kernel mode
     pub time_system: Second,
+
+    /// Peak memory usage, page faults, context switches and block I/O
+    pub resource_usage: ResourceUsage,
+
+    /// Whether the command has been terminated because it exceeded the timeout
+    pub timed_out: bool,
 }
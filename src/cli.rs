@@ -30,7 +30,7 @@ fn build_command() -> Command {
                        The latter is only available if the shell is not explicitly disabled via \
                        '--shell=none'. If multiple commands are given, hyperfine will show a \
                        comparison of the respective runtimes.")
-                .required(true)
+                .required_unless_present("suite")
                 .action(ArgAction::Append)
                 .value_hint(ValueHint::CommandString)
                 .value_parser(NonEmptyStringValueParser::new()),
@@ -72,6 +72,45 @@ fn build_command() -> Command {
                 .help("Perform exactly NUM runs for each command. If this option is not specified, \
                        hyperfine automatically determines the number of runs."),
         )
+        .arg(
+            Arg::new("target-precision")
+                .long("target-precision")
+                .action(ArgAction::Set)
+                .value_name("PERCENT")
+                .conflicts_with("runs")
+                .help(
+                    "Keep adding timing runs until the confidence interval of the mean \
+                     (or median, see --target-precision-statistic) is narrower than ±PERCENT \
+                     of its value. Stable commands then need fewer runs, noisy commands get \
+                     more runs. At least --min-runs runs are performed. The benchmark stops at \
+                     --max-runs runs or when the --time-budget is used up, even if the \
+                     precision has not been reached.\n\n  \
+                     Example:  hyperfine --target-precision 1% 'sleep 0.1'",
+                ),
+        )
+        .arg(
+            Arg::new("target-precision-statistic")
+                .long("target-precision-statistic")
+                .action(ArgAction::Set)
+                .value_name("STAT")
+                .value_parser(["mean", "median"])
+                .requires("target-precision")
+                .help(
+                    "The statistic whose confidence interval is used for --target-precision. \
+                     Possible values: mean (default), median.",
+                ),
+        )
+        .arg(
+            Arg::new("time-budget")
+                .long("time-budget")
+                .action(ArgAction::Set)
+                .value_name("SECONDS")
+                .requires("target-precision")
+                .help(
+                    "Maximum time (in seconds) to spend on the timing runs of each command \
+                     when using --target-precision (default: 60).",
+                ),
+        )
         .arg(
             Arg::new("setup")
                 .long("setup")
@@ -143,11 +182,52 @@ fn build_command() -> Command {
                      artifacts that need to be cleaned up."
                 ),
         )
+        .arg(
+            Arg::new("env")
+                .long("env")
+                .action(ArgAction::Append)
+                .num_args(1)
+                .value_name("KEY=VALUE")
+                .help(
+                    "Set environment variables for the benchmarked command, as well as its \
+                     setup, prepare, conclude and cleanup commands. Multiple variables can be \
+                     separated by spaces (with shell-like quoting), e.g. --env 'A=1 B=\"x y\"'. \
+                     Parameters like {name} are substituted in the values.\nThe --env option \
+                     can be specified once for all commands or multiple times, once for each \
+                     command.",
+                ),
+        )
+        .arg(
+            Arg::new("env-clear")
+                .long("env-clear")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Start the commands with an empty environment (apart from the variables \
+                     given with --env) instead of the environment of hyperfine. This makes \
+                     benchmarks independent of the current environment. Note that this also \
+                     clears variables like PATH or HOME.",
+                ),
+        )
+        .arg(
+            Arg::new("cwd")
+                .long("cwd")
+                .action(ArgAction::Append)
+                .num_args(1)
+                .value_name("DIR")
+                .value_hint(ValueHint::DirPath)
+                .help(
+                    "Run the benchmarked command, as well as its setup, prepare, conclude and \
+                     cleanup commands, in the directory DIR. Parameters like {name} are \
+                     substituted in DIR. With -N, a relative program path like './app' is \
+                     resolved against DIR.\nThe --cwd option can be specified once for all \
+                     commands or multiple times, once for each command.",
+                ),
+        )
         .arg(
             Arg::new("parameter-scan")
                 .long("parameter-scan")
                 .short('P')
-                .action(ArgAction::Set)
+                .action(ArgAction::Append)
                 .allow_hyphen_values(true)
                 .value_names(["VAR", "MIN", "MAX"])
                 .help(
@@ -158,21 +238,59 @@ fn build_command() -> Command {
                      To have the value increase following different patterns, use shell arithmetics.\n\n  \
                      Example: hyperfine -P size 0 3 'sleep $((2**{size}))'\n\n\
                      This performs benchmarks with power of 2 increases: 'sleep 1', 'sleep 2', 'sleep 4', …\n\
-                     The exact syntax may vary depending on your shell and OS."
+                     The exact syntax may vary depending on your shell and OS.\n\n\
+                     MIN, MAX and the step size may use the size suffixes K, M and G (multiples \
+                     of 1024), like '4K'.\n\n\
+                     The option can be specified multiple times, and it can be combined with \
+                     --parameter-list, to run benchmarks for all possible parameter combinations."
                 ),
         )
         .arg(
             Arg::new("parameter-step-size")
                 .long("parameter-step-size")
                 .short('D')
-                .action(ArgAction::Set)
+                .action(ArgAction::Append)
                 .value_names(["DELTA"])
                 .requires("parameter-scan")
                 .help(
                     "This argument requires --parameter-scan to be specified as well. \
                      Traverse the range MIN..MAX in steps of DELTA.\n\n  \
                      Example:  hyperfine -P delay 0.3 0.7 -D 0.2 'sleep {delay}'\n\n\
-                     This performs benchmarks for 'sleep 0.3', 'sleep 0.5' and 'sleep 0.7'.",
+                     This performs benchmarks for 'sleep 0.3', 'sleep 0.5' and 'sleep 0.7'.\n\n\
+                     If --parameter-scan is used multiple times, the step size can be specified \
+                     once for all scans or once for each scan, in the same order.",
+                ),
+        )
+        .arg(
+            Arg::new("parameter-scan-geometric")
+                .long("parameter-scan-geometric")
+                .action(ArgAction::Append)
+                .value_names(["VAR", "MIN", "MAX"])
+                .help(
+                    "Perform benchmark runs for each value in the range MIN..MAX, where each \
+                     value is the previous one multiplied by a factor (see --factor). Replaces \
+                     the string '{VAR}' in each command by the current parameter value. The \
+                     bounds may use the size suffixes K, M and G (multiples of 1024).\n\n  \
+                     Example:  hyperfine --parameter-scan-geometric size 1K 1M 'head -c {size} /dev/urandom'\n\n\
+                     This performs benchmarks for 'head -c 1024 /dev/urandom', 'head -c 2048 \
+                     /dev/urandom', …, 'head -c 1048576 /dev/urandom'.\n\n\
+                     Like --parameter-scan, the option can be specified multiple times and can be \
+                     combined with the other parameter options.",
+                ),
+        )
+        .arg(
+            Arg::new("factor")
+                .long("factor")
+                .action(ArgAction::Append)
+                .value_name("FACTOR")
+                .requires("parameter-scan-geometric")
+                .help(
+                    "This argument requires --parameter-scan-geometric to be specified as well. \
+                     Multiply each value of the range by FACTOR to get the next one (default: 2).\n\n  \
+                     Example:  hyperfine --parameter-scan-geometric n 1 1000 --factor 10 'seq {n}'\n\n\
+                     This performs benchmarks for 'seq 1', 'seq 10', 'seq 100' and 'seq 1000'. \
+                     If --parameter-scan-geometric is used multiple times, the factor can be \
+                     specified once for all scans or once for each scan, in the same order.",
                 ),
         )
         .arg(
@@ -182,14 +300,83 @@ fn build_command() -> Command {
                 .action(ArgAction::Append)
                 .allow_hyphen_values(true)
                 .value_names(["VAR", "VALUES"])
-                .conflicts_with_all(["parameter-scan", "parameter-step-size"])
                 .help(
                     "Perform benchmark runs for each value in the comma-separated list VALUES. \
                      Replaces the string '{VAR}' in each command by the current parameter value\
                      .\n\nExample:  hyperfine -L compiler gcc,clang '{compiler} -O2 main.cpp'\n\n\
                      This performs benchmarks for 'gcc -O2 main.cpp' and 'clang -O2 main.cpp'.\n\n\
-                     The option can be specified multiple times to run benchmarks for all \
-                     possible parameter combinations.\n"
+                     The option can be specified multiple times, and it can be combined with \
+                     --parameter-scan, to run benchmarks for all possible parameter combinations.\n"
+                ),
+        )
+        .arg(
+            Arg::new("parameter-list-file")
+                .long("parameter-list-file")
+                .action(ArgAction::Append)
+                .value_names(["VAR", "FILE"])
+                .help(
+                    "Like --parameter-list, but read the values from FILE, one value per line. \
+                     Empty lines are ignored.",
+                ),
+        )
+        .arg(
+            Arg::new("parameter-list-cmd")
+                .long("parameter-list-cmd")
+                .action(ArgAction::Append)
+                .value_names(["VAR", "CMD"])
+                .value_hint(ValueHint::CommandString)
+                .help(
+                    "Like --parameter-list, but use the lines of the output of CMD as values. \
+                     The command is executed once, with the shell from --shell (or without \
+                     a shell for -N), before the benchmarks start.\n\n  \
+                     Example:  hyperfine --parameter-list-cmd rev 'git rev-list -5 HEAD' \
+                     --setup 'git checkout {rev} && make' './app'",
+                ),
+        )
+        .arg(
+            Arg::new("parameter-zip")
+                .long("parameter-zip")
+                .action(ArgAction::Append)
+                .value_name("VARS")
+                .requires("parameter-list")
+                .help(
+                    "Combine the values of the comma-separated parameter names VARS \
+                     element-wise instead of benchmarking all possible combinations. The \
+                     parameters need to have the same number of values.\n\n  \
+                     Example:  hyperfine -L cc gcc,clang -L opt 2,3 --parameter-zip cc,opt \
+                     '{cc} -O{opt} main.c'\n\n\
+                     This performs benchmarks for 'gcc -O2 main.c' and 'clang -O3 main.c'. \
+                     Other parameters are still combined with all of these pairs.",
+                ),
+        )
+        .arg(
+            Arg::new("parameter-filter")
+                .long("parameter-filter")
+                .action(ArgAction::Append)
+                .value_name("EXPR")
+                .help(
+                    "Only benchmark the parameter combinations for which the condition EXPR \
+                     holds. Conditions can compare parameters and numbers or quoted strings \
+                     (==, !=, <, <=, >, >=), use arithmetic (+, -, *, /, %) and combine \
+                     conditions with &&, || and !. Parameters are compared as numbers if \
+                     both sides are numeric. This option can be specified multiple times, in \
+                     which case all of the conditions have to hold.\n\n  \
+                     Example:  hyperfine -L threads 1,2,4,8 -L cores 2,4 \
+                     --parameter-filter 'threads <= cores' 'run -t {threads} -c {cores}'",
+                ),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .action(ArgAction::Set)
+                .value_name("SECONDS")
+                .help(
+                    "Terminate a command if a single run takes longer than SECONDS. On Unix, the \
+                     command and all of its child processes receive SIGTERM, followed by SIGKILL \
+                     if they do not exit within a short grace period. Unless '--ignore-failure' \
+                     is used, this aborts the benchmark. Otherwise, the run is recorded as timed \
+                     out and the benchmark continues. The timeout also applies to the setup, \
+                     prepare, conclude and cleanup commands.",
                 ),
         )
         .arg(
@@ -254,6 +441,114 @@ fn build_command() -> Command {
                    * 'mean-time': order benchmarks by mean runtime\n"
             ),
         )
+        .arg(
+            Arg::new("show-percentiles")
+                .long("show-percentiles")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Print the percentiles P5, P25, P75, P95 and P99, the interquartile range \
+                     (IQR) and the coefficient of variation (CoV, standard deviation relative \
+                     to the mean) of the run times of each command. These statistics are always \
+                     included in the JSON, CSV and markup exports.",
+                ),
+        )
+        .arg(
+            Arg::new("confidence-level")
+                .long("confidence-level")
+                .action(ArgAction::Set)
+                .value_name("PERCENT")
+                .help(
+                    "Confidence level of the confidence intervals for the mean, the median and \
+                     the relative speeds, and of the interval used by --target-precision \
+                     (default: 95%).",
+                ),
+        )
+        .arg(
+            Arg::new("bootstrap-resamples")
+                .long("bootstrap-resamples")
+                .action(ArgAction::Set)
+                .value_name("NUM")
+                .help(
+                    "Number of bootstrap resamples that are used to compute the confidence \
+                     intervals of the mean, the median and the relative speeds. Unlike the \
+                     '± σ' values, bootstrap intervals do not assume normally distributed run \
+                     times. Use 0 to disable them (default: 10000, maximum: 1000000).",
+                ),
+        )
+        .arg(
+            Arg::new("significance-test")
+                .long("significance-test")
+                .action(ArgAction::Set)
+                .value_name("TEST")
+                .value_parser(["welch", "mann-whitney"])
+                .help(
+                    "Statistical test that is used to check whether the difference between the \
+                     reference command and each other command is significant (p < 0.05). The \
+                     p-values are shown in the summary and included in the JSON and markup \
+                     exports:\n  \
+                       * 'welch' (default): Welch's t-test, compares the mean times\n  \
+                       * 'mann-whitney': Mann-Whitney U test, a rank-based test that is\n    \
+                         robust against outliers\n",
+                ),
+        )
+        .arg(
+            Arg::new("baseline")
+                .long("baseline")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .help(
+                    "Compare the results with a previous run, given as a JSON file written by \
+                     '--export-json'. Commands are matched by name and parameter values. A \
+                     command has regressed if it is slower than its baseline by more than the \
+                     --regression-threshold and the difference is statistically significant \
+                     (see --significance-test). hyperfine exits with a non-zero exit code if \
+                     any command has regressed. The comparison is printed and included in the \
+                     JSON and markup exports.",
+                ),
+        )
+        .arg(
+            Arg::new("regression-threshold")
+                .long("regression-threshold")
+                .action(ArgAction::Set)
+                .value_name("PERCENT")
+                .requires("baseline")
+                .help(
+                    "Minimum slowdown compared to the --baseline that counts as a regression \
+                     (default: 5%).",
+                ),
+        )
+        .arg(
+            Arg::new("resume")
+                .long("resume")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .help(
+                    "Resume an interrupted session from the JSON file that has been written by \
+                     '--export-json'. Commands whose results in this file are complete (matched \
+                     by name and parameter values) are not benchmarked again. The exports \
+                     contain the merged results of the whole session, so FILE can also be \
+                     used as the '--export-json' target of the resumed run.",
+                ),
+        )
+        .arg(
+            Arg::new("execution-order")
+                .long("execution-order")
+                .action(ArgAction::Set)
+                .value_name("ORDER")
+                .value_parser(["sequential", "round-robin", "random"])
+                .help(
+                    "Specify the order in which the timing runs of the different commands are \
+                     performed:\n  \
+                       * 'sequential' (default): all runs of a command are performed before\n    \
+                         moving on to the next command\n  \
+                       * 'round-robin': alternate between the commands, one run at a time\n  \
+                       * 'random': perform the runs of all commands in random order\n\
+                     Interleaving the runs spreads effects like thermal throttling or background \
+                     load evenly across all commands. The setup command and the warmup runs of \
+                     all commands are executed before the interleaved timing runs start, the \
+                     cleanup commands after all of them have finished.",
+                ),
+        )
         .arg(
             Arg::new("time-unit")
                 .long("time-unit")
@@ -281,9 +576,44 @@ fn build_command() -> Command {
                 .value_name("FILE")
                 .value_hint(ValueHint::FilePath)
                 .help("Export the timing summary statistics as CSV to the given FILE. If you need \
-                       the timing results for each individual run, use the JSON export format. \
+                       the timing results for each individual run, use the JSON export format \
+                       or --export-csv-long. The output time unit is always seconds."),
+        )
+        .arg(
+            Arg::new("export-csv-long")
+                .long("export-csv-long")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the individual timing runs as CSV to the given FILE, with one row \
+                       per run: the start timestamp (seconds since the Unix epoch), the wall \
+                       clock, user and system time, the exit code and the parameter values. \
                        The output time unit is always seconds."),
         )
+        .arg(
+            Arg::new("export-events")
+                .long("export-events")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Write a log of all events to the given FILE while the benchmarks are \
+                       running (or to stdout for '-', preferably in combination with \
+                       '--style=none'). Each line is a JSON object for one event: calibration, \
+                       start of a benchmark, setup, warmup run, prepare, timing run (with the \
+                       real, user and system time and the exit code), conclude, cleanup and the \
+                       result of the benchmark. The time unit is always seconds."),
+        )
+        .arg(
+            Arg::new("export-html")
+                .long("export-html")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export a self-contained HTML report to the given FILE. It contains the \
+                       summary table, the speed comparison and charts of the run times \
+                       (histograms, a box plot and the progression over all runs). \
+                       The output time unit can be changed using the --time-unit option."),
+        )
         .arg(
             Arg::new("export-json")
                 .long("export-json")
@@ -293,6 +623,18 @@ fn build_command() -> Command {
                 .help("Export the timing summary statistics and timings of individual runs as JSON to the given FILE. \
                        The output time unit is always seconds"),
         )
+        .arg(
+            Arg::new("export-junit")
+                .long("export-junit")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the results as a JUnit XML report to the given FILE, for CI systems \
+                       and test dashboards. Each command is a test case with the mean run time as \
+                       its duration and the statistics as properties. Commands with failed runs, \
+                       a missed --target-precision or a regression compared to the --baseline \
+                       are reported as failures. The output time unit is always seconds."),
+        )
         .arg(
             Arg::new("export-markdown")
                 .long("export-markdown")
@@ -311,6 +653,33 @@ fn build_command() -> Command {
                 .help("Export the timing summary statistics as an Emacs org-mode table to the given FILE. \
                        The output time unit can be changed using the --time-unit option."),
         )
+        .arg(
+            Arg::new("export-prometheus")
+                .long("export-prometheus")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Export the timing summary statistics in the Prometheus / OpenMetrics text \
+                       format to the given FILE, e.g. for the textfile collector of the \
+                       node_exporter. There is one gauge per statistic, labeled with the command \
+                       and its parameters. The output time unit is always seconds."),
+        )
+        .arg(
+            Arg::new("export-svg")
+                .long("export-svg")
+                .action(ArgAction::Append)
+                .value_name("KIND=FILE")
+                .help("Export a chart of the results as an SVG image to the given FILE. This option \
+                       can be specified multiple times. <KIND> can be:\n\
+                       \n  \
+                         histogram:     Histogram of the run times of all commands.\n  \
+                         whisker:       Box and whisker plot of the run times.\n  \
+                         progression:   Run times in the order of execution.\n  \
+                         parametrized:  Mean run time as a function of the benchmark \
+                       parameter (requires --parameter-scan or --parameter-list).\n\
+                       \n\
+                       The time unit can be changed using the --time-unit option."),
+        )
         .arg(
             Arg::new("show-output")
                 .long("show-output")
@@ -370,6 +739,30 @@ fn build_command() -> Command {
                 .help("Give a meaningful name to a command. This can be specified multiple times \
                        if several commands are benchmarked."),
         )
+        .arg(
+            Arg::new("suite")
+                .long("suite")
+                .action(ArgAction::Set)
+                .value_name("FILE")
+                .value_hint(ValueHint::FilePath)
+                .help("Run the benchmark groups that are defined in the given TOML file. Each \
+                       [[benchmark]] table defines a group with a 'name' and a list of 'commands', \
+                       as well as optional settings like 'parameters', 'setup', 'prepare', \
+                       'runs', 'warmup', 'shell' or 'export'. The groups are benchmarked one \
+                       after the other. Options that are given on the command line apply to \
+                       all groups and take precedence over the settings in the file."),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .action(ArgAction::SetTrue)
+                .help("Print the benchmark plan without running anything: every benchmark with \
+                       its name, parameters and command line, the setup, prepare, conclude and \
+                       cleanup commands, and the shell that would be used. The plan is also \
+                       checked for placeholders that do not match any parameter, unused \
+                       parameters and duplicate command names. If a '--baseline' or '--resume' \
+                       file is given, its results are used to estimate the total run time."),
+        )
         // This option is hidden for now, as it is not yet clear yet if we want to 'stabilize' this,
         // see discussion in https://github.com/sharkdp/hyperfine/issues/527
         .arg(
@@ -1,18 +1,25 @@
 use std::collections::BTreeMap;
+use std::env;
 use std::fmt;
+use std::path::{self, Path};
 use std::str::FromStr;
 
+use crate::options::{parse_environment, ExecutorKind};
 use crate::parameter::tokenize::tokenize;
 use crate::parameter::ParameterValue;
 use crate::{
     error::{OptionsError, ParameterScanError},
     parameter::{
-        range_step::{Numeric, RangeStep},
+        expression::{Expression, Value},
+        placeholder::{check_placeholders, replace_placeholders, uses_parameter},
+        range_step::{GeometricRange, Numeric, RangeStep, MAX_PARAMETERS},
+        source,
+        suffix::expand_size_suffix,
         ParameterNameAndValue,
     },
 };
 
-use clap::{parser::ValuesRef, ArgMatches};
+use clap::ArgMatches;
 
 use anyhow::{bail, Context, Result};
 use rust_decimal::Decimal;
@@ -28,6 +35,12 @@ pub struct Command<'a> {
 
     /// Zero or more parameter values.
     parameters: Vec<ParameterNameAndValue<'a>>,
+
+    /// Environment variables from `--env` (without parameter substitution)
+    environment: Option<&'a str>,
+
+    /// Working directory from `--cwd` (without parameter substitution)
+    working_directory: Option<&'a str>,
 }
 
 impl<'a> Command<'a> {
@@ -36,6 +49,8 @@ impl<'a> Command<'a> {
             name,
             expression,
             parameters: Vec::new(),
+            environment: None,
+            working_directory: None,
         }
     }
 
@@ -48,6 +63,22 @@ impl<'a> Command<'a> {
             name,
             expression,
             parameters: parameters.into_iter().collect(),
+            environment: None,
+            working_directory: None,
+        }
+    }
+
+    /// Run the command with the given environment variables and in the given directory
+    /// (see `--env` and `--cwd`)
+    pub fn with_environment(
+        self,
+        environment: Option<&'a str>,
+        working_directory: Option<&'a str>,
+    ) -> Command<'a> {
+        Command {
+            environment,
+            working_directory,
+            ..self
         }
     }
 
@@ -84,7 +115,22 @@ impl<'a> Command<'a> {
             .with_context(|| format!("Failed to parse command '{command_line}'"))?
             .into_iter();
 
-        if let Some(program_name) = tokens.next() {
+        if let Some(mut program_name) = tokens.next() {
+            // A relative path like './app' is resolved against the directory from '--cwd'.
+            // This is done explicitly, as the platforms differ in whether they resolve it
+            // against the new or the current working directory.
+            if let Some(directory) = self.get_working_directory() {
+                if program_name.contains(path::is_separator)
+                    && Path::new(&program_name).is_relative()
+                {
+                    program_name = env::current_dir()
+                        .context("Could not determine the current working directory")?
+                        .join(directory)
+                        .join(program_name)
+                        .to_string_lossy()
+                        .into_owned();
+                }
+            }
             let mut command_builder = std::process::Command::new(program_name);
             command_builder.args(tokens);
             Ok(command_builder)
@@ -93,148 +139,125 @@ impl<'a> Command<'a> {
         }
     }
 
+    /// The environment variables for this command, with parameters substituted
+    pub fn get_environment(&self) -> Result<Vec<(String, String)>> {
+        match self.environment {
+            Some(environment) => Ok(parse_environment(&self.replace_parameters_in(environment))?),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// The working directory for this command, with parameters substituted
+    pub fn get_working_directory(&self) -> Option<String> {
+        self.working_directory
+            .map(|directory| self.replace_parameters_in(directory))
+    }
+
     pub fn get_parameters(&self) -> &[(&'a str, ParameterValue)] {
         &self.parameters
     }
 
+    /// The command and its name (if any) as given, i.e. without parameter substitution
+    pub fn get_templates(&self) -> impl Iterator<Item = &'a str> {
+        self.name
+            .into_iter()
+            .chain(std::iter::once(self.expression))
+    }
+
+    /// Checks that the placeholders in `template` (the command itself or e.g. the argument of
+    /// `--prepare`) can be evaluated with the parameter values of this command
+    pub fn check_placeholders_in(&self, template: &str) -> Result<()> {
+        check_placeholders(template, &self.parameters)
+    }
+
     pub fn get_unused_parameters(&self) -> impl Iterator<Item = &(&'a str, ParameterValue)> {
         self.parameters
             .iter()
-            .filter(move |(parameter, _)| !self.expression.contains(&format!("{{{parameter}}}")))
+            .filter(move |(parameter, _)| !uses_parameter(self.expression, parameter))
     }
 
+    /// Replaces the placeholders (like `{foo}` or `{foo*2:04}`) by the parameter values
     fn replace_parameters_in(&self, original: &str) -> String {
-        let mut result = String::new();
-        let mut replacements = BTreeMap::<String, String>::new();
-        for (param_name, param_value) in &self.parameters {
-            replacements.insert(format!("{{{param_name}}}"), param_value.to_string());
-        }
-        let mut remaining = original;
-        // Manually replace consecutive occurrences to avoid double-replacing: e.g.,
+        // Substituted values are not replaced again: e.g.,
         //
         //     hyperfine -L foo 'a,{bar}' -L bar 'baz,quux' 'echo {foo} {bar}'
         //
         // should not ever run 'echo baz baz'. See `test_get_command_line_nonoverlapping`.
-        'outer: while let Some(head) = remaining.chars().next() {
-            for (k, v) in &replacements {
-                if remaining.starts_with(k.as_str()) {
-                    result.push_str(v);
-                    remaining = &remaining[k.len()..];
-                    continue 'outer;
-                }
-            }
-            result.push(head);
-            remaining = &remaining[head.len_utf8()..];
-        }
-        result
+        replace_placeholders(original, &self.parameters)
     }
 }
 
+/// A parameter with all of its values, e.g. from `--parameter-scan` or `--parameter-list`
+type ParameterDefinition<'a> = (&'a str, Vec<ParameterValue>);
+
+/// The arguments of a `--parameter-scan` or `--parameter-scan-geometric` option, and its
+/// position on the command line
+struct ParameterScanArguments<'a> {
+    index: usize,
+    name: &'a str,
+    min: &'a str,
+    max: &'a str,
+    /// Step size or factor
+    step: Option<&'a str>,
+}
+
+/// The values of one axis of the parameter space. Every value assigns one or more parameters.
+type ParameterAxis<'a, 'b> = Vec<Vec<(&'a str, &'b ParameterValue)>>;
+
 /// A collection of commands that should be benchmarked
 pub struct Commands<'a>(Vec<Command<'a>>);
 
 impl<'a> Commands<'a> {
     pub fn from_cli_arguments(matches: &'a ArgMatches) -> Result<Commands> {
-        let command_names = matches.get_many::<String>("command-name");
+        let command_names = matches
+            .get_many::<String>("command-name")
+            .map_or(vec![], |names| {
+                names.map(|v| v.as_str()).collect::<Vec<_>>()
+            });
         let command_strings = matches
             .get_many::<String>("command")
             .unwrap_or_default()
             .map(|v| v.as_str())
             .collect::<Vec<_>>();
 
-        if let Some(args) = matches.get_many::<String>("parameter-scan") {
-            let step_size = matches
-                .get_one::<String>("parameter-step-size")
-                .map(|s| s.as_str());
-            Ok(Self(Self::get_parameter_scan_commands(
-                command_names,
-                command_strings,
-                args,
-                step_size,
-            )?))
-        } else if let Some(args) = matches.get_many::<String>("parameter-list") {
-            let command_names = command_names.map_or(vec![], |names| {
-                names.map(|v| v.as_str()).collect::<Vec<_>>()
-            });
-            let args: Vec<_> = args.map(|v| v.as_str()).collect::<Vec<_>>();
-            let param_names_and_values: Vec<(&str, Vec<String>)> = args
-                .chunks_exact(2)
-                .map(|pair| {
-                    let name = pair[0];
-                    let list_str = pair[1];
-                    (name, tokenize(list_str))
-                })
-                .collect();
-            {
-                let duplicates =
-                    Self::find_duplicates(param_names_and_values.iter().map(|(name, _)| *name));
-                if !duplicates.is_empty() {
-                    bail!("Duplicate parameter names: {}", &duplicates.join(", "));
-                }
-            }
-
-            let dimensions: Vec<usize> = std::iter::once(command_strings.len())
-                .chain(
-                    param_names_and_values
-                        .iter()
-                        .map(|(_, values)| values.len()),
-                )
-                .collect();
-            let param_space_size = dimensions.iter().product();
-            if param_space_size == 0 {
-                return Ok(Self(Vec::new()));
+        let parameters = Self::get_parameters(matches)?;
+        {
+            let duplicates = Self::find_duplicates(parameters.iter().map(|(name, _)| *name));
+            if !duplicates.is_empty() {
+                bail!("Duplicate parameter names: {}", &duplicates.join(", "));
             }
+        }
 
-            // `--command-name` should appear exactly once or exactly B times,
-            // where B is the total number of benchmarks.
-            let command_name_count = command_names.len();
-            if command_name_count > 1 && command_name_count != param_space_size {
-                return Err(OptionsError::UnexpectedCommandNameCount(
-                    command_name_count,
-                    param_space_size,
-                )
-                .into());
-            }
+        let zipped_names: Vec<Vec<&str>> =
+            matches
+                .get_many::<String>("parameter-zip")
+                .map_or(vec![], |zips| {
+                    zips.map(|names| names.split(',').map(str::trim).collect())
+                        .collect()
+                });
+        let mut axes = Self::parameter_axes(&parameters, &zipped_names)?;
+        Self::check_parameter_space_size(&axes)?;
 
-            let mut i = 0;
-            let mut commands = Vec::with_capacity(param_space_size);
-            let mut index = vec![0usize; dimensions.len()];
-            'outer: loop {
-                let name = command_names
-                    .get(i)
-                    .or_else(|| command_names.first())
-                    .copied();
-                i += 1;
-
-                let (command_index, params_indices) = index.split_first().unwrap();
-                let parameters: Vec<_> = param_names_and_values
-                    .iter()
-                    .zip(params_indices)
-                    .map(|((name, values), i)| (*name, ParameterValue::Text(values[*i].clone())))
-                    .collect();
-                commands.push(Command::new_parametrized(
-                    name,
-                    command_strings[*command_index],
-                    parameters,
-                ));
-
-                // Increment index, exiting loop on overflow.
-                for (i, n) in index.iter_mut().zip(dimensions.iter()) {
-                    *i += 1;
-                    if *i < *n {
-                        continue 'outer;
-                    } else {
-                        *i = 0;
-                    }
-                }
-                break 'outer;
+        let filters = matches
+            .get_many::<String>("parameter-filter")
+            .unwrap_or_default()
+            .map(|filter| {
+                Expression::parse(filter)
+                    .map(|expression| (filter.as_str(), expression))
+                    .with_context(|| format!("Invalid parameter filter '{filter}'"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !filters.is_empty() {
+            if axes.is_empty() {
+                bail!(
+                    "'--parameter-filter' requires parameters, e.g. from '--parameter-scan' or \
+                     '--parameter-list'"
+                );
             }
+            axes = vec![Self::filter_parameter_space(&parameters, &axes, &filters)?];
+        }
 
-            Ok(Self(commands))
-        } else {
-            let command_names = command_names.map_or(vec![], |names| {
-                names.map(|v| v.as_str()).collect::<Vec<_>>()
-            });
+        if axes.is_empty() {
             if command_names.len() > command_strings.len() {
                 return Err(OptionsError::TooManyCommandNames(command_strings.len()).into());
             }
@@ -244,6 +267,15 @@ impl<'a> Commands<'a> {
                 commands.push(Command::new(command_names.get(i).copied(), s));
             }
             Ok(Self(commands))
+        } else {
+            let commands =
+                Self::build_parametrized_commands(command_names, command_strings, &axes)?;
+            for command in &commands {
+                for template in command.get_templates() {
+                    command.check_placeholders_in(template)?;
+                }
+            }
+            Ok(Self(commands))
         }
     }
 
@@ -268,58 +300,332 @@ impl<'a> Commands<'a> {
             .collect()
     }
 
-    fn build_parameter_scan_commands<'b, T: Numeric>(
-        param_name: &'b str,
-        param_min: T,
-        param_max: T,
-        step: T,
-        command_names: Vec<&'b str>,
-        command_strings: Vec<&'b str>,
-    ) -> Result<Vec<Command<'b>>, ParameterScanError> {
-        let param_range = RangeStep::new(param_min, param_max, step)?;
-        let param_count = param_range.size_hint().1.unwrap();
-        let command_name_count = command_names.len();
+    /// Collects the values of all parameter options (`--parameter-scan`, `--parameter-list`,
+    /// …), in the order in which they appear on the command line
+    fn get_parameters(matches: &'a ArgMatches) -> Result<Vec<ParameterDefinition<'a>>> {
+        let mut parameters = vec![];
+
+        for scan in Self::get_scans(matches, "parameter-scan", "parameter-step-size")? {
+            let values = Self::get_parameter_scan_values(scan.min, scan.max, scan.step)?;
+            parameters.push((scan.index, scan.name, values));
+        }
+
+        for scan in Self::get_scans(matches, "parameter-scan-geometric", "factor")? {
+            let values = Self::get_geometric_parameter_scan_values(scan.min, scan.max, scan.step)?;
+            parameters.push((scan.index, scan.name, values));
+        }
+
+        if let (Some(args), Some(indices)) = (
+            matches.get_many::<String>("parameter-list"),
+            matches.indices_of("parameter-list"),
+        ) {
+            let args: Vec<_> = args.map(|v| v.as_str()).collect();
+            let indices: Vec<_> = indices.collect();
+            for (i, pair) in args.chunks_exact(2).enumerate() {
+                let name = pair[0];
+                let values = tokenize(pair[1])
+                    .into_iter()
+                    .map(ParameterValue::Text)
+                    .collect();
+                parameters.push((indices[2 * i], name, values));
+            }
+        }
+
+        let executor_kind = ExecutorKind::from_cli_arguments(matches)?;
+        let values_from_command =
+            |command_line: &str| source::values_from_command(command_line, &executor_kind);
+        let sources: [(&str, source::Reader); 2] = [
+            ("parameter-list-file", &source::values_from_file),
+            ("parameter-list-cmd", &values_from_command),
+        ];
+        for (option, read_values) in sources {
+            if let (Some(args), Some(indices)) = (
+                matches.get_many::<String>(option),
+                matches.indices_of(option),
+            ) {
+                let args: Vec<_> = args.map(|v| v.as_str()).collect();
+                let indices: Vec<_> = indices.collect();
+                for (i, pair) in args.chunks_exact(2).enumerate() {
+                    let values = read_values(pair[1])?
+                        .into_iter()
+                        .map(ParameterValue::Text)
+                        .collect();
+                    parameters.push((indices[2 * i], pair[0], values));
+                }
+            }
+        }
+
+        parameters.sort_by_key(|(index, _, _)| *index);
+        Ok(parameters
+            .into_iter()
+            .map(|(_, name, values)| (name, values))
+            .collect())
+    }
+
+    /// Collects the arguments of all scans of the given option, with the step of each scan
+    fn get_scans(
+        matches: &'a ArgMatches,
+        scan_option: &str,
+        step_option: &'static str,
+    ) -> Result<Vec<ParameterScanArguments<'a>>, ParameterScanError> {
+        let (args, indices) = match (
+            matches.get_many::<String>(scan_option),
+            matches.indices_of(scan_option),
+        ) {
+            (Some(args), Some(indices)) => (
+                args.map(|v| v.as_str()).collect::<Vec<_>>(),
+                indices.collect::<Vec<_>>(),
+            ),
+            _ => return Ok(vec![]),
+        };
+        let steps: Vec<_> = matches
+            .get_many::<String>(step_option)
+            .map_or(vec![], |steps| steps.map(|v| v.as_str()).collect());
+
+        // The step option should appear at most once or exactly S times,
+        // where S is the number of scans.
+        let num_scans = args.len() / 3;
+        if steps.len() > 1 && steps.len() != num_scans {
+            return Err(ParameterScanError::UnexpectedStepCount(
+                step_option,
+                steps.len(),
+                num_scans,
+            ));
+        }
+
+        Ok(args
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(i, scan)| ParameterScanArguments {
+                index: indices[3 * i],
+                name: scan[0],
+                min: scan[1],
+                max: scan[2],
+                step: steps.get(i).or_else(|| steps.first()).copied(),
+            })
+            .collect())
+    }
+
+    /// Arranges the parameters into the axes of the parameter space. Each parameter is an
+    /// axis of its own, except for parameters that are zipped (see `--parameter-zip`): these
+    /// are combined element-wise into a single axis, at the position of the first of them.
+    fn parameter_axes<'b>(
+        parameters: &'b [ParameterDefinition<'a>],
+        zipped_names: &[Vec<&str>],
+    ) -> Result<Vec<ParameterAxis<'a, 'b>>> {
+        for name in zipped_names.iter().flatten() {
+            if !parameters.iter().any(|(parameter, _)| parameter == name) {
+                bail!(
+                    "Unknown parameter '{name}' in '--parameter-zip'. Zipped parameters have \
+                     to be defined with '--parameter-scan', '--parameter-scan-geometric', \
+                     '--parameter-list', '--parameter-list-file' or '--parameter-list-cmd'"
+                );
+            }
+        }
+        let duplicates = Self::find_duplicates(zipped_names.iter().flatten().copied());
+        if !duplicates.is_empty() {
+            bail!(
+                "Parameters can only be zipped once: {}",
+                &duplicates.join(", ")
+            );
+        }
+
+        let mut axes = vec![];
+        for (index, (name, values)) in parameters.iter().enumerate() {
+            let zipped = match zipped_names.iter().find(|names| names.contains(name)) {
+                Some(zipped) => zipped,
+                None => {
+                    axes.push(values.iter().map(|v| vec![(*name, v)]).collect());
+                    continue;
+                }
+            };
+            if parameters[..index]
+                .iter()
+                .any(|(other, _)| zipped.contains(other))
+            {
+                // The axis has already been added with an earlier parameter
+                continue;
+            }
+
+            let members: Vec<_> = parameters
+                .iter()
+                .filter(|(other, _)| zipped.contains(other))
+                .collect();
+            for (other, other_values) in &members {
+                if other_values.len() != values.len() {
+                    bail!(
+                        "The zipped parameters '{name}' and '{other}' have a different number \
+                         of values ({} and {})",
+                        values.len(),
+                        other_values.len()
+                    );
+                }
+            }
+            axes.push(
+                (0..values.len())
+                    .map(|i| {
+                        members
+                            .iter()
+                            .map(|(name, values)| (*name, &values[i]))
+                            .collect()
+                    })
+                    .collect(),
+            );
+        }
+
+        Ok(axes)
+    }
+
+    /// Makes sure that the number of parameter combinations is within the same limit as the
+    /// number of values of a single parameter, before any of the combinations are built
+    fn check_parameter_space_size(axes: &[ParameterAxis]) -> Result<(), ParameterScanError> {
+        axes.iter()
+            .try_fold(1usize, |size, axis| {
+                size.checked_mul(axis.len())
+                    .filter(|size| *size <= MAX_PARAMETERS)
+            })
+            .map(|_| ())
+            .ok_or(ParameterScanError::TooLarge)
+    }
+
+    /// Combines the axes of the parameter space into a single axis that only contains the
+    /// combinations for which all of the filters (see `--parameter-filter`) hold. The order of
+    /// the combinations is the same as in the product space, the first axis varies fastest.
+    fn filter_parameter_space<'b>(
+        parameters: &[ParameterDefinition<'a>],
+        axes: &[ParameterAxis<'a, 'b>],
+        filters: &[(&str, Expression)],
+    ) -> Result<ParameterAxis<'a, 'b>> {
+        for (filter, expression) in filters {
+            for name in expression.parameters() {
+                if !parameters.iter().any(|(parameter, _)| *parameter == name) {
+                    bail!("Unknown parameter '{name}' in the parameter filter '{filter}'");
+                }
+            }
+        }
+
+        let mut combinations = vec![vec![]];
+        for axis in axes {
+            combinations = axis
+                .iter()
+                .flat_map(|values| {
+                    combinations.iter().map(move |combination: &Vec<_>| {
+                        combination.iter().chain(values).copied().collect()
+                    })
+                })
+                .collect();
+        }
+
+        let mut filtered = vec![];
+        for combination in combinations {
+            let lookup = |name: &str| {
+                combination
+                    .iter()
+                    .find(|(parameter, _)| *parameter == name)
+                    .map(|(_, value)| Value::from(*value))
+            };
+            let mut included = true;
+            for (filter, expression) in filters {
+                included = expression.evaluate_condition(&lookup).with_context(|| {
+                    format!("Could not evaluate the parameter filter '{filter}'")
+                })?;
+                if !included {
+                    break;
+                }
+            }
+            if included {
+                filtered.push(combination);
+            }
+        }
+
+        if filtered.is_empty() {
+            bail!("All parameter combinations have been excluded by '--parameter-filter'");
+        }
+        Ok(filtered)
+    }
+
+    /// Builds one command for each point in the product space of the command list and the
+    /// parameter axes. The commands vary fastest, followed by the axes in the given order.
+    fn build_parametrized_commands(
+        command_names: Vec<&'a str>,
+        command_strings: Vec<&'a str>,
+        axes: &[ParameterAxis<'a, '_>],
+    ) -> Result<Vec<Command<'a>>, OptionsError<'static>> {
+        let dimensions: Vec<usize> = std::iter::once(command_strings.len())
+            .chain(axes.iter().map(|axis| axis.len()))
+            .collect();
+        let param_space_size = dimensions.iter().product();
+        if param_space_size == 0 {
+            return Ok(Vec::new());
+        }
 
         // `--command-name` should appear exactly once or exactly B times,
         // where B is the total number of benchmarks.
-        if command_name_count > 1 && command_name_count != param_count {
-            return Err(ParameterScanError::UnexpectedCommandNameCount(
+        let command_name_count = command_names.len();
+        if command_name_count > 1 && command_name_count != param_space_size {
+            return Err(OptionsError::UnexpectedCommandNameCount(
                 command_name_count,
-                param_count,
+                param_space_size,
             ));
         }
 
         let mut i = 0;
-        let mut commands = vec![];
-        for value in param_range {
-            for cmd in &command_strings {
-                let name = command_names
-                    .get(i)
-                    .or_else(|| command_names.first())
-                    .copied();
-                commands.push(Command::new_parametrized(
-                    name,
-                    cmd,
-                    vec![(param_name, ParameterValue::Numeric(value.into()))],
-                ));
-                i += 1;
+        let mut commands = Vec::with_capacity(param_space_size);
+        let mut index = vec![0usize; dimensions.len()];
+        'outer: loop {
+            let name = command_names
+                .get(i)
+                .or_else(|| command_names.first())
+                .copied();
+            i += 1;
+
+            let (command_index, params_indices) = index.split_first().unwrap();
+            let parameters: Vec<_> = axes
+                .iter()
+                .zip(params_indices)
+                .flat_map(|(axis, i)| &axis[*i])
+                .map(|(name, value)| (*name, (*value).clone()))
+                .collect();
+            commands.push(Command::new_parametrized(
+                name,
+                command_strings[*command_index],
+                parameters,
+            ));
+
+            // Increment index, exiting loop on overflow.
+            for (i, n) in index.iter_mut().zip(dimensions.iter()) {
+                *i += 1;
+                if *i < *n {
+                    continue 'outer;
+                } else {
+                    *i = 0;
+                }
             }
+            break 'outer;
         }
+
         Ok(commands)
     }
 
-    fn get_parameter_scan_commands<'b>(
-        command_names: Option<ValuesRef<'b, String>>,
-        command_strings: Vec<&'b str>,
-        mut vals: ValuesRef<'b, String>,
+    fn build_parameter_scan_values<T: Numeric>(
+        param_min: T,
+        param_max: T,
+        step: T,
+    ) -> Result<Vec<ParameterValue>, ParameterScanError> {
+        Ok(RangeStep::new(param_min, param_max, step)?
+            .map(|value| ParameterValue::Numeric(value.into()))
+            .collect())
+    }
+
+    fn get_parameter_scan_values(
+        param_min: &str,
+        param_max: &str,
         step: Option<&str>,
-    ) -> Result<Vec<Command<'b>>, ParameterScanError> {
-        let command_names = command_names.map_or(vec![], |names| {
-            names.map(|v| v.as_str()).collect::<Vec<_>>()
-        });
-        let param_name = vals.next().unwrap().as_str();
-        let param_min = vals.next().unwrap().as_str();
-        let param_max = vals.next().unwrap().as_str();
+    ) -> Result<Vec<ParameterValue>, ParameterScanError> {
+        let param_min = &expand_size_suffix(param_min);
+        let param_max = &expand_size_suffix(param_max);
+        let step = step.map(expand_size_suffix);
+        let step = step.as_deref();
 
         // attempt to parse as integers
         if let (Ok(param_min), Ok(param_max), Ok(step)) = (
@@ -327,14 +633,7 @@ impl<'a> Commands<'a> {
             param_max.parse::<i32>(),
             step.unwrap_or("1").parse::<i32>(),
         ) {
-            return Self::build_parameter_scan_commands(
-                param_name,
-                param_min,
-                param_max,
-                step,
-                command_names,
-                command_strings,
-            );
+            return Self::build_parameter_scan_values(param_min, param_max, step);
         }
 
         // try parsing them as decimals
@@ -346,13 +645,42 @@ impl<'a> Commands<'a> {
         }
 
         let step = Decimal::from_str(step.unwrap())?;
-        Self::build_parameter_scan_commands(
-            param_name,
-            param_min,
-            param_max,
-            step,
-            command_names,
-            command_strings,
+        Self::build_parameter_scan_values(param_min, param_max, step)
+    }
+
+    fn build_geometric_parameter_scan_values<T: Numeric>(
+        param_min: T,
+        param_max: T,
+        factor: T,
+    ) -> Result<Vec<ParameterValue>, ParameterScanError> {
+        Ok(GeometricRange::new(param_min, param_max, factor)?
+            .map(|value| ParameterValue::Numeric(value.into()))
+            .collect())
+    }
+
+    fn get_geometric_parameter_scan_values(
+        param_min: &str,
+        param_max: &str,
+        factor: Option<&str>,
+    ) -> Result<Vec<ParameterValue>, ParameterScanError> {
+        let param_min = expand_size_suffix(param_min);
+        let param_max = expand_size_suffix(param_max);
+        let factor = factor.unwrap_or("2");
+
+        // attempt to parse as integers
+        if let (Ok(param_min), Ok(param_max), Ok(factor)) = (
+            param_min.parse::<i32>(),
+            param_max.parse::<i32>(),
+            factor.parse::<i32>(),
+        ) {
+            return Self::build_geometric_parameter_scan_values(param_min, param_max, factor);
+        }
+
+        // try parsing them as decimals
+        Self::build_geometric_parameter_scan_values(
+            Decimal::from_str(&param_min)?,
+            Decimal::from_str(&param_max)?,
+            Decimal::from_str(factor)?,
         )
     }
 }
@@ -471,17 +799,28 @@ fn test_build_parameter_scan_commands() {
     assert_eq!(commands[1].get_command_line(), "echo 2");
 }
 
+/// Builds the commands for a single parameter scan
+#[cfg(test)]
+fn build_parameter_scan_commands<'a, T: Numeric>(
+    param_name: &'a str,
+    param_min: T,
+    param_max: T,
+    step: T,
+    command_names: Vec<&'a str>,
+    command_strings: Vec<&'a str>,
+) -> Result<Vec<Command<'a>>, OptionsError<'static>> {
+    let values = Commands::build_parameter_scan_values(param_min, param_max, step).unwrap();
+    let axis = values
+        .iter()
+        .map(|value| vec![(param_name, value)])
+        .collect();
+    Commands::build_parametrized_commands(command_names, command_strings, &[axis])
+}
+
 #[test]
 fn test_parameter_scan_commands_int() {
-    let commands = Commands::build_parameter_scan_commands(
-        "val",
-        1i32,
-        7i32,
-        3i32,
-        vec![],
-        vec!["echo {val}"],
-    )
-    .unwrap();
+    let commands =
+        build_parameter_scan_commands("val", 1i32, 7i32, 3i32, vec![], vec!["echo {val}"]).unwrap();
     assert_eq!(commands.len(), 3);
     assert_eq!(commands[2].get_name(), "echo 7");
     assert_eq!(commands[2].get_command_line(), "echo 7");
@@ -493,7 +832,7 @@ fn test_parameter_scan_commands_decimal() {
     let param_max = Decimal::from_str("1").unwrap();
     let step = Decimal::from_str("0.33").unwrap();
 
-    let commands = Commands::build_parameter_scan_commands(
+    let commands = build_parameter_scan_commands(
         "val",
         param_min,
         param_max,
@@ -509,7 +848,7 @@ fn test_parameter_scan_commands_decimal() {
 
 #[test]
 fn test_parameter_scan_commands_names() {
-    let commands = Commands::build_parameter_scan_commands(
+    let commands = build_parameter_scan_commands(
         "val",
         1i32,
         3i32,
@@ -528,7 +867,7 @@ fn test_parameter_scan_commands_names() {
 
 #[test]
 fn test_get_specified_command_names() {
-    let commands = Commands::build_parameter_scan_commands(
+    let commands = build_parameter_scan_commands(
         "val",
         1i32,
         3i32,
@@ -547,7 +886,7 @@ fn test_get_specified_command_names() {
 
 #[test]
 fn test_different_command_name_count_with_parameters() {
-    let result = Commands::build_parameter_scan_commands(
+    let result = build_parameter_scan_commands(
         "val",
         1i32,
         3i32,
@@ -557,6 +896,186 @@ fn test_different_command_name_count_with_parameters() {
     );
     assert!(matches!(
         result.unwrap_err(),
-        ParameterScanError::UnexpectedCommandNameCount(2, 3)
+        OptionsError::UnexpectedCommandNameCount(2, 3)
     ));
 }
+
+#[test]
+fn test_build_zipped_parameter_list_commands() {
+    use crate::cli::get_cli_arguments;
+
+    let matches = get_cli_arguments(vec![
+        "hyperfine",
+        "-L",
+        "compiler",
+        "gcc,clang",
+        "-L",
+        "size",
+        "1,2",
+        "-L",
+        "flags",
+        "-O2,-O3",
+        "--parameter-zip",
+        "compiler,flags",
+        "{compiler} {flags} -DSIZE={size}",
+    ]);
+    let commands = Commands::from_cli_arguments(&matches).unwrap().0;
+    let command_lines: Vec<_> = commands.iter().map(|c| c.get_command_line()).collect();
+    assert_eq!(
+        command_lines,
+        vec![
+            "gcc -O2 -DSIZE=1",
+            "clang -O3 -DSIZE=1",
+            "gcc -O2 -DSIZE=2",
+            "clang -O3 -DSIZE=2",
+        ]
+    );
+}
+
+#[test]
+fn test_zipped_parameter_list_errors() {
+    use crate::cli::get_cli_arguments;
+
+    let error = |args: Vec<&str>| {
+        let matches = get_cli_arguments(
+            vec![
+                "hyperfine",
+                "-L",
+                "a",
+                "1,2",
+                "-L",
+                "b",
+                "x,y,z",
+                "echo {a} {b}",
+            ]
+            .into_iter()
+            .chain(args),
+        );
+        Commands::from_cli_arguments(&matches)
+            .err()
+            .unwrap()
+            .to_string()
+    };
+
+    assert_eq!(
+        error(vec!["--parameter-zip", "a,b"]),
+        "The zipped parameters 'a' and 'b' have a different number of values (2 and 3)"
+    );
+    assert!(error(vec!["--parameter-zip", "a,c"]).starts_with("Unknown parameter 'c'"));
+    assert_eq!(
+        error(vec!["--parameter-zip", "a", "--parameter-zip", "a,b"]),
+        "Parameters can only be zipped once: a"
+    );
+    assert_eq!(
+        error(vec![
+            "--parameter-zip",
+            "a",
+            "-n",
+            "x",
+            "-n",
+            "y",
+            "-n",
+            "z"
+        ]),
+        "'--command-name' has been specified 3 times. It has to appear exactly once, or \
+         exactly 6 times (number of benchmarks)"
+    );
+}
+
+#[test]
+fn test_build_filtered_parameter_commands() {
+    use crate::cli::get_cli_arguments;
+
+    let matches = get_cli_arguments(vec![
+        "hyperfine",
+        "-L",
+        "threads",
+        "1,2,4,8",
+        "-L",
+        "profile",
+        "debug,release",
+        "--parameter-filter",
+        "threads <= 4",
+        "--parameter-filter",
+        "!(profile == 'release' && threads < 2)",
+        "-n",
+        "{profile}-{threads}",
+        "run -t {threads} --{profile}",
+    ]);
+    let commands = Commands::from_cli_arguments(&matches).unwrap().0;
+    let names: Vec<_> = commands.iter().map(|c| c.get_name()).collect();
+    assert_eq!(
+        names,
+        vec!["debug-1", "debug-2", "debug-4", "release-2", "release-4"]
+    );
+}
+
+#[test]
+fn test_parameter_space_size_limit() {
+    use crate::cli::get_cli_arguments;
+
+    let error = |scans: usize| {
+        let mut args = vec!["hyperfine", "--dry-run"];
+        for name in ["a", "b", "c", "d"].iter().take(scans) {
+            args.extend(["-P", name, "1", "100000"]);
+        }
+        args.push("echo {a}{b}{c}{d}");
+        let matches = get_cli_arguments(args);
+        Commands::from_cli_arguments(&matches)
+            .err()
+            .map(|e| e.to_string())
+    };
+
+    assert_eq!(error(1), None);
+    assert_eq!(error(2), Some("Parameter range is too large".into()));
+    // The product of the sizes would overflow
+    assert_eq!(error(4), Some("Parameter range is too large".into()));
+}
+
+#[test]
+fn test_parameter_filter_errors() {
+    use crate::cli::get_cli_arguments;
+
+    let error = |args: Vec<&str>| {
+        let matches = get_cli_arguments(
+            vec!["hyperfine", "-L", "a", "1,2,3", "echo {a}"]
+                .into_iter()
+                .chain(args),
+        );
+        format!(
+            "{:#}",
+            Commands::from_cli_arguments(&matches).err().unwrap()
+        )
+    };
+
+    assert_eq!(
+        error(vec!["--parameter-filter", "a >"]),
+        "Invalid parameter filter 'a >': Unexpected end of expression"
+    );
+    assert_eq!(
+        error(vec!["--parameter-filter", "b > 1"]),
+        "Unknown parameter 'b' in the parameter filter 'b > 1'"
+    );
+    assert_eq!(
+        error(vec!["--parameter-filter", "a + 1"]),
+        "Could not evaluate the parameter filter 'a + 1': Expected a condition, got '2'"
+    );
+    assert_eq!(
+        error(vec!["--parameter-filter", "a > 3"]),
+        "All parameter combinations have been excluded by '--parameter-filter'"
+    );
+    assert_eq!(
+        error(vec![
+            "--parameter-filter",
+            "a > 1",
+            "-n",
+            "x",
+            "-n",
+            "y",
+            "-n",
+            "z"
+        ]),
+        "'--command-name' has been specified 3 times. It has to appear exactly once, or \
+         exactly 2 times (number of benchmarks)"
+    );
+}
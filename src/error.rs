@@ -15,10 +15,14 @@ pub enum ParameterScanError {
     TooLarge,
     #[error("Zero is not a valid parameter step")]
     ZeroStep,
+    #[error("The start of a geometric parameter range has to be positive")]
+    NonPositiveStart,
+    #[error("The factor of a geometric parameter range has to be larger than 1")]
+    InvalidFactor,
     #[error("A step size is required when the range bounds are floating point numbers. The step size can be specified with the '-D/--parameter-step-size <DELTA>' parameter")]
     StepRequired,
-    #[error("'--command-name' has been specified {0} times. It has to appear exactly once, or exactly {1} times (number of benchmarks)")]
-    UnexpectedCommandNameCount(usize, usize),
+    #[error("'--{0}' has been specified {1} times. It has to appear at most once, or exactly {2} times (number of parameter scans)")]
+    UnexpectedStepCount(&'static str, usize, usize),
 }
 
 impl From<num::ParseIntError> for ParameterScanError {
@@ -51,8 +55,26 @@ pub enum OptionsError<'a> {
     EmptyShell,
     #[error("Failed to parse '--shell <command>' expression as command line: {0}")]
     ShellParseError(shell_words::ParseError),
+    #[error("Invalid environment variable '{0}' for '--env'. Expected the form KEY=VALUE")]
+    InvalidEnvironmentVariable(String),
+    #[error("Failed to parse '--env' value '{0}': {1}")]
+    EnvironmentParseError(String, shell_words::ParseError),
     #[error("Unknown output policy '{0}'. Use './{0}' to output to a file named '{0}'.")]
     UnknownOutputPolicy(String),
+    #[error("Invalid target precision '{0}'. Expected a positive percentage like '1%'")]
+    InvalidTargetPrecision(String),
+    #[error(
+        "Invalid confidence level '{0}'. Expected a percentage between 0% and 100%, like '95%'"
+    )]
+    InvalidConfidenceLevel(String),
+    #[error("Invalid regression threshold '{0}'. Expected a non-negative percentage like '5%'")]
+    InvalidRegressionThreshold(String),
+    #[error("The time budget has to be a positive number of seconds")]
+    InvalidTimeBudget,
+    #[error("The number of bootstrap resamples can not be larger than {0}")]
+    TooManyBootstrapResamples(u64),
+    #[error("The timeout has to be a positive number of seconds")]
+    InvalidTimeout,
     #[error("The file '{0}' specified as '--input' does not exist")]
     StdinDataFileDoesNotExist(String),
 }
@@ -76,7 +76,7 @@ fn cfg_test_table_header(unit_short_name: &str) -> String {
 }
 
 #[cfg(test)]
-use crate::options::SortOrder;
+use crate::options::{SignificanceTest, SortOrder};
 
 #[cfg(test)]
 use crate::util::units::Unit;
@@ -97,6 +97,7 @@ fn test_asciidoc_format_s() {
         BenchmarkResult {
             command: String::from("FOO=1 BAR=2 command | 1"),
             command_with_unused_parameters: String::from("FOO=1 BAR=2 command | 1"),
+            command_line: None,
             mean: 1.0,
             stddev: Some(2.0),
             median: 1.0,
@@ -104,8 +105,18 @@ fn test_asciidoc_format_s() {
             system: 4.0,
             min: 5.0,
             max: 6.0,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![7.0, 8.0, 9.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: {
                 let mut params = BTreeMap::new();
                 params.insert("foo".into(), "1".into());
@@ -116,6 +127,7 @@ fn test_asciidoc_format_s() {
         BenchmarkResult {
             command: String::from("FOO=1 BAR=7 command | 2"),
             command_with_unused_parameters: String::from("FOO=1 BAR=7 command | 2"),
+            command_line: None,
             mean: 11.0,
             stddev: Some(12.0),
             median: 11.0,
@@ -123,8 +135,18 @@ fn test_asciidoc_format_s() {
             system: 14.0,
             min: 15.0,
             max: 16.0,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![17.0, 18.0, 19.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: {
                 let mut params = BTreeMap::new();
                 params.insert("foo".into(), "1".into());
@@ -136,7 +158,12 @@ fn test_asciidoc_format_s() {
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&results, Some(Unit::Second), SortOrder::Command)
+            .serialize(
+                &results,
+                Some(Unit::Second),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
             .unwrap(),
     )
     .unwrap();
@@ -152,7 +179,7 @@ fn test_asciidoc_format_s() {
 | 11.000 ± 12.000 
 | 15.000 
 | 16.000 
-| 11.00 ± 25.06 
+| 11.00 ± 25.06 (p < 0.001) 
 |===
 ",
         cfg_test_table_header("s")
@@ -172,6 +199,7 @@ fn test_asciidoc_format_ms() {
         BenchmarkResult {
             command: String::from("FOO=1 BAR=7 command | 2"),
             command_with_unused_parameters: String::from("FOO=1 BAR=7 command | 2"),
+            command_line: None,
             mean: 0.011,
             stddev: Some(0.012),
             median: 0.011,
@@ -179,8 +207,18 @@ fn test_asciidoc_format_ms() {
             system: 0.014,
             min: 0.015,
             max: 0.016,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.017, 0.018, 0.019]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: {
                 let mut params = BTreeMap::new();
                 params.insert("foo".into(), "1".into());
@@ -191,6 +229,7 @@ fn test_asciidoc_format_ms() {
         BenchmarkResult {
             command: String::from("FOO=1 BAR=2 command | 1"),
             command_with_unused_parameters: String::from("FOO=1 BAR=2 command | 1"),
+            command_line: None,
             mean: 1.0,
             stddev: Some(2.0),
             median: 1.0,
@@ -198,8 +237,18 @@ fn test_asciidoc_format_ms() {
             system: 4.0,
             min: 5.0,
             max: 6.0,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![7.0, 8.0, 9.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: {
                 let mut params = BTreeMap::new();
                 params.insert("foo".into(), "1".into());
@@ -211,7 +260,12 @@ fn test_asciidoc_format_ms() {
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&results, Some(Unit::MilliSecond), SortOrder::Command)
+            .serialize(
+                &results,
+                Some(Unit::MilliSecond),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
             .unwrap(),
     )
     .unwrap();
@@ -227,7 +281,7 @@ fn test_asciidoc_format_ms() {
 | 1000.0 ± 2000.0 
 | 5000.0 
 | 6000.0 
-| 90.91 ± 207.11 
+| 90.91 ± 207.11 (p = 0.005) 
 |===
 ",
         cfg_test_table_header("ms")
@@ -4,7 +4,7 @@ use csv::WriterBuilder;
 
 use super::Exporter;
 use crate::benchmark::benchmark_result::BenchmarkResult;
-use crate::options::SortOrder;
+use crate::options::{SignificanceTest, SortOrder};
 use crate::util::units::Unit;
 
 use anyhow::Result;
@@ -18,13 +18,36 @@ impl Exporter for CsvExporter {
         results: &[BenchmarkResult],
         _unit: Option<Unit>,
         _sort_order: SortOrder,
+        _significance_test: SignificanceTest,
     ) -> Result<Vec<u8>> {
         let mut writer = WriterBuilder::new().from_writer(vec![]);
 
         {
             let mut headers: Vec<Cow<[u8]>> = [
                 // The list of times and exit codes cannot be exported to the CSV file - omit them.
-                "command", "mean", "stddev", "median", "user", "system", "min", "max",
+                "command",
+                "mean",
+                "stddev",
+                "median",
+                "user",
+                "system",
+                "min",
+                "max",
+                "p5",
+                "p25",
+                "p75",
+                "p95",
+                "p99",
+                "iqr",
+                "coefficient_of_variation",
+                "max_rss_mean",
+                "max_rss_max",
+                "minor_page_faults",
+                "major_page_faults",
+                "voluntary_context_switches",
+                "involuntary_context_switches",
+                "block_input_operations",
+                "block_output_operations",
             ]
             .iter()
             .map(|x| Cow::Borrowed(x.as_bytes()))
@@ -50,6 +73,43 @@ impl Exporter for CsvExporter {
             ] {
                 fields.push(Cow::Owned(f.to_string().into_bytes()))
             }
+            match &res.extended_statistics {
+                Some(statistics) => {
+                    for f in &[
+                        statistics.p5,
+                        statistics.p25,
+                        statistics.p75,
+                        statistics.p95,
+                        statistics.p99,
+                        statistics.iqr,
+                    ] {
+                        fields.push(Cow::Owned(f.to_string().into_bytes()))
+                    }
+                    fields.push(match statistics.coefficient_of_variation {
+                        Some(cov) => Cow::Owned(cov.to_string().into_bytes()),
+                        None => Cow::Borrowed(&b""[..]),
+                    });
+                }
+                None => fields.extend(std::iter::repeat(Cow::Borrowed(&b""[..])).take(7)),
+            }
+            // Resource usage is not available on all platforms. Leave the columns empty then.
+            match &res.resource_usage {
+                Some(usage) => {
+                    for f in &[
+                        usage.max_rss_mean,
+                        usage.max_rss_max as f64,
+                        usage.minor_page_faults,
+                        usage.major_page_faults,
+                        usage.voluntary_context_switches,
+                        usage.involuntary_context_switches,
+                        usage.block_input_operations,
+                        usage.block_output_operations,
+                    ] {
+                        fields.push(Cow::Owned(f.to_string().into_bytes()))
+                    }
+                }
+                None => fields.extend(std::iter::repeat(Cow::Borrowed(&b""[..])).take(8)),
+            }
             for v in res.parameters.values() {
                 fields.push(Cow::Borrowed(v.as_bytes()))
             }
@@ -60,8 +120,81 @@ impl Exporter for CsvExporter {
     }
 }
 
+/// CSV export in "long" format, with one row for each timing run instead of one row for
+/// each command
+#[derive(Default)]
+pub struct CsvLongExporter {}
+
+impl Exporter for CsvLongExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        _unit: Option<Unit>,
+        _sort_order: SortOrder,
+        _significance_test: SignificanceTest,
+    ) -> Result<Vec<u8>> {
+        let mut writer = WriterBuilder::new().from_writer(vec![]);
+
+        let mut headers: Vec<String> = [
+            "command",
+            "run",
+            "timestamp",
+            "time_real",
+            "time_user",
+            "time_system",
+            "exit_code",
+            "timed_out",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        if let Some(res) = results.first() {
+            for param_name in res.parameters.keys() {
+                headers.push(format!("parameter_{param_name}"));
+            }
+        }
+        writer.write_record(headers)?;
+
+        // Values that are not available (e.g. in results that have been read from older
+        // JSON exports) are left empty
+        let value = |values: &Option<Vec<f64>>, run: usize| {
+            values
+                .as_ref()
+                .and_then(|values| values.get(run))
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+        };
+
+        for res in results {
+            let num_runs = res.times.as_ref().map_or(0, |times| times.len());
+            for run in 0..num_runs {
+                let mut fields = vec![
+                    res.command.clone(),
+                    (run + 1).to_string(),
+                    value(&res.timestamps, run),
+                    value(&res.times, run),
+                    value(&res.times_user, run),
+                    value(&res.times_system, run),
+                    res.exit_codes
+                        .get(run)
+                        .cloned()
+                        .flatten()
+                        .map(|code| code.to_string())
+                        .unwrap_or_default(),
+                    res.timed_out.get(run).cloned().unwrap_or(false).to_string(),
+                ];
+                fields.extend(res.parameters.values().cloned());
+                writer.write_record(fields)?;
+            }
+        }
+
+        Ok(writer.into_inner()?)
+    }
+}
+
 #[test]
 fn test_csv() {
+    use crate::benchmark::benchmark_result::{ExtendedStatistics, ResourceUsageSummary};
     use std::collections::BTreeMap;
     let exporter = CsvExporter::default();
 
@@ -69,6 +202,7 @@ fn test_csv() {
         BenchmarkResult {
             command: String::from("FOO=one BAR=two command | 1"),
             command_with_unused_parameters: String::from("FOO=one BAR=two command | 1"),
+            command_line: None,
             mean: 1.0,
             stddev: Some(2.0),
             median: 1.0,
@@ -76,8 +210,35 @@ fn test_csv() {
             system: 4.0,
             min: 5.0,
             max: 6.0,
+            extended_statistics: Some(ExtendedStatistics {
+                p5: 5.1,
+                p25: 5.5,
+                p75: 5.75,
+                p95: 5.9,
+                p99: 5.99,
+                iqr: 0.25,
+                coefficient_of_variation: Some(2.0),
+            }),
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![7.0, 8.0, 9.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: Some(ResourceUsageSummary {
+                max_rss_mean: 2048.0,
+                max_rss_max: 4096,
+                minor_page_faults: 10.0,
+                major_page_faults: 0.0,
+                voluntary_context_switches: 1.5,
+                involuntary_context_switches: 2.0,
+                block_input_operations: 0.0,
+                block_output_operations: 8.0,
+            }),
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: {
                 let mut params = BTreeMap::new();
                 params.insert("foo".into(), "one".into());
@@ -88,6 +249,7 @@ fn test_csv() {
         BenchmarkResult {
             command: String::from("FOO=one BAR=seven command | 2"),
             command_with_unused_parameters: String::from("FOO=one BAR=seven command | 2"),
+            command_line: None,
             mean: 11.0,
             stddev: Some(12.0),
             median: 11.0,
@@ -95,8 +257,18 @@ fn test_csv() {
             system: 14.0,
             min: 15.0,
             max: 16.5,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![17.0, 18.0, 19.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: {
                 let mut params = BTreeMap::new();
                 params.insert("foo".into(), "one".into());
@@ -106,17 +278,71 @@ fn test_csv() {
         },
     ];
     let exps: String = String::from(
-        "command,mean,stddev,median,user,system,min,max,parameter_bar,parameter_foo\n\
-        FOO=one BAR=two command | 1,1,2,1,3,4,5,6,two,one\n\
-        FOO=one BAR=seven command | 2,11,12,11,13,14,15,16.5,seven,one\n\
+        "command,mean,stddev,median,user,system,min,max,p5,p25,p75,p95,p99,iqr,\
+        coefficient_of_variation,max_rss_mean,max_rss_max,minor_page_faults,major_page_faults,\
+        voluntary_context_switches,involuntary_context_switches,block_input_operations,block_output_operations,\
+        parameter_bar,parameter_foo\n\
+        FOO=one BAR=two command | 1,1,2,1,3,4,5,6,5.1,5.5,5.75,5.9,5.99,0.25,2,2048,4096,10,0,1.5,2,0,8,two,one\n\
+        FOO=one BAR=seven command | 2,11,12,11,13,14,15,16.5,,,,,,,,,,,,,,,,seven,one\n\
         ",
     );
     let gens = String::from_utf8(
         exporter
-            .serialize(&results, Some(Unit::Second), SortOrder::Command)
+            .serialize(
+                &results,
+                Some(Unit::Second),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
             .unwrap(),
     )
     .unwrap();
 
     assert_eq!(exps, gens);
 }
+
+#[test]
+fn test_csv_long() {
+    use std::collections::BTreeMap;
+
+    let results = vec![
+        BenchmarkResult {
+            command: String::from("sleep 0.1"),
+            times: Some(vec![0.1, 0.2]),
+            times_user: Some(vec![0.01, 0.02]),
+            times_system: Some(vec![0.03, 0.04]),
+            timestamps: Some(vec![1700000000.5, 1700000001.0]),
+            exit_codes: vec![Some(0), None],
+            timed_out: vec![false, true],
+            parameters: BTreeMap::from([("t".to_string(), "0.1".to_string())]),
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: String::from("sleep 0.3"),
+            times: Some(vec![0.3]),
+            exit_codes: vec![Some(1)],
+            parameters: BTreeMap::from([("t".to_string(), "0.3".to_string())]),
+            ..Default::default()
+        },
+    ];
+
+    let gens = String::from_utf8(
+        CsvLongExporter::default()
+            .serialize(
+                &results,
+                Some(Unit::Second),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        gens,
+        "command,run,timestamp,time_real,time_user,time_system,exit_code,timed_out,parameter_t\n\
+         sleep 0.1,1,1700000000.5,0.1,0.01,0.03,0,false,0.1\n\
+         sleep 0.1,2,1700000001,0.2,0.02,0.04,,true,0.1\n\
+         sleep 0.3,1,,0.3,,,1,false,0.3\n"
+    );
+}
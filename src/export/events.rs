@@ -0,0 +1,179 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use serde::Serialize;
+
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::util::timestamp::unix_timestamp;
+use crate::util::units::Second;
+
+use anyhow::{Context, Result};
+
+/// Something that happened during a benchmark. Benchmarks are numbered like in the terminal
+/// output (starting at 1), and so are the runs of a benchmark.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    /// Measurement of the time that is needed to spawn the shell
+    Calibration { time_overhead: Second },
+
+    BenchmarkStarted {
+        benchmark: usize,
+        command: &'a str,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        parameters: BTreeMap<&'a str, String>,
+    },
+
+    /// The `--setup` command has been executed
+    Setup { benchmark: usize, time_real: Second },
+
+    WarmupRun {
+        benchmark: usize,
+        run: u64,
+        time_real: Second,
+        exit_code: Option<i32>,
+    },
+
+    /// The `--prepare` command has been executed
+    Prepare { benchmark: usize, time_real: Second },
+
+    TimingRun {
+        benchmark: usize,
+        run: u64,
+        time_real: Second,
+        time_user: Second,
+        time_system: Second,
+        exit_code: Option<i32>,
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        timed_out: bool,
+    },
+
+    /// The `--conclude` command has been executed
+    Conclude { benchmark: usize, time_real: Second },
+
+    /// The `--cleanup` command has been executed
+    Cleanup { benchmark: usize, time_real: Second },
+
+    BenchmarkFinished {
+        benchmark: usize,
+        result: &'a BenchmarkResult,
+    },
+}
+
+#[derive(Serialize)]
+struct TimestampedEvent<'a> {
+    /// Seconds since the Unix epoch
+    timestamp: f64,
+
+    #[serde(flatten)]
+    event: &'a Event<'a>,
+}
+
+/// Log of all events as newline-delimited JSON (see `--export-events`), which is written
+/// while the benchmarks are running. Events are discarded if no log has been requested.
+#[derive(Default)]
+pub struct EventLog {
+    writer: Option<RefCell<Box<dyn Write>>>,
+}
+
+impl EventLog {
+    /// Write the events to the given file, or to stdout for "-"
+    pub fn new(filename: &str) -> Result<Self> {
+        let writer: Box<dyn Write> = if filename == "-" {
+            Box::new(io::stdout())
+        } else {
+            let file = File::create(filename)
+                .with_context(|| format!("Could not create event log '{filename}'"))?;
+            Box::new(BufWriter::new(file))
+        };
+
+        Ok(EventLog {
+            writer: Some(RefCell::new(writer)),
+        })
+    }
+
+    #[cfg(test)]
+    fn with_writer(writer: Box<dyn Write>) -> Self {
+        EventLog {
+            writer: Some(RefCell::new(writer)),
+        }
+    }
+
+    /// Append an event to the log. Every event is flushed immediately, such that the log
+    /// can be followed live.
+    pub fn emit(&self, event: Event) -> Result<()> {
+        let writer = match &self.writer {
+            Some(writer) => writer,
+            None => return Ok(()),
+        };
+
+        let mut line = serde_json::to_vec(&TimestampedEvent {
+            timestamp: unix_timestamp(),
+            event: &event,
+        })?;
+        line.push(b'\n');
+
+        let mut writer = writer.borrow_mut();
+        writer
+            .write_all(&line)
+            .and_then(|_| writer.flush())
+            .context("Failed to write to the event log")
+    }
+}
+
+#[test]
+fn test_event_log() {
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Buffer::default();
+    let log = EventLog::with_writer(Box::new(buffer.clone()));
+    log.emit(Event::Setup {
+        benchmark: 1,
+        time_real: 0.5,
+    })
+    .unwrap();
+    log.emit(Event::TimingRun {
+        benchmark: 1,
+        run: 2,
+        time_real: 1.0,
+        time_user: 0.25,
+        time_system: 0.125,
+        exit_code: Some(0),
+        timed_out: false,
+    })
+    .unwrap();
+
+    let output = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["event"], "setup");
+    assert_eq!(lines[0]["time_real"], 0.5);
+    assert!(lines[0]["timestamp"].as_f64().unwrap() > 0.0);
+    assert_eq!(lines[1]["event"], "timing_run");
+    assert_eq!(lines[1]["run"], 2);
+    assert_eq!(lines[1]["exit_code"], 0);
+    assert!(lines[1].get("timed_out").is_none());
+
+    // Without a log, events are discarded
+    EventLog::default()
+        .emit(Event::Calibration { time_overhead: 0.1 })
+        .unwrap();
+}
@@ -0,0 +1,230 @@
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use super::markup::determine_unit_from_results;
+use super::svg::{self, escape};
+use super::Exporter;
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::benchmark::relative_speed::{self, BenchmarkResultWithRelativeSpeed};
+use crate::options::{SignificanceTest, SortOrder};
+use crate::output::format::{format_duration_value, format_p_value};
+use crate::util::units::Unit;
+
+use anyhow::Result;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 1320px; padding: 0 1em; color: #222; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: right; }
+th:first-child, td:first-child { text-align: left; }
+code { font-family: monospace; }
+.charts { display: flex; flex-wrap: wrap; gap: 1em; }
+.swatch { display: inline-block; width: 0.8em; height: 0.8em; margin-right: 0.4em; }
+.note { color: #777; }
+";
+
+/// Self-contained HTML report with the results table, the speed comparison and charts of
+/// the run times (as inline SVG, such that the report can be viewed offline).
+#[derive(Default)]
+pub struct HtmlExporter {}
+
+impl Exporter for HtmlExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        unit: Option<Unit>,
+        sort_order: SortOrder,
+        significance_test: SignificanceTest,
+    ) -> Result<Vec<u8>> {
+        let unit = unit.unwrap_or_else(|| determine_unit_from_results(results));
+        let entries = relative_speed::compute(results, sort_order, significance_test);
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<title>Benchmark results</title>\n");
+        let _ = writeln!(html, "<style>{STYLE}</style>");
+        html.push_str("</head>\n<body>\n<h1>Benchmark results</h1>\n");
+
+        html.push_str(&results_table(results, &entries, unit));
+        html.push_str(&speed_comparison(&entries));
+
+        if let Some(chart) = svg::whisker(results, unit) {
+            let _ = writeln!(html, "<h2>Distribution</h2>\n{chart}");
+        }
+
+        if let Some(chart) = svg::parametrized(results, unit) {
+            let _ = writeln!(html, "<h2>Parameters</h2>\n{chart}");
+        }
+
+        for (index, result) in results.iter().enumerate() {
+            let charts: Vec<String> = vec![
+                svg::command_histogram(result, unit, index),
+                svg::command_progression(result, unit, index),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if charts.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(
+                html,
+                "<h3><span class=\"swatch\" style=\"background: {}\"></span><code>{}</code></h3>",
+                svg::color(index),
+                escape(&result.command_with_unused_parameters)
+            );
+            let _ = writeln!(html, "<div class=\"charts\">\n{}</div>", charts.join(""));
+        }
+
+        html.push_str("</body>\n</html>\n");
+
+        Ok(html.into_bytes())
+    }
+}
+
+fn results_table(
+    results: &[BenchmarkResult],
+    entries: &[BenchmarkResultWithRelativeSpeed],
+    unit: Unit,
+) -> String {
+    let notation = format!("[{}]", unit.short_name());
+    let format = |value| format_duration_value(value, Some(unit)).0;
+
+    let mut table = String::from("<h2>Results</h2>\n<table>\n");
+    let _ = writeln!(
+        table,
+        "<tr><th>Command</th><th>Runs</th><th>Mean {notation}</th><th>Median {notation}</th>\
+         <th>Min {notation}</th><th>Max {notation}</th><th>Relative</th></tr>"
+    );
+
+    for entry in entries {
+        let result = entry.result;
+        let index = results
+            .iter()
+            .position(|r| std::ptr::eq(r, result))
+            .unwrap_or(0);
+
+        let stddev_str = result
+            .stddev
+            .map(|stddev| format!(" ± {}", format(stddev)))
+            .unwrap_or_default();
+        let relative_stddev_str = match entry.relative_speed_stddev {
+            Some(stddev) if !entry.is_reference => format!(" ± {stddev:.2}"),
+            _ => "".into(),
+        };
+        let incomplete_str = if result.incomplete {
+            " <span class=\"note\">(incomplete)</span>"
+        } else {
+            ""
+        };
+
+        let _ = writeln!(
+            table,
+            "<tr><td><span class=\"swatch\" style=\"background: {}\"></span><code>{}</code>{}</td>\
+             <td>{}</td><td>{}{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.2}{}</td></tr>",
+            svg::color(index),
+            escape(&result.command_with_unused_parameters),
+            incomplete_str,
+            result.times.as_ref().map_or(0, |times| times.len()),
+            format(result.mean),
+            stddev_str,
+            format(result.median),
+            format(result.min),
+            format(result.max),
+            entry.relative_speed,
+            relative_stddev_str,
+        );
+    }
+
+    table.push_str("</table>\n");
+    table
+}
+
+/// Speed comparison with the fastest command, worded like the summary in the terminal
+fn speed_comparison(entries: &[BenchmarkResultWithRelativeSpeed]) -> String {
+    let reference = match entries.iter().find(|entry| entry.is_reference) {
+        Some(reference) if entries.len() > 1 => reference,
+        _ => return "".into(),
+    };
+
+    let mut html = String::from("<h2>Summary</h2>\n");
+    let _ = writeln!(
+        html,
+        "<p><code>{}</code> ran</p>\n<ul>",
+        escape(&reference.result.command_with_unused_parameters)
+    );
+
+    for entry in entries.iter().filter(|entry| !entry.is_reference) {
+        let stddev_str = entry
+            .relative_speed_stddev
+            .map(|stddev| format!(" ± {stddev:.2}"))
+            .unwrap_or_default();
+        let comparator = match entry.relative_ordering {
+            Ordering::Less => "times slower than",
+            Ordering::Greater => "times faster than",
+            Ordering::Equal => "times as fast as",
+        };
+        let significance_str = match entry.significance {
+            Some(significance) if significance.is_significant() => {
+                format!(" ({})", escape(&format_p_value(significance.p_value)))
+            }
+            Some(significance) => format!(
+                " <span class=\"note\">(not significant, {})</span>",
+                escape(&format_p_value(significance.p_value))
+            ),
+            None => "".into(),
+        };
+
+        let _ = writeln!(
+            html,
+            "<li><b>{:.2}</b>{} {} <code>{}</code>{}</li>",
+            entry.relative_speed,
+            stddev_str,
+            comparator,
+            escape(&entry.result.command_with_unused_parameters),
+            significance_str,
+        );
+    }
+
+    html.push_str("</ul>\n");
+    html
+}
+
+#[test]
+fn test_html_export() {
+    let result = |command: &str, times: Vec<f64>| {
+        let mean = times.iter().sum::<f64>() / times.len() as f64;
+        BenchmarkResult {
+            command: command.into(),
+            command_with_unused_parameters: command.into(),
+            mean,
+            median: mean,
+            min: mean,
+            max: mean,
+            times: Some(times),
+            ..Default::default()
+        }
+    };
+    let results = vec![
+        result("sleep 0.1 && echo '<done>'", vec![0.1, 0.11, 0.09]),
+        result("sleep 0.2", vec![0.2, 0.21, 0.19]),
+    ];
+
+    let html = String::from_utf8(
+        HtmlExporter::default()
+            .serialize(&results, None, SortOrder::Command, SignificanceTest::Welch)
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.ends_with("</html>\n"));
+    assert!(html.contains("<th>Mean [ms]</th>"));
+    assert!(html.contains("<code>sleep 0.1 &amp;&amp; echo &#39;&lt;done&gt;&#39;</code>"));
+    assert!(!html.contains("<done>"));
+    assert!(html.contains("<b>2.00</b> times faster than <code>sleep 0.2</code>"));
+
+    // A box plot, plus a histogram and a progression chart for each command
+    assert_eq!(html.matches("<svg").count(), 5);
+}
@@ -1,20 +1,108 @@
+use std::fs::File;
+use std::io::BufReader;
+
 use serde::*;
 use serde_json::to_vec_pretty;
 
 use super::Exporter;
 use crate::benchmark::benchmark_result::BenchmarkResult;
-use crate::options::SortOrder;
-use crate::util::units::Unit;
+use crate::benchmark::relative_speed;
+use crate::options::{SignificanceTest, SortOrder};
+use crate::util::units::{Scalar, Unit};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 #[derive(Serialize, Debug)]
 struct HyperfineSummary<'a> {
     results: &'a [BenchmarkResult],
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    comparison: Vec<Comparison<'a>>,
+}
+
+/// The part of a JSON export that is needed to read the results back in
+#[derive(Deserialize)]
+struct ExportedResults {
+    results: Vec<BenchmarkResult>,
 }
 
-#[derive(Default)]
-pub struct JsonExporter {}
+/// Read the benchmark results from a file that has been written with `--export-json`
+pub fn read_results(filename: &str) -> Result<Vec<BenchmarkResult>> {
+    let file = File::open(filename)
+        .with_context(|| format!("Could not open JSON export file '{filename}'"))?;
+    let exported: ExportedResults = serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Could not read results from JSON export file '{filename}'"))?;
+
+    Ok(exported
+        .results
+        .into_iter()
+        .map(|mut result| {
+            result.command_with_unused_parameters = result.command.clone();
+            result
+        })
+        .collect())
+}
+
+/// Comparison of a single benchmark with the reference command, or with the fastest one if
+/// there is no reference command
+#[derive(Serialize, Debug)]
+struct Comparison<'a> {
+    command: &'a str,
+    reference: &'a str,
+    relative_speed: Scalar,
+    relative_speed_stddev: Option<Scalar>,
+    significance_test: &'static str,
+    p_value: Scalar,
+    significant: bool,
+}
+
+impl<'a> Comparison<'a> {
+    fn from_results(
+        results: &'a [BenchmarkResult],
+        has_reference_command: bool,
+        significance_test: SignificanceTest,
+    ) -> Vec<Self> {
+        if results.len() < 2 {
+            return vec![];
+        }
+
+        let reference = relative_speed::reference_of(results, has_reference_command);
+        let entries = relative_speed::compute_from_reference(
+            results,
+            reference,
+            SortOrder::Command,
+            significance_test,
+        );
+
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let significance = entry.significance?;
+                Some(Comparison {
+                    command: &entry.result.command,
+                    reference: &reference.command,
+                    relative_speed: entry.relative_speed,
+                    relative_speed_stddev: entry.relative_speed_stddev,
+                    significance_test: significance.test.name(),
+                    p_value: significance.p_value,
+                    significant: significance.is_significant(),
+                })
+            })
+            .collect()
+    }
+}
+
+pub struct JsonExporter {
+    has_reference_command: bool,
+}
+
+impl JsonExporter {
+    pub fn new(has_reference_command: bool) -> Self {
+        JsonExporter {
+            has_reference_command,
+        }
+    }
+}
 
 impl Exporter for JsonExporter {
     fn serialize(
@@ -22,8 +110,16 @@ impl Exporter for JsonExporter {
         results: &[BenchmarkResult],
         _unit: Option<Unit>,
         _sort_order: SortOrder,
+        significance_test: SignificanceTest,
     ) -> Result<Vec<u8>> {
-        let mut output = to_vec_pretty(&HyperfineSummary { results });
+        let mut output = to_vec_pretty(&HyperfineSummary {
+            results,
+            comparison: Comparison::from_results(
+                results,
+                self.has_reference_command,
+                significance_test,
+            ),
+        });
         if let Ok(ref mut content) = output {
             content.push(b'\n');
         }
@@ -0,0 +1,257 @@
+use std::fmt::Write;
+
+use super::svg::escape;
+use super::Exporter;
+use crate::benchmark::baseline::Verdict;
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::options::{SignificanceTest, SortOrder};
+use crate::output::format::format_p_value;
+use crate::util::units::Unit;
+
+use anyhow::Result;
+
+/// JUnit XML report for CI dashboards. Each command is a test case with the mean run time
+/// as its duration. Failed runs, a target precision that has not been reached within the
+/// budget and regressions compared to the `--baseline` are reported as failures.
+#[derive(Default)]
+pub struct JunitExporter {}
+
+impl Exporter for JunitExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        _unit: Option<Unit>,
+        _sort_order: SortOrder,
+        _significance_test: SignificanceTest,
+    ) -> Result<Vec<u8>> {
+        let num_failures = results.iter().filter(|r| !failures(r).is_empty()).count();
+        let total_time: f64 = results.iter().map(|r| r.mean).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"hyperfine\" tests=\"{}\" failures=\"{num_failures}\" time=\"{total_time:.6}\">",
+            results.len()
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"hyperfine\" tests=\"{}\" failures=\"{num_failures}\" errors=\"0\" \
+             skipped=\"0\" time=\"{total_time:.6}\">",
+            results.len()
+        );
+
+        for result in results {
+            xml.push_str(&testcase(result));
+        }
+
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+
+        Ok(xml.into_bytes())
+    }
+}
+
+fn testcase(result: &BenchmarkResult) -> String {
+    let mut xml = String::new();
+    let _ = writeln!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"hyperfine\" time=\"{:.6}\">",
+        escape(&result.command_with_unused_parameters),
+        result.mean
+    );
+
+    xml.push_str("      <properties>\n");
+    for (name, value) in properties(result) {
+        let _ = writeln!(
+            xml,
+            "        <property name=\"{}\" value=\"{}\"/>",
+            escape(&name),
+            escape(&value)
+        );
+    }
+    xml.push_str("      </properties>\n");
+
+    let failures = failures(result);
+    if !failures.is_empty() {
+        let _ = writeln!(
+            xml,
+            "      <failure message=\"{}\">{}</failure>",
+            escape(&failures.join("; ")),
+            escape(&failures.join("\n"))
+        );
+    }
+
+    xml.push_str("    </testcase>\n");
+    xml
+}
+
+/// Statistics of the benchmark (times in seconds) and its parameter values
+fn properties(result: &BenchmarkResult) -> Vec<(String, String)> {
+    let mut properties = vec![
+        ("command".to_string(), result.command.clone()),
+        (
+            "runs".to_string(),
+            result
+                .times
+                .as_ref()
+                .map_or(0, |times| times.len())
+                .to_string(),
+        ),
+        ("mean".to_string(), result.mean.to_string()),
+    ];
+    if let Some(stddev) = result.stddev {
+        properties.push(("stddev".to_string(), stddev.to_string()));
+    }
+    properties.extend(vec![
+        ("median".to_string(), result.median.to_string()),
+        ("user".to_string(), result.user.to_string()),
+        ("system".to_string(), result.system.to_string()),
+        ("min".to_string(), result.min.to_string()),
+        ("max".to_string(), result.max.to_string()),
+    ]);
+
+    if let Some(statistics) = &result.extended_statistics {
+        properties.extend(vec![
+            ("p5".to_string(), statistics.p5.to_string()),
+            ("p25".to_string(), statistics.p25.to_string()),
+            ("p75".to_string(), statistics.p75.to_string()),
+            ("p95".to_string(), statistics.p95.to_string()),
+            ("p99".to_string(), statistics.p99.to_string()),
+            ("iqr".to_string(), statistics.iqr.to_string()),
+        ]);
+    }
+    if let Some(precision) = result.precision.and_then(|p| p.achieved) {
+        properties.push(("precision".to_string(), precision.to_string()));
+    }
+    if let Some(baseline) = &result.baseline {
+        properties.push((
+            "baseline_mean".to_string(),
+            baseline.baseline_mean.to_string(),
+        ));
+        properties.push((
+            "baseline_relative_change".to_string(),
+            baseline.relative_change.to_string(),
+        ));
+    }
+    if result.incomplete {
+        properties.push(("incomplete".to_string(), "true".to_string()));
+    }
+    for (name, value) in &result.parameters {
+        properties.push((format!("parameter.{name}"), value.clone()));
+    }
+
+    properties
+}
+
+/// Reasons for the test case to fail, if any
+fn failures(result: &BenchmarkResult) -> Vec<String> {
+    let mut failures = vec![];
+
+    let num_runs = result.exit_codes.len();
+    let num_timed_out = result.timed_out.iter().filter(|&&t| t).count();
+    let num_failed = result
+        .exit_codes
+        .iter()
+        .zip(
+            result
+                .timed_out
+                .iter()
+                .cloned()
+                .chain(std::iter::repeat(false)),
+        )
+        .filter(|&(code, timed_out)| !timed_out && *code != Some(0))
+        .count();
+    if num_timed_out > 0 {
+        failures.push(format!("{num_timed_out} of {num_runs} runs timed out"));
+    }
+    if num_failed > 0 {
+        failures.push(format!(
+            "{num_failed} of {num_runs} runs failed with a non-zero exit code"
+        ));
+    }
+
+    if let Some(precision) = result.precision.filter(|p| !p.is_reached()) {
+        let achieved = precision
+            .achieved
+            .map(|achieved| format!(" (achieved ±{:.1}%)", achieved * 100.0))
+            .unwrap_or_default();
+        failures.push(format!(
+            "Target precision of ±{:.1}% not reached within the budget{achieved}",
+            precision.target * 100.0
+        ));
+    }
+
+    if let Some(baseline) = result
+        .baseline
+        .as_ref()
+        .filter(|b| b.verdict == Verdict::Regression)
+    {
+        let p_value = baseline
+            .p_value
+            .map(|p| format!(" ({})", format_p_value(p)))
+            .unwrap_or_default();
+        failures.push(format!(
+            "{:.1}% slower than the baseline{p_value}",
+            baseline.relative_change * 100.0
+        ));
+    }
+
+    failures
+}
+
+#[test]
+fn test_junit_export() {
+    use crate::benchmark::baseline::BaselineComparison;
+    use crate::benchmark::benchmark_result::PrecisionOutcome;
+    use std::collections::BTreeMap;
+
+    let results = vec![
+        BenchmarkResult {
+            command: "sleep 0.1".into(),
+            command_with_unused_parameters: "sleep 0.1".into(),
+            mean: 0.1,
+            stddev: Some(0.01),
+            times: Some(vec![0.09, 0.1, 0.11]),
+            exit_codes: vec![Some(0), Some(0), Some(0)],
+            parameters: BTreeMap::from([("t".to_string(), "0.1".to_string())]),
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: "grep x <file>".into(),
+            command_with_unused_parameters: "grep x <file>".into(),
+            mean: 0.2,
+            times: Some(vec![0.2, 0.2]),
+            exit_codes: vec![Some(0), Some(1)],
+            precision: Some(PrecisionOutcome {
+                target: 0.01,
+                achieved: Some(0.025),
+            }),
+            baseline: Some(BaselineComparison {
+                baseline_mean: 0.1,
+                relative_change: 1.0,
+                p_value: None,
+                verdict: Verdict::Regression,
+            }),
+            ..Default::default()
+        },
+    ];
+
+    let xml = String::from_utf8(
+        JunitExporter::default()
+            .serialize(&results, None, SortOrder::Command, SignificanceTest::Welch)
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+    assert!(xml.contains("<testsuite name=\"hyperfine\" tests=\"2\" failures=\"1\""));
+    assert!(xml.contains("<testcase name=\"sleep 0.1\" classname=\"hyperfine\" time=\"0.100000\">"));
+    assert!(xml.contains("<property name=\"stddev\" value=\"0.01\"/>"));
+    assert!(xml.contains("<property name=\"parameter.t\" value=\"0.1\"/>"));
+    assert!(xml.contains("<testcase name=\"grep x &lt;file&gt;\""));
+    assert!(xml.contains(
+        "<failure message=\"1 of 2 runs failed with a non-zero exit code; \
+         Target precision of ±1.0% not reached within the budget (achieved ±2.5%); \
+         100.0% slower than the baseline\">"
+    ));
+    assert_eq!(xml.matches("<failure").count(), 1);
+}
@@ -29,7 +29,7 @@ impl MarkupExporter for MarkdownExporter {
 }
 
 #[cfg(test)]
-use crate::options::SortOrder;
+use crate::options::{SignificanceTest, SortOrder};
 
 /// Check Markdown-based data row formatting
 #[test]
@@ -74,6 +74,7 @@ fn test_markdown_format_ms() {
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -81,13 +82,24 @@ fn test_markdown_format_ms() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -95,22 +107,37 @@ fn test_markdown_format_ms() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&timing_results, None, SortOrder::Command)
+            .serialize(
+                &timing_results,
+                None,
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
             .unwrap(),
     )
     .unwrap();
     let expect = format!(
         "{}\
 | `sleep 0.1` | 105.7 ± 1.6 | 102.3 | 108.0 | 1.00 |
-| `sleep 2` | 2005.0 ± 2.0 | 2002.0 | 2008.0 | 18.97 ± 0.29 |
+| `sleep 2` | 2005.0 ± 2.0 | 2002.0 | 2008.0 | 18.97 ± 0.29 (p < 0.001) |
 ",
         cfg_test_table_header("ms".to_string())
     );
@@ -131,6 +158,7 @@ fn test_markdown_format_s() {
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -138,13 +166,24 @@ fn test_markdown_format_s() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -152,21 +191,36 @@ fn test_markdown_format_s() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&timing_results, None, SortOrder::Command)
+            .serialize(
+                &timing_results,
+                None,
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
             .unwrap(),
     )
     .unwrap();
     let expect = format!(
         "{}\
-| `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 |
+| `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 (p < 0.001) |
 | `sleep 0.1` | 0.106 ± 0.002 | 0.102 | 0.108 | 1.00 |
 ",
         cfg_test_table_header("s".to_string())
@@ -188,6 +242,7 @@ fn test_markdown_format_time_unit_s() {
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -195,13 +250,24 @@ fn test_markdown_format_time_unit_s() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -209,8 +275,18 @@ fn test_markdown_format_time_unit_s() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
@@ -218,13 +294,18 @@ fn test_markdown_format_time_unit_s() {
     {
         let actual = String::from_utf8(
             exporter
-                .serialize(&timing_results, Some(Unit::Second), SortOrder::Command)
+                .serialize(
+                    &timing_results,
+                    Some(Unit::Second),
+                    SortOrder::Command,
+                    SignificanceTest::Welch,
+                )
                 .unwrap(),
         )
         .unwrap();
         let expect = format!(
             "{}\
-| `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 |
+| `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 (p < 0.001) |
 | `sleep 0.1` | 0.106 ± 0.002 | 0.102 | 0.108 | 1.00 |
 ",
             cfg_test_table_header("s".to_string())
@@ -236,14 +317,19 @@ fn test_markdown_format_time_unit_s() {
     {
         let actual = String::from_utf8(
             exporter
-                .serialize(&timing_results, Some(Unit::Second), SortOrder::MeanTime)
+                .serialize(
+                    &timing_results,
+                    Some(Unit::Second),
+                    SortOrder::MeanTime,
+                    SignificanceTest::Welch,
+                )
                 .unwrap(),
         )
         .unwrap();
         let expect = format!(
             "{}\
 | `sleep 0.1` | 0.106 ± 0.002 | 0.102 | 0.108 | 1.00 |
-| `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 |
+| `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 (p < 0.001) |
 ",
             cfg_test_table_header("s".to_string())
         );
@@ -266,6 +352,7 @@ fn test_markdown_format_time_unit_ms() {
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -273,13 +360,24 @@ fn test_markdown_format_time_unit_ms() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -287,21 +385,36 @@ fn test_markdown_format_time_unit_ms() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&timing_results, Some(Unit::MilliSecond), SortOrder::Command)
+            .serialize(
+                &timing_results,
+                Some(Unit::MilliSecond),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
             .unwrap(),
     )
     .unwrap();
     let expect = format!(
         "{}\
-| `sleep 2` | 2005.0 ± 2.0 | 2002.0 | 2008.0 | 18.97 ± 0.29 |
+| `sleep 2` | 2005.0 ± 2.0 | 2002.0 | 2008.0 | 18.97 ± 0.29 (p < 0.001) |
 | `sleep 0.1` | 105.7 ± 1.6 | 102.3 | 108.0 | 1.00 |
 ",
         cfg_test_table_header("ms".to_string())
@@ -309,3 +422,110 @@ fn test_markdown_format_time_unit_ms() {
 
     assert_eq!(expect, actual);
 }
+
+/// Percentiles, IQR and coefficient of variation are exported in a second table
+#[test]
+fn test_markdown_format_extended_statistics() {
+    use super::Exporter;
+    use crate::benchmark::benchmark_result::{BenchmarkResult, ExtendedStatistics};
+    use crate::util::units::Unit;
+    let exporter = MarkdownExporter::default();
+
+    let times = vec![0.1, 0.2, 0.3, 0.4, 0.5];
+    let timing_results = vec![BenchmarkResult {
+        command: String::from("sleep 0.3"),
+        command_with_unused_parameters: String::from("sleep 0.3"),
+        mean: 0.3,
+        stddev: Some(0.15),
+        median: 0.3,
+        min: 0.1,
+        max: 0.5,
+        extended_statistics: ExtendedStatistics::from_times(&times, 0.3, Some(0.15)),
+        times: Some(times),
+        ..Default::default()
+    }];
+
+    let actual = String::from_utf8(
+        exporter
+            .serialize(
+                &timing_results,
+                Some(Unit::MilliSecond),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    let expect = format!(
+        "{}\
+| `sleep 0.3` | 300.0 ± 150.0 | 100.0 | 500.0 | 1.00 |
+
+| Command | P5 [ms] | P25 [ms] | P75 [ms] | P95 [ms] | P99 [ms] | IQR [ms] | CoV |
+|:---|---:|---:|---:|---:|---:|---:|---:|
+| `sleep 0.3` | 120.0 | 200.0 | 400.0 | 480.0 | 496.0 | 200.0 | 50.00% |
+",
+        cfg_test_table_header("ms".to_string())
+    );
+
+    assert_eq!(expect, actual);
+}
+
+/// The comparison with the baseline is exported in a separate table
+#[test]
+fn test_markdown_format_baseline_comparison() {
+    use super::Exporter;
+    use crate::benchmark::baseline::{BaselineComparison, Verdict};
+    use crate::benchmark::benchmark_result::BenchmarkResult;
+    use crate::util::units::Unit;
+    let exporter = MarkdownExporter::default();
+
+    let timing_results = vec![
+        BenchmarkResult {
+            command: String::from("sleep 1.5"),
+            command_with_unused_parameters: String::from("sleep 1.5"),
+            mean: 1.5,
+            min: 1.5,
+            max: 1.5,
+            baseline: Some(BaselineComparison {
+                baseline_mean: 1.0,
+                relative_change: 0.5,
+                p_value: Some(0.0001),
+                verdict: Verdict::Regression,
+            }),
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: String::from("sleep 2"),
+            command_with_unused_parameters: String::from("sleep 2"),
+            mean: 2.0,
+            min: 2.0,
+            max: 2.0,
+            ..Default::default()
+        },
+    ];
+
+    let actual = String::from_utf8(
+        exporter
+            .serialize(
+                &timing_results,
+                Some(Unit::Second),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    let expect = format!(
+        "{}\
+| `sleep 1.5` | 1.500 | 1.500 | 1.500 | 1.00 |
+| `sleep 2` | 2.000 | 2.000 | 2.000 | 1.33 |
+
+| Command | Baseline [s] | Current [s] | Change | p-value | Verdict |
+|:---|---:|---:|---:|---:|:---|
+| `sleep 1.5` | 1.000 | 1.500 | +50.0% | p < 0.001 | regression |
+",
+        cfg_test_table_header("s".to_string())
+    );
+
+    assert_eq!(expect, actual);
+}
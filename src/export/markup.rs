@@ -1,7 +1,8 @@
+use crate::benchmark::baseline::Verdict;
 use crate::benchmark::relative_speed::BenchmarkResultWithRelativeSpeed;
 use crate::benchmark::{benchmark_result::BenchmarkResult, relative_speed};
-use crate::options::SortOrder;
-use crate::output::format::format_duration_value;
+use crate::options::{SignificanceTest, SortOrder};
+use crate::output::format::{format_duration_value, format_p_value, format_percentage_precise};
 use crate::util::units::Unit;
 
 use super::Exporter;
@@ -63,6 +64,18 @@ pub trait MarkupExporter {
             } else {
                 "".into()
             };
+            let significance_str = match entry.significance {
+                Some(significance) if significance.is_significant() => {
+                    format!(" ({})", format_p_value(significance.p_value))
+                }
+                Some(significance) => {
+                    format!(
+                        " (not significant, {})",
+                        format_p_value(significance.p_value)
+                    )
+                }
+                None => "".into(),
+            };
 
             // prepare table row entries
             table.push_str(&self.table_row(&[
@@ -70,7 +83,7 @@ pub trait MarkupExporter {
                 &format!("{mean_str}{stddev_str}"),
                 &min_str,
                 &max_str,
-                &format!("{rel_str}{rel_stddev_str}"),
+                &format!("{rel_str}{rel_stddev_str}{significance_str}"),
             ]))
         }
 
@@ -80,6 +93,136 @@ pub trait MarkupExporter {
         table
     }
 
+    fn table_extended_statistics(
+        &self,
+        entries: &[BenchmarkResultWithRelativeSpeed],
+        unit: Unit,
+    ) -> String {
+        if entries
+            .iter()
+            .all(|entry| entry.result.extended_statistics.is_none())
+        {
+            return "".into();
+        }
+
+        let notation = format!("[{}]", unit.short_name());
+
+        let mut cells_alignment = vec![Alignment::Left];
+        cells_alignment.extend((0..7).map(|_| Alignment::Right));
+
+        let mut table = self.table_header(&cells_alignment);
+
+        table.push_str(&self.table_row(&[
+            "Command",
+            &format!("P5 {notation}"),
+            &format!("P25 {notation}"),
+            &format!("P75 {notation}"),
+            &format!("P95 {notation}"),
+            &format!("P99 {notation}"),
+            &format!("IQR {notation}"),
+            "CoV",
+        ]));
+
+        table.push_str(&self.table_divider(&cells_alignment));
+
+        for entry in entries {
+            let statistics = match &entry.result.extended_statistics {
+                Some(statistics) => statistics,
+                None => continue,
+            };
+
+            let cmd_str = entry
+                .result
+                .command_with_unused_parameters
+                .replace('|', "\\|");
+            let format = |value| format_duration_value(value, Some(unit)).0;
+            let cov_str = statistics
+                .coefficient_of_variation
+                .map(format_percentage_precise)
+                .unwrap_or_default();
+
+            table.push_str(&self.table_row(&[
+                &self.command(&cmd_str),
+                &format(statistics.p5),
+                &format(statistics.p25),
+                &format(statistics.p75),
+                &format(statistics.p95),
+                &format(statistics.p99),
+                &format(statistics.iqr),
+                &cov_str,
+            ]));
+        }
+
+        table.push_str(&self.table_footer(&cells_alignment));
+
+        table
+    }
+
+    fn table_baseline_comparison(
+        &self,
+        entries: &[BenchmarkResultWithRelativeSpeed],
+        unit: Unit,
+    ) -> String {
+        if entries.iter().all(|entry| entry.result.baseline.is_none()) {
+            return "".into();
+        }
+
+        let notation = format!("[{}]", unit.short_name());
+
+        let cells_alignment = [
+            Alignment::Left,
+            Alignment::Right,
+            Alignment::Right,
+            Alignment::Right,
+            Alignment::Right,
+            Alignment::Left,
+        ];
+
+        let mut table = self.table_header(&cells_alignment);
+
+        table.push_str(&self.table_row(&[
+            "Command",
+            &format!("Baseline {notation}"),
+            &format!("Current {notation}"),
+            "Change",
+            "p-value",
+            "Verdict",
+        ]));
+
+        table.push_str(&self.table_divider(&cells_alignment));
+
+        for entry in entries {
+            let comparison = match &entry.result.baseline {
+                Some(comparison) => comparison,
+                None => continue,
+            };
+
+            let cmd_str = entry
+                .result
+                .command_with_unused_parameters
+                .replace('|', "\\|");
+            let p_value_str = comparison.p_value.map(format_p_value).unwrap_or_default();
+            let verdict_str = match comparison.verdict {
+                Verdict::Regression => "regression",
+                Verdict::Improvement => "improvement",
+                Verdict::Unchanged => "unchanged",
+            };
+
+            table.push_str(&self.table_row(&[
+                &self.command(&cmd_str),
+                &format_duration_value(comparison.baseline_mean, Some(unit)).0,
+                &format_duration_value(entry.result.mean, Some(unit)).0,
+                &format!("{:+.1}%", comparison.relative_change * 100.0),
+                &p_value_str,
+                verdict_str,
+            ]));
+        }
+
+        table.push_str(&self.table_footer(&cells_alignment));
+
+        table
+    }
+
     fn table_row(&self, cells: &[&str]) -> String;
 
     fn table_divider(&self, cell_aligmnents: &[Alignment]) -> String;
@@ -95,7 +238,7 @@ pub trait MarkupExporter {
     fn command(&self, size: &str) -> String;
 }
 
-fn determine_unit_from_results(results: &[BenchmarkResult]) -> Unit {
+pub(super) fn determine_unit_from_results(results: &[BenchmarkResult]) -> Unit {
     if let Some(first_result) = results.first() {
         // Use the first BenchmarkResult entry to determine the unit for all entries.
         format_duration_value(first_result.mean, None).1
@@ -111,11 +254,23 @@ impl<T: MarkupExporter> Exporter for T {
         results: &[BenchmarkResult],
         unit: Option<Unit>,
         sort_order: SortOrder,
+        significance_test: SignificanceTest,
     ) -> Result<Vec<u8>> {
         let unit = unit.unwrap_or_else(|| determine_unit_from_results(results));
-        let entries = relative_speed::compute(results, sort_order);
+        let entries = relative_speed::compute(results, sort_order, significance_test);
+
+        let mut table = self.table_results(&entries, unit);
+
+        for extra_table in [
+            self.table_extended_statistics(&entries, unit),
+            self.table_baseline_comparison(&entries, unit),
+        ] {
+            if !extra_table.is_empty() {
+                table.push('\n');
+                table.push_str(&extra_table);
+            }
+        }
 
-        let table = self.table_results(&entries, unit);
         Ok(table.as_bytes().to_vec())
     }
 }
@@ -128,6 +283,7 @@ fn test_determine_unit_from_results_unit_given_s() {
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -135,13 +291,24 @@ fn test_determine_unit_from_results_unit_given_s() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -149,8 +316,18 @@ fn test_determine_unit_from_results_unit_given_s() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
@@ -170,6 +347,7 @@ fn test_determine_unit_from_results_unit_given_ms() {
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -177,13 +355,24 @@ fn test_determine_unit_from_results_unit_given_ms() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -191,8 +380,18 @@ fn test_determine_unit_from_results_unit_given_ms() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
@@ -212,6 +411,7 @@ fn test_determine_unit_from_results_unit_first_s() {
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -219,13 +419,24 @@ fn test_determine_unit_from_results_unit_first_s() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -233,8 +444,18 @@ fn test_determine_unit_from_results_unit_first_s() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
@@ -254,6 +475,7 @@ fn test_determine_unit_from_results_unit_first_ms() {
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -261,13 +483,24 @@ fn test_determine_unit_from_results_unit_first_ms() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -275,8 +508,18 @@ fn test_determine_unit_from_results_unit_first_ms() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
@@ -1,24 +1,38 @@
+use std::cell::RefCell;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 
 mod asciidoc;
 mod csv;
+mod events;
+mod html;
 mod json;
+mod junit;
 mod markdown;
 mod markup;
 mod orgmode;
+mod prometheus;
+mod svg;
 
 use self::asciidoc::AsciidocExporter;
-use self::csv::CsvExporter;
+use self::csv::{CsvExporter, CsvLongExporter};
+use self::html::HtmlExporter;
 use self::json::JsonExporter;
+use self::junit::JunitExporter;
 use self::markdown::MarkdownExporter;
 use self::orgmode::OrgmodeExporter;
+use self::prometheus::PrometheusExporter;
+use self::svg::SvgExporter;
+
+pub use self::events::{Event, EventLog};
+pub use self::json::read_results as read_json_results;
+pub use self::svg::ChartKind;
 
 use crate::benchmark::benchmark_result::BenchmarkResult;
-use crate::options::SortOrder;
+use crate::options::{SignificanceTest, SortOrder};
 use crate::util::units::Unit;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::ArgMatches;
 
 /// The desired form of exporter to use for a given file.
@@ -30,14 +44,29 @@ pub enum ExportType {
     /// CSV (comma separated values) format
     Csv,
 
+    /// CSV format with one row for each timing run
+    CsvLong,
+
+    /// Self-contained HTML report with charts
+    Html,
+
     /// JSON format
     Json,
 
+    /// JUnit XML report
+    Junit,
+
     /// Markdown table
     Markdown,
 
     /// Emacs org-mode tables
     Orgmode,
+
+    /// Prometheus / OpenMetrics text format
+    Prometheus,
+
+    /// SVG image with a chart of the results
+    Svg(ChartKind),
 }
 
 /// Interface for different exporters.
@@ -48,6 +77,7 @@ trait Exporter {
         results: &[BenchmarkResult],
         unit: Option<Unit>,
         sort_order: SortOrder,
+        significance_test: SignificanceTest,
     ) -> Result<Vec<u8>>;
 }
 
@@ -59,6 +89,9 @@ pub enum ExportTarget {
 struct ExporterWithTarget {
     exporter: Box<dyn Exporter>,
     target: ExportTarget,
+    /// Why the results could not be serialized for the last intermediate export (e.g. a
+    /// parametrized chart before any parametrized benchmark has finished)
+    error: RefCell<Option<String>>,
 }
 
 /// Handles the management of multiple file exporters.
@@ -66,15 +99,30 @@ struct ExporterWithTarget {
 pub struct ExportManager {
     exporters: Vec<ExporterWithTarget>,
     time_unit: Option<Unit>,
+    significance_test: SignificanceTest,
+    has_reference_command: bool,
+    events: EventLog,
 }
 
 impl ExportManager {
     /// Build the ExportManager that will export the results specified
     /// in the given ArgMatches
-    pub fn from_cli_arguments(matches: &ArgMatches, time_unit: Option<Unit>) -> Result<Self> {
+    pub fn from_cli_arguments(
+        matches: &ArgMatches,
+        time_unit: Option<Unit>,
+        significance_test: SignificanceTest,
+        has_reference_command: bool,
+    ) -> Result<Self> {
         let mut export_manager = Self {
             exporters: vec![],
             time_unit,
+            significance_test,
+            has_reference_command,
+            events: matches
+                .get_one::<String>("export-events")
+                .map(|filename| EventLog::new(filename))
+                .transpose()?
+                .unwrap_or_default(),
         };
         {
             let mut add_exporter = |flag, exporttype| -> Result<()> {
@@ -85,9 +133,44 @@ impl ExportManager {
             };
             add_exporter("export-asciidoc", ExportType::Asciidoc)?;
             add_exporter("export-json", ExportType::Json)?;
+            add_exporter("export-junit", ExportType::Junit)?;
             add_exporter("export-csv", ExportType::Csv)?;
+            add_exporter("export-csv-long", ExportType::CsvLong)?;
+            add_exporter("export-html", ExportType::Html)?;
             add_exporter("export-markdown", ExportType::Markdown)?;
             add_exporter("export-orgmode", ExportType::Orgmode)?;
+            add_exporter("export-prometheus", ExportType::Prometheus)?;
+        }
+        for value in matches
+            .get_many::<String>("export-svg")
+            .into_iter()
+            .flatten()
+        {
+            let (kind, filename) = value.split_once('=').ok_or_else(|| {
+                anyhow!("Expected an argument of the form '<KIND>=<FILE>' for --export-svg, got '{value}'")
+            })?;
+            let kind = ChartKind::from_name(kind).ok_or_else(|| {
+                anyhow!(
+                    "Unknown chart type '{kind}' for --export-svg. Possible values are \
+                     'histogram', 'whisker', 'progression' and 'parametrized'"
+                )
+            })?;
+            let parameter_options = [
+                "parameter-scan",
+                "parameter-scan-geometric",
+                "parameter-list",
+                "parameter-list-file",
+                "parameter-list-cmd",
+            ];
+            if kind == ChartKind::Parametrized
+                && !parameter_options.iter().any(|id| matches.contains_id(id))
+            {
+                bail!(
+                    "The 'parametrized' chart of --export-svg requires --parameter-scan, \
+                     --parameter-scan-geometric or --parameter-list"
+                );
+            }
+            export_manager.add_exporter(ExportType::Svg(kind), filename)?;
         }
         Ok(export_manager)
     }
@@ -97,9 +180,14 @@ impl ExportManager {
         let exporter: Box<dyn Exporter> = match export_type {
             ExportType::Asciidoc => Box::<AsciidocExporter>::default(),
             ExportType::Csv => Box::<CsvExporter>::default(),
-            ExportType::Json => Box::<JsonExporter>::default(),
+            ExportType::CsvLong => Box::<CsvLongExporter>::default(),
+            ExportType::Html => Box::<HtmlExporter>::default(),
+            ExportType::Json => Box::new(JsonExporter::new(self.has_reference_command)),
+            ExportType::Junit => Box::<JunitExporter>::default(),
             ExportType::Markdown => Box::<MarkdownExporter>::default(),
             ExportType::Orgmode => Box::<OrgmodeExporter>::default(),
+            ExportType::Prometheus => Box::<PrometheusExporter>::default(),
+            ExportType::Svg(kind) => Box::new(SvgExporter::new(kind)),
         };
 
         self.exporters.push(ExporterWithTarget {
@@ -111,30 +199,54 @@ impl ExportManager {
                     .with_context(|| format!("Could not create export file '{filename}'"))?;
                 ExportTarget::File(filename.to_string())
             },
+            error: RefCell::new(None),
         });
 
         Ok(())
     }
 
+    /// The log for the events during the benchmarks (see `--export-events`)
+    pub fn events(&self) -> &EventLog {
+        &self.events
+    }
+
     /// Write the given results to all Exporters. The 'intermediate' flag specifies
     /// whether this is being called while still performing benchmarks, or if this
     /// is the final call after all benchmarks have been finished. In the former case,
     /// results are written to all file targets (to always have them up to date, even
     /// if a benchmark fails). In the latter case, we only print to stdout targets (in
     /// order not to clutter the output of hyperfine with intermediate results).
+    ///
+    /// If the results can not be serialized for a file target, the intermediate export
+    /// skips that file, such that the remaining benchmarks are still performed. The error
+    /// is only reported by the final call, if the last intermediate export failed as well.
     pub fn write_results(
         &self,
         results: &[BenchmarkResult],
         sort_order: SortOrder,
         intermediate: bool,
     ) -> Result<()> {
+        let mut errors = vec![];
         for e in &self.exporters {
-            let content = || e.exporter.serialize(results, self.time_unit, sort_order);
+            let content = || {
+                e.exporter
+                    .serialize(results, self.time_unit, sort_order, self.significance_test)
+            };
 
             match e.target {
                 ExportTarget::File(ref filename) => {
                     if intermediate {
-                        write_to_file(filename, &content()?)?
+                        match content() {
+                            Ok(content) => {
+                                *e.error.borrow_mut() = None;
+                                write_to_file(filename, &content)?
+                            }
+                            Err(error) => *e.error.borrow_mut() = Some(error.to_string()),
+                        }
+                    } else if let Some(error) = e.error.borrow().as_ref() {
+                        errors.push(format!(
+                            "Could not export the results to '{filename}': {error}"
+                        ));
                     }
                 }
                 ExportTarget::Stdout => {
@@ -145,13 +257,19 @@ impl ExportManager {
                 }
             }
         }
+        if !errors.is_empty() {
+            bail!("{}", errors.join("\n"));
+        }
         Ok(())
     }
 }
 
 /// Write the given content to a file with the specified name
 fn write_to_file(filename: &str, content: &[u8]) -> Result<()> {
-    let mut file = OpenOptions::new().write(true).open(filename)?;
+    let mut file = OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(filename)?;
     file.write_all(content)
         .with_context(|| format!("Failed to export results to '{filename}'"))
 }
@@ -23,7 +23,7 @@ impl MarkupExporter for OrgmodeExporter {
 }
 
 #[cfg(test)]
-use crate::options::SortOrder;
+use crate::options::{SignificanceTest, SortOrder};
 
 /// Check Emacs org-mode data row formatting
 #[test]
@@ -80,6 +80,7 @@ fn test_orgmode_format_ms() {
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -87,13 +88,24 @@ fn test_orgmode_format_ms() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -101,22 +113,32 @@ fn test_orgmode_format_ms() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&results, None, SortOrder::Command)
+            .serialize(&results, None, SortOrder::Command, SignificanceTest::Welch)
             .unwrap(),
     )
     .unwrap();
     let expect = format!(
         "{}\
 | =sleep 0.1=  |  105.7 ± 1.6 |  102.3 |  108.0 |  1.00 |
-| =sleep 2=  |  2005.0 ± 2.0 |  2002.0 |  2008.0 |  18.97 ± 0.29 |
+| =sleep 2=  |  2005.0 ± 2.0 |  2002.0 |  2008.0 |  18.97 ± 0.29 (p < 0.001) |
 ",
         cfg_test_table_header("ms".to_string())
     );
@@ -139,6 +161,7 @@ fn test_orgmode_format_s() {
         BenchmarkResult {
             command: String::from("sleep 2"),
             command_with_unused_parameters: String::from("sleep 2"),
+            command_line: None,
             mean: 2.0050,
             stddev: Some(0.0020),
             median: 2.0050,
@@ -146,13 +169,24 @@ fn test_orgmode_format_s() {
             system: 0.0012,
             min: 2.0020,
             max: 2.0080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![2.0, 2.0, 2.0]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
             command_with_unused_parameters: String::from("sleep 0.1"),
+            command_line: None,
             mean: 0.1057,
             stddev: Some(0.0016),
             median: 0.1057,
@@ -160,21 +194,36 @@ fn test_orgmode_format_s() {
             system: 0.0011,
             min: 0.1023,
             max: 0.1080,
+            extended_statistics: None,
+            confidence_intervals: None,
+            precision: None,
             times: Some(vec![0.1, 0.1, 0.1]),
+            times_user: None,
+            times_system: None,
+            timestamps: None,
+            resource_usage: None,
             exit_codes: vec![Some(0), Some(0), Some(0)],
+            timed_out: vec![],
+            incomplete: false,
+            baseline: None,
             parameters: BTreeMap::new(),
         },
     ];
 
     let actual = String::from_utf8(
         exporter
-            .serialize(&results, Some(Unit::Second), SortOrder::Command)
+            .serialize(
+                &results,
+                Some(Unit::Second),
+                SortOrder::Command,
+                SignificanceTest::Welch,
+            )
             .unwrap(),
     )
     .unwrap();
     let expect = format!(
         "{}\
-| =sleep 2=  |  2.005 ± 0.002 |  2.002 |  2.008 |  18.97 ± 0.29 |
+| =sleep 2=  |  2.005 ± 0.002 |  2.002 |  2.008 |  18.97 ± 0.29 (p < 0.001) |
 | =sleep 0.1=  |  0.106 ± 0.002 |  0.102 |  0.108 |  1.00 |
 ",
         cfg_test_table_header("s".to_string())
@@ -0,0 +1,187 @@
+use std::fmt::Write;
+
+use super::Exporter;
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::options::{SignificanceTest, SortOrder};
+use crate::util::units::{Second, Unit};
+
+use anyhow::Result;
+
+/// Extracts a statistic from a benchmark result, if available
+type Statistic = fn(&BenchmarkResult) -> Option<Second>;
+
+/// Gauges that are exported for each benchmark, with their description
+const METRICS: [(&str, &str, Statistic); 7] = [
+    ("mean", "Mean wall clock time", |r| Some(r.mean)),
+    ("median", "Median wall clock time", |r| Some(r.median)),
+    ("stddev", "Standard deviation of the wall clock time", |r| {
+        r.stddev
+    }),
+    ("min", "Minimum wall clock time", |r| Some(r.min)),
+    ("max", "Maximum wall clock time", |r| Some(r.max)),
+    ("user", "Mean time spent in user mode", |r| Some(r.user)),
+    ("system", "Mean time spent in kernel mode", |r| {
+        Some(r.system)
+    }),
+];
+
+/// Prometheus / OpenMetrics text format, e.g. for the textfile collector of the
+/// node_exporter. Times are always in seconds.
+#[derive(Default)]
+pub struct PrometheusExporter {}
+
+impl Exporter for PrometheusExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        _unit: Option<Unit>,
+        _sort_order: SortOrder,
+        _significance_test: SignificanceTest,
+    ) -> Result<Vec<u8>> {
+        let mut text = String::new();
+
+        for (name, help, value) in METRICS {
+            let _ = writeln!(text, "# HELP hyperfine_{name}_seconds {help}.");
+            let _ = writeln!(text, "# TYPE hyperfine_{name}_seconds gauge");
+            for result in results {
+                if let Some(value) = value(result) {
+                    let _ = writeln!(
+                        text,
+                        "hyperfine_{name}_seconds{{{}}} {value}",
+                        labels(result)
+                    );
+                }
+            }
+        }
+        text.push_str("# EOF\n");
+
+        Ok(text.into_bytes())
+    }
+}
+
+/// Label set with the command and all of its parameters. Parameter names that end up the
+/// same after sanitizing (like `num-threads` and `num_threads`) get a numeric suffix.
+fn labels(result: &BenchmarkResult) -> String {
+    let mut names = vec!["command".to_string()];
+    let mut labels = vec![format!(
+        "command=\"{}\"",
+        escape_label_value(&result.command)
+    )];
+    for (name, value) in &result.parameters {
+        let mut name = label_name(name);
+        if name == "command" {
+            name = "parameter_command".into();
+        }
+        if names.contains(&name) {
+            name = (2..)
+                .map(|n| format!("{name}_{n}"))
+                .find(|candidate| !names.contains(candidate))
+                .unwrap();
+        }
+        labels.push(format!("{name}=\"{}\"", escape_label_value(value)));
+        names.push(name);
+    }
+    labels.join(",")
+}
+
+/// Replace all characters that are not allowed in label names by underscores
+fn label_name(name: &str) -> String {
+    let mut label: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !label.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        label.insert(0, '_');
+    }
+    label
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[test]
+fn test_label_name() {
+    assert_eq!(label_name("threads"), "threads");
+    assert_eq!(label_name("num-threads"), "num_threads");
+    assert_eq!(label_name("2x"), "_2x");
+    assert_eq!(label_name(""), "_");
+}
+
+#[test]
+fn test_labels_are_unique() {
+    use std::collections::BTreeMap;
+
+    let result = BenchmarkResult {
+        command: "echo".into(),
+        parameters: BTreeMap::from([
+            ("num-threads".to_string(), "1".to_string()),
+            ("num_threads".to_string(), "2".to_string()),
+            ("command".to_string(), "3".to_string()),
+            ("parameter_command".to_string(), "4".to_string()),
+            ("num_threads_2".to_string(), "5".to_string()),
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(
+        labels(&result),
+        "command=\"echo\",parameter_command=\"3\",num_threads=\"1\",num_threads_2=\"2\",\
+         num_threads_2_2=\"5\",parameter_command_2=\"4\""
+    );
+}
+
+#[test]
+fn test_prometheus_export() {
+    use std::collections::BTreeMap;
+
+    let results = vec![
+        BenchmarkResult {
+            command: "sleep 0.1".into(),
+            mean: 0.1,
+            median: 0.1,
+            min: 0.1,
+            max: 0.1,
+            user: 0.001,
+            system: 0.002,
+            parameters: BTreeMap::from([
+                ("t".to_string(), "0.1".to_string()),
+                ("command".to_string(), "sleep".to_string()),
+            ]),
+            ..Default::default()
+        },
+        BenchmarkResult {
+            command: "echo \"a\\b\"".into(),
+            mean: 0.2,
+            stddev: Some(0.01),
+            ..Default::default()
+        },
+    ];
+
+    let text = String::from_utf8(
+        PrometheusExporter::default()
+            .serialize(&results, None, SortOrder::Command, SignificanceTest::Welch)
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(text.contains(
+        "# HELP hyperfine_mean_seconds Mean wall clock time.\n\
+         # TYPE hyperfine_mean_seconds gauge\n\
+         hyperfine_mean_seconds{command=\"sleep 0.1\",parameter_command=\"sleep\",t=\"0.1\"} 0.1\n\
+         hyperfine_mean_seconds{command=\"echo \\\"a\\\\b\\\"\"} 0.2\n"
+    ));
+    assert!(text.contains("hyperfine_user_seconds{command=\"sleep 0.1\",parameter_command=\"sleep\",t=\"0.1\"} 0.001\n"));
+
+    // Only the second benchmark has a standard deviation
+    assert_eq!(text.matches("hyperfine_stddev_seconds{").count(), 1);
+    assert!(text.ends_with("# EOF\n"));
+}
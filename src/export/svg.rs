@@ -0,0 +1,1016 @@
+//! Charts of the benchmark results, rendered as standalone SVG images. These replace the
+//! matplotlib scripts in `scripts/` for the most common use cases.
+
+use std::fmt::Write;
+
+use super::markup::determine_unit_from_results;
+use super::Exporter;
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::options::{SignificanceTest, SortOrder};
+use crate::parameter::suffix::expand_size_suffix;
+use crate::util::statistics::percentile;
+use crate::util::units::{Scalar, Unit};
+
+use anyhow::{bail, Result};
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 360.0;
+
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 24.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 56.0;
+
+/// Approximate width of a character of the axis labels, used to shorten long commands
+const CHAR_WIDTH: f64 = 7.0;
+
+/// Colors for the different commands (matplotlib's default color cycle)
+const COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+/// Color of the command with the given index
+pub fn color(index: usize) -> &'static str {
+    COLORS[index % COLORS.len()]
+}
+
+/// Escape a string for use in XML text and attributes
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Shorten a label to the given number of characters
+fn truncate(label: &str, max_chars: usize) -> String {
+    if label.chars().count() <= max_chars {
+        label.to_string()
+    } else {
+        let shortened: String = label.chars().take(max_chars.saturating_sub(1)).collect();
+        format!("{shortened}…")
+    }
+}
+
+/// Factor to convert seconds to the given unit
+fn unit_factor(unit: Unit) -> Scalar {
+    match unit {
+        Unit::Second => 1.0,
+        Unit::MilliSecond => 1e3,
+        Unit::MicroSecond => 1e6,
+    }
+}
+
+/// Format a value that has already been converted to the given unit, with the same
+/// precision as `Unit::format`
+fn format_value(value: Scalar, unit: Unit) -> String {
+    match unit {
+        Unit::Second => format!("{value:.3} {}", unit.short_name()),
+        _ => format!("{value:.1} {}", unit.short_name()),
+    }
+}
+
+/// Round numbers between `min` and `max` that are suitable as axis ticks. The distance
+/// between the ticks is at least `min_step`.
+fn nice_ticks(min: Scalar, max: Scalar, target_count: usize, min_step: Scalar) -> Vec<Scalar> {
+    let range = max - min;
+    if range <= 0.0 || !range.is_finite() {
+        return vec![min];
+    }
+
+    let raw_step = range / target_count as Scalar;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let normalized = raw_step / magnitude;
+    let step = magnitude
+        * if normalized < 1.5 {
+            1.0
+        } else if normalized < 3.0 {
+            2.0
+        } else if normalized < 7.0 {
+            5.0
+        } else {
+            10.0
+        };
+    let step = step.max(min_step);
+
+    let first = (min / step).ceil() as i64;
+    let last = (max / step + 1e-9).floor() as i64;
+    (first..=last).map(|i| i as Scalar * step).collect()
+}
+
+/// Number of decimals that are needed to distinguish the given ticks
+fn tick_decimals(ticks: &[Scalar]) -> usize {
+    match ticks {
+        // The tolerance prevents rounding errors like log10(0.09999…) from adding a digit
+        [first, second, ..] => (-(second - first).log10() - 1e-9).ceil().max(0.0) as usize,
+        _ => 0,
+    }
+}
+
+/// Linear mapping from data coordinates to pixels
+#[derive(Clone, Copy)]
+struct Scale {
+    domain: (Scalar, Scalar),
+    range: (f64, f64),
+}
+
+impl Scale {
+    fn new(domain: (Scalar, Scalar), range: (f64, f64)) -> Self {
+        // Avoid a division by zero for constant data
+        let domain = if domain.1 > domain.0 {
+            domain
+        } else {
+            let padding = if domain.0 != 0.0 {
+                domain.0.abs() * 0.05
+            } else {
+                1.0
+            };
+            (domain.0 - padding, domain.1 + padding)
+        };
+        Scale { domain, range }
+    }
+
+    fn map(&self, value: Scalar) -> f64 {
+        let t = (value - self.domain.0) / (self.domain.1 - self.domain.0);
+        self.range.0 + t * (self.range.1 - self.range.0)
+    }
+}
+
+/// An SVG image with a single plot area
+struct Chart {
+    svg: String,
+}
+
+impl Chart {
+    fn new(title: &str) -> Self {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" \
+             viewBox=\"0 0 {WIDTH} {HEIGHT}\" font-family=\"sans-serif\" font-size=\"12\">"
+        );
+        let _ = writeln!(
+            svg,
+            "<rect width=\"{WIDTH}\" height=\"{HEIGHT}\" fill=\"white\"/>"
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"24\" text-anchor=\"middle\" font-size=\"14\">{}</text>",
+            WIDTH / 2.0,
+            escape(&truncate(title, ((WIDTH - 32.0) / CHAR_WIDTH) as usize))
+        );
+        Chart { svg }
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), color: &str) {
+        let _ = writeln!(
+            self.svg,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{color}\"/>",
+            from.0, from.1, to.0, to.1
+        );
+    }
+
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str, tooltip: &str) {
+        let _ = writeln!(
+            self.svg,
+            "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{fill}\" \
+             fill-opacity=\"0.75\" stroke=\"{fill}\"><title>{}</title></rect>",
+            width.max(0.0),
+            height.max(0.0),
+            escape(tooltip)
+        );
+    }
+
+    fn circle(&mut self, center: (f64, f64), radius: f64, color: &str, filled: bool) {
+        let fill = if filled { color } else { "none" };
+        let _ = writeln!(
+            self.svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{radius}\" fill=\"{fill}\" stroke=\"{color}\"/>",
+            center.0, center.1
+        );
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], color: &str) {
+        let points: Vec<String> = points
+            .iter()
+            .map(|(x, y)| format!("{x:.1},{y:.1}"))
+            .collect();
+        let _ = writeln!(
+            self.svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>",
+            points.join(" ")
+        );
+    }
+
+    fn text(&mut self, position: (f64, f64), anchor: &str, content: &str) {
+        let _ = writeln!(
+            self.svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"{anchor}\">{}</text>",
+            position.0,
+            position.1,
+            escape(content)
+        );
+    }
+
+    /// Draw a numeric y axis with horizontal grid lines
+    fn y_axis(&mut self, scale: &Scale, label: &str, min_step: Scalar) {
+        let ticks = nice_ticks(scale.domain.0, scale.domain.1, 6, min_step);
+        let decimals = tick_decimals(&ticks);
+        for tick in ticks {
+            let y = scale.map(tick);
+            self.line((MARGIN_LEFT, y), (WIDTH - MARGIN_RIGHT, y), "#e0e0e0");
+            self.text(
+                (MARGIN_LEFT - 6.0, y + 4.0),
+                "end",
+                &format!("{tick:.decimals$}"),
+            );
+        }
+
+        let center = (HEIGHT - MARGIN_BOTTOM + MARGIN_TOP) / 2.0;
+        let _ = writeln!(
+            self.svg,
+            "<text transform=\"translate(16 {center:.1}) rotate(-90)\" text-anchor=\"middle\">{}</text>",
+            escape(label)
+        );
+    }
+
+    /// Draw a numeric x axis
+    fn x_axis(&mut self, scale: &Scale, label: &str) {
+        let ticks = nice_ticks(scale.domain.0, scale.domain.1, 8, 0.0);
+        let decimals = tick_decimals(&ticks);
+        for tick in ticks {
+            let x = scale.map(tick);
+            let y = HEIGHT - MARGIN_BOTTOM;
+            self.line((x, y), (x, y + 4.0), "black");
+            self.text((x, y + 18.0), "middle", &format!("{tick:.decimals$}"));
+        }
+        self.x_label(label);
+    }
+
+    fn x_label(&mut self, label: &str) {
+        self.text(
+            ((WIDTH - MARGIN_RIGHT + MARGIN_LEFT) / 2.0, HEIGHT - 12.0),
+            "middle",
+            label,
+        );
+    }
+
+    /// Draw a legend in the top right corner of the plot area if there is more than one
+    /// series
+    fn legend(&mut self, entries: &[(&str, &str)]) {
+        if entries.len() < 2 {
+            return;
+        }
+
+        let max_chars = ((WIDTH - MARGIN_LEFT - MARGIN_RIGHT) / 2.0 / CHAR_WIDTH) as usize;
+        let labels: Vec<String> = entries
+            .iter()
+            .map(|(label, _)| truncate(label, max_chars))
+            .collect();
+        let longest = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+        let width = longest as f64 * CHAR_WIDTH + 32.0;
+        let height = entries.len() as f64 * 18.0 + 8.0;
+        let left = WIDTH - MARGIN_RIGHT - width - 8.0;
+        let top = MARGIN_TOP + 8.0;
+
+        let _ = writeln!(
+            self.svg,
+            "<rect x=\"{left:.1}\" y=\"{top:.1}\" width=\"{width:.1}\" height=\"{height:.1}\" \
+             fill=\"white\" fill-opacity=\"0.85\" stroke=\"#c0c0c0\"/>"
+        );
+        for (index, ((_, color), label)) in entries.iter().zip(&labels).enumerate() {
+            let y = top + 8.0 + index as f64 * 18.0;
+            let _ = writeln!(
+                self.svg,
+                "<rect x=\"{:.1}\" y=\"{y:.1}\" width=\"12\" height=\"12\" fill=\"{color}\"/>",
+                left + 8.0
+            );
+            self.text((left + 26.0, y + 10.0), "start", label);
+        }
+    }
+
+    /// Draw the frame around the plot area and return the finished image
+    fn finish(mut self) -> String {
+        let _ = writeln!(
+            self.svg,
+            "<rect x=\"{MARGIN_LEFT}\" y=\"{MARGIN_TOP}\" width=\"{:.1}\" height=\"{:.1}\" \
+             fill=\"none\" stroke=\"black\"/>",
+            WIDTH - MARGIN_LEFT - MARGIN_RIGHT,
+            HEIGHT - MARGIN_TOP - MARGIN_BOTTOM
+        );
+        self.svg.push_str("</svg>\n");
+        self.svg
+    }
+}
+
+fn x_range() -> (f64, f64) {
+    (MARGIN_LEFT, WIDTH - MARGIN_RIGHT)
+}
+
+fn y_range() -> (f64, f64) {
+    (HEIGHT - MARGIN_BOTTOM, MARGIN_TOP)
+}
+
+/// Run times of a benchmark in the given unit, if available
+fn times_in_unit(result: &BenchmarkResult, unit: Unit) -> Option<Vec<Scalar>> {
+    let times = result.times.as_ref().filter(|times| !times.is_empty())?;
+    let factor = unit_factor(unit);
+    Some(times.iter().map(|t| t * factor).collect())
+}
+
+/// Run times of a single command, as shown in a chart
+struct Series<'a> {
+    label: &'a str,
+    color: &'static str,
+    times: Vec<Scalar>,
+}
+
+impl<'a> Series<'a> {
+    fn new(result: &'a BenchmarkResult, unit: Unit, color_index: usize) -> Option<Self> {
+        Some(Series {
+            label: &result.command_with_unused_parameters,
+            color: color(color_index),
+            times: times_in_unit(result, unit)?,
+        })
+    }
+
+    /// The run times of all results, with one color per command
+    fn all(results: &'a [BenchmarkResult], unit: Unit) -> Vec<Self> {
+        results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| Series::new(result, unit, index))
+            .collect()
+    }
+
+    fn legend(series: &[Self]) -> Vec<(&str, &str)> {
+        series.iter().map(|s| (s.label, s.color)).collect()
+    }
+}
+
+/// Title of a chart that shows the given series
+fn title_for(series: &[Series], title: &str) -> String {
+    match series {
+        [single] => single.label.to_string(),
+        _ => title.to_string(),
+    }
+}
+
+/// Number of bins of the histogram for the given number of runs
+fn histogram_bins(num_runs: usize) -> usize {
+    ((num_runs as f64).sqrt().ceil() as usize).clamp(1, 50)
+}
+
+/// Histogram of the run times of all benchmarks. The bars of the different commands are
+/// shown next to each other within each bin.
+pub fn histogram(results: &[BenchmarkResult], unit: Unit) -> Option<String> {
+    let series = Series::all(results, unit);
+    draw_histogram(&series, unit, &title_for(&series, "Run time histogram"))
+}
+
+/// Histogram of the run times of a single benchmark
+pub fn command_histogram(
+    result: &BenchmarkResult,
+    unit: Unit,
+    color_index: usize,
+) -> Option<String> {
+    let series = Series::new(result, unit, color_index)?;
+    draw_histogram(&[series], unit, &result.command_with_unused_parameters)
+}
+
+fn draw_histogram(series: &[Series], unit: Unit, title: &str) -> Option<String> {
+    if series.is_empty() {
+        return None;
+    }
+
+    let all_times = || series.iter().flat_map(|s| s.times.iter().cloned());
+    let min = all_times().fold(f64::INFINITY, f64::min);
+    let max = all_times().fold(f64::NEG_INFINITY, f64::max);
+    let x = Scale::new((min, max), x_range());
+    let (min, max) = x.domain;
+
+    let num_runs = series.iter().map(|s| s.times.len()).max().unwrap_or(0);
+    let num_bins = histogram_bins(num_runs);
+    let bin_width = (max - min) / num_bins as Scalar;
+    let counts: Vec<Vec<usize>> = series
+        .iter()
+        .map(|s| {
+            let mut counts = vec![0usize; num_bins];
+            for t in &s.times {
+                let bin = (((t - min) / bin_width) as usize).min(num_bins - 1);
+                counts[bin] += 1;
+            }
+            counts
+        })
+        .collect();
+
+    let max_count = counts.iter().flatten().cloned().max().unwrap_or(0);
+    let y = Scale::new((0.0, (max_count as f64 * 1.1).max(1.0)), y_range());
+
+    let mut chart = Chart::new(title);
+    chart.y_axis(&y, "Count", 1.0);
+    for bin in 0..num_bins {
+        let lower = min + bin as Scalar * bin_width;
+        let upper = lower + bin_width;
+        let bar_width = (x.map(upper) - x.map(lower)) / series.len() as f64;
+
+        for (index, (s, counts)) in series.iter().zip(&counts).enumerate() {
+            let count = counts[bin];
+            let left = x.map(lower) + index as f64 * bar_width;
+            let top = y.map(count as f64);
+            let label = if series.len() > 1 {
+                format!("{}\n", s.label)
+            } else {
+                String::new()
+            };
+            chart.rect(
+                left,
+                top,
+                bar_width,
+                y.map(0.0) - top,
+                s.color,
+                &format!(
+                    "{label}{} … {}: {count}",
+                    format_value(lower, unit),
+                    format_value(upper, unit)
+                ),
+            );
+        }
+    }
+    chart.x_axis(&x, &format!("Time [{}]", unit.short_name()));
+    chart.legend(&Series::legend(series));
+
+    Some(chart.finish())
+}
+
+/// Moving average over a window of the given width, with the edge values repeated at both ends
+fn moving_average(values: &[Scalar], width: usize) -> Vec<Scalar> {
+    let width = width.max(1);
+    let before = width / 2;
+    let padded: Vec<Scalar> = std::iter::repeat(values[0])
+        .take(before)
+        .chain(values.iter().cloned())
+        .chain(std::iter::repeat(values[values.len() - 1]).take(width - 1 - before))
+        .collect();
+
+    padded
+        .windows(width)
+        .map(|window| window.iter().sum::<Scalar>() / width as Scalar)
+        .collect()
+}
+
+/// Run times of all benchmarks in the order of execution, together with their moving
+/// averages. This helps to detect caching effects, thermal throttling or background
+/// interference.
+pub fn progression(results: &[BenchmarkResult], unit: Unit) -> Option<String> {
+    let series = Series::all(results, unit);
+    draw_progression(&series, unit, &title_for(&series, "Run time progression"))
+}
+
+/// Run times of a single benchmark in the order of execution
+pub fn command_progression(
+    result: &BenchmarkResult,
+    unit: Unit,
+    color_index: usize,
+) -> Option<String> {
+    let series = Series::new(result, unit, color_index)?;
+    draw_progression(&[series], unit, &result.command_with_unused_parameters)
+}
+
+fn draw_progression(series: &[Series], unit: Unit, title: &str) -> Option<String> {
+    if series.is_empty() {
+        return None;
+    }
+
+    let num_runs = series.iter().map(|s| s.times.len()).max().unwrap_or(0);
+    let max = series
+        .iter()
+        .flat_map(|s| s.times.iter().cloned())
+        .fold(0.0, f64::max);
+    let x = Scale::new((0.0, (num_runs + 1) as Scalar), x_range());
+    let y = Scale::new((0.0, max * 1.1), y_range());
+
+    let mut chart = Chart::new(title);
+    chart.y_axis(&y, &format!("Time [{}]", unit.short_name()), 0.0);
+
+    for s in series {
+        for (run, t) in s.times.iter().enumerate() {
+            chart.circle((x.map((run + 1) as Scalar), y.map(*t)), 2.0, s.color, true);
+        }
+    }
+
+    // With a single command, the moving average is easier to see in black
+    for s in series {
+        let average = moving_average(&s.times, s.times.len() / 5);
+        let points: Vec<(f64, f64)> = average
+            .iter()
+            .enumerate()
+            .map(|(run, t)| (x.map((run + 1) as Scalar), y.map(*t)))
+            .collect();
+        chart.polyline(&points, if series.len() > 1 { s.color } else { "black" });
+    }
+
+    chart.x_axis(&x, "Run");
+    chart.legend(&Series::legend(series));
+
+    Some(chart.finish())
+}
+
+/// Box and whisker plot of the run times of all benchmarks. The box extends from the lower
+/// to the upper quartile, with a line at the median. The whiskers extend to the most extreme
+/// run times within 1.5 IQR of the box, and the remaining run times are shown as outliers.
+pub fn whisker(results: &[BenchmarkResult], unit: Unit) -> Option<String> {
+    let entries: Vec<(usize, &BenchmarkResult, Vec<Scalar>)> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(index, result)| {
+            let mut times = times_in_unit(result, unit)?;
+            times.sort_by(|a, b| a.total_cmp(b));
+            Some((index, result, times))
+        })
+        .collect();
+    if entries.is_empty() {
+        return None;
+    }
+
+    let max = entries
+        .iter()
+        .map(|(_, _, times)| times[times.len() - 1])
+        .fold(0.0, f64::max);
+    let y = Scale::new((0.0, max * 1.1), y_range());
+
+    let slot_width = (WIDTH - MARGIN_LEFT - MARGIN_RIGHT) / entries.len() as f64;
+    let box_width = (slot_width * 0.5).min(80.0);
+    let max_label_chars = (slot_width / CHAR_WIDTH) as usize;
+
+    let mut chart = Chart::new("Run time distribution");
+    chart.y_axis(&y, &format!("Time [{}]", unit.short_name()), 0.0);
+
+    for (slot, (index, result, times)) in entries.iter().enumerate() {
+        let color = color(*index);
+        let center = MARGIN_LEFT + (slot as f64 + 0.5) * slot_width;
+        let (left, right) = (center - box_width / 2.0, center + box_width / 2.0);
+
+        let q1 = percentile(times, 25.0);
+        let median = percentile(times, 50.0);
+        let q3 = percentile(times, 75.0);
+        let iqr = q3 - q1;
+        let lower_whisker = times
+            .iter()
+            .cloned()
+            .find(|&t| t >= q1 - 1.5 * iqr)
+            .unwrap_or(q1);
+        let upper_whisker = times
+            .iter()
+            .rev()
+            .cloned()
+            .find(|&t| t <= q3 + 1.5 * iqr)
+            .unwrap_or(q3);
+
+        chart.line((center, y.map(lower_whisker)), (center, y.map(q1)), "black");
+        chart.line((center, y.map(q3)), (center, y.map(upper_whisker)), "black");
+        for whisker in [lower_whisker, upper_whisker] {
+            let y = y.map(whisker);
+            chart.line(
+                (center - box_width / 4.0, y),
+                (center + box_width / 4.0, y),
+                "black",
+            );
+        }
+
+        chart.rect(
+            left,
+            y.map(q3),
+            box_width,
+            y.map(q1) - y.map(q3),
+            color,
+            &format!(
+                "{}\nmedian: {}, quartiles: {} … {}",
+                result.command_with_unused_parameters,
+                format_value(median, unit),
+                format_value(q1, unit),
+                format_value(q3, unit)
+            ),
+        );
+        chart.line((left, y.map(median)), (right, y.map(median)), "black");
+
+        for &t in times
+            .iter()
+            .filter(|&&t| t < lower_whisker || t > upper_whisker)
+        {
+            chart.circle((center, y.map(t)), 3.0, "black", false);
+        }
+
+        chart.text(
+            (center, HEIGHT - MARGIN_BOTTOM + 18.0),
+            "middle",
+            &truncate(&result.command_with_unused_parameters, max_label_chars),
+        );
+    }
+
+    Some(chart.finish())
+}
+
+/// Replace the occurrences of a parameter value in a command by the `{name}` placeholder.
+/// Occurrences that are part of a longer number or word are left untouched.
+fn with_placeholder(command: &str, name: &str, value: &str) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '.' || c == '_';
+
+    let mut label = String::new();
+    let mut rest = command;
+    while let Some(position) = rest.find(value) {
+        let (before, after) = (&rest[..position], &rest[position + value.len()..]);
+        let standalone = !before.ends_with(is_word_char) && !after.starts_with(is_word_char);
+
+        label.push_str(before);
+        if standalone {
+            let _ = write!(label, "{{{name}}}");
+        } else {
+            label.push_str(value);
+        }
+        rest = after;
+    }
+    label.push_str(rest);
+    label
+}
+
+/// The numeric value of a parameter, which may have a size suffix like "4K"
+fn numeric_value(value: &str) -> Option<Scalar> {
+    expand_size_suffix(value.trim()).parse().ok()
+}
+
+/// The parameter to show on the x axis of a parametrized chart: the first parameter with
+/// numeric values only, preferably one that takes more than one value. Results without any
+/// parameters (like the one for `--reference`) are ignored.
+fn x_parameter(results: &[BenchmarkResult]) -> Option<&str> {
+    let results: Vec<&BenchmarkResult> = results
+        .iter()
+        .filter(|result| !result.parameters.is_empty())
+        .collect();
+    let first = results.first()?;
+    let numeric: Vec<&str> = first
+        .parameters
+        .keys()
+        .map(String::as_str)
+        .filter(|name| {
+            results.iter().all(|result| {
+                result
+                    .parameters
+                    .get(*name)
+                    .is_some_and(|value| numeric_value(value).is_some())
+            })
+        })
+        .collect();
+
+    numeric
+        .iter()
+        .find(|name| {
+            results
+                .iter()
+                .any(|result| result.parameters[**name] != first.parameters[**name])
+        })
+        .or_else(|| numeric.first())
+        .copied()
+}
+
+/// Mean run time (with the standard deviation as error bars) as a function of a numeric
+/// parameter. Benchmarks for different commands (or different values of the remaining
+/// parameters) are shown as separate lines.
+pub fn parametrized(results: &[BenchmarkResult], unit: Unit) -> Option<String> {
+    let name = x_parameter(results)?;
+    let results: Vec<&BenchmarkResult> = results
+        .iter()
+        .filter(|result| result.parameters.contains_key(name))
+        .collect();
+    let x_value = |result: &&BenchmarkResult| -> Scalar {
+        numeric_value(&result.parameters[name]).unwrap_or_default()
+    };
+
+    // The n-th benchmark with a given parameter value belongs to the n-th line
+    let mut lines: Vec<(String, Vec<&BenchmarkResult>)> = vec![];
+    let mut seen: Vec<(&str, usize)> = vec![];
+    for &result in &results {
+        let value = result.parameters[name].as_str();
+        let line = match seen.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, count)) => {
+                *count += 1;
+                *count - 1
+            }
+            None => {
+                seen.push((value, 1));
+                0
+            }
+        };
+
+        if line == lines.len() {
+            let label = with_placeholder(&result.command_with_unused_parameters, name, value);
+            lines.push((label, vec![]));
+        }
+        lines[line].1.push(result);
+    }
+
+    let factor = unit_factor(unit);
+    let min_x = results.iter().map(x_value).fold(f64::INFINITY, f64::min);
+    let max_x = results
+        .iter()
+        .map(x_value)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let max_y = results
+        .iter()
+        .map(|result| (result.mean + result.stddev.unwrap_or(0.0)) * factor)
+        .fold(0.0, f64::max);
+    let x = Scale::new((min_x, max_x), x_range());
+    let y = Scale::new((0.0, max_y * 1.1), y_range());
+
+    let title = match lines.as_slice() {
+        [(label, _)] => label.clone(),
+        _ => format!("Mean run time by '{name}'"),
+    };
+    let mut chart = Chart::new(&title);
+    chart.y_axis(&y, &format!("Time [{}]", unit.short_name()), 0.0);
+
+    for (index, (_, line)) in lines.iter().enumerate() {
+        let color = color(index);
+        let mut points = vec![];
+        for result in line {
+            let center = x.map(x_value(result));
+            let mean = result.mean * factor;
+            if let Some(stddev) = result.stddev.map(|s| s * factor) {
+                let (lower, upper) = (y.map(mean - stddev), y.map(mean + stddev));
+                chart.line((center, lower), (center, upper), color);
+                chart.line((center - 4.0, lower), (center + 4.0, lower), color);
+                chart.line((center - 4.0, upper), (center + 4.0, upper), color);
+            }
+            chart.circle((center, y.map(mean)), 3.0, color, true);
+            points.push((center, y.map(mean)));
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        chart.polyline(&points, color);
+    }
+
+    chart.x_axis(&x, name);
+    let legend: Vec<(&str, &str)> = lines
+        .iter()
+        .enumerate()
+        .map(|(index, (label, _))| (label.as_str(), color(index)))
+        .collect();
+    chart.legend(&legend);
+
+    Some(chart.finish())
+}
+
+/// The charts that can be exported with `--export-svg`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartKind {
+    Histogram,
+    Whisker,
+    Progression,
+    Parametrized,
+}
+
+impl ChartKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "histogram" => Some(ChartKind::Histogram),
+            "whisker" => Some(ChartKind::Whisker),
+            "progression" => Some(ChartKind::Progression),
+            "parametrized" => Some(ChartKind::Parametrized),
+            _ => None,
+        }
+    }
+}
+
+/// Exports a single chart of all benchmark results as an SVG image
+pub struct SvgExporter {
+    kind: ChartKind,
+}
+
+impl SvgExporter {
+    pub fn new(kind: ChartKind) -> Self {
+        SvgExporter { kind }
+    }
+}
+
+impl Exporter for SvgExporter {
+    fn serialize(
+        &self,
+        results: &[BenchmarkResult],
+        unit: Option<Unit>,
+        _sort_order: SortOrder,
+        _significance_test: SignificanceTest,
+    ) -> Result<Vec<u8>> {
+        let unit = unit.unwrap_or_else(|| determine_unit_from_results(results));
+        let chart = match self.kind {
+            ChartKind::Histogram => histogram(results, unit),
+            ChartKind::Whisker => whisker(results, unit),
+            ChartKind::Progression => progression(results, unit),
+            ChartKind::Parametrized => parametrized(results, unit),
+        };
+
+        match chart {
+            Some(chart) => Ok(chart.into_bytes()),
+            None if self.kind == ChartKind::Parametrized => {
+                bail!("The parametrized chart requires a numeric benchmark parameter")
+            }
+            None => bail!("The chart requires the run times of the individual benchmark runs"),
+        }
+    }
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(
+        escape("sleep 1 && echo \"<a>\" > 'b'"),
+        "sleep 1 &amp;&amp; echo &quot;&lt;a&gt;&quot; &gt; &#39;b&#39;"
+    );
+}
+
+#[test]
+fn test_truncate() {
+    assert_eq!(truncate("sleep 1", 10), "sleep 1");
+    assert_eq!(truncate("sleep 1.234", 8), "sleep 1…");
+}
+
+#[test]
+fn test_nice_ticks() {
+    let formatted = |ticks: Vec<Scalar>| -> Vec<String> {
+        let decimals = tick_decimals(&ticks);
+        ticks.iter().map(|t| format!("{t:.decimals$}")).collect()
+    };
+
+    assert_eq!(
+        formatted(nice_ticks(0.0, 10.0, 5, 0.0)),
+        ["0", "2", "4", "6", "8", "10"]
+    );
+    assert_eq!(
+        formatted(nice_ticks(0.0, 1.1, 6, 0.0)),
+        ["0.0", "0.2", "0.4", "0.6", "0.8", "1.0"]
+    );
+    assert_eq!(
+        formatted(nice_ticks(93.0, 131.0, 6, 0.0)),
+        ["95", "100", "105", "110", "115", "120", "125", "130"]
+    );
+    assert_eq!(formatted(nice_ticks(1.0, 1.0, 6, 0.0)), ["1"]);
+    assert_eq!(formatted(nice_ticks(0.0, 2.2, 6, 1.0)), ["0", "1", "2"]);
+}
+
+#[test]
+fn test_moving_average() {
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0], 1), vec![1.0, 2.0, 3.0]);
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0], 0), vec![1.0, 2.0, 3.0]);
+    assert_eq!(
+        moving_average(&[1.0, 2.0, 3.0, 4.0], 2),
+        vec![1.0, 1.5, 2.5, 3.5]
+    );
+    assert_eq!(
+        moving_average(&[0.0, 3.0, 0.0, 0.0], 3),
+        vec![1.0, 1.0, 1.0, 0.0]
+    );
+}
+
+#[test]
+fn test_histogram() {
+    let result = BenchmarkResult {
+        command_with_unused_parameters: "sleep 0.1".into(),
+        times: Some(vec![0.1, 0.1, 0.2, 0.3]),
+        ..Default::default()
+    };
+
+    let svg = command_histogram(&result, Unit::MilliSecond, 0).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<title>").count(), histogram_bins(4));
+    assert!(svg.contains("<title>100.0 ms … 200.0 ms: 2</title>"));
+    assert!(svg.contains("<title>200.0 ms … 300.0 ms: 2</title>"));
+    assert!(svg.contains("Time [ms]"));
+
+    let without_times = BenchmarkResult::default();
+    assert!(command_histogram(&without_times, Unit::Second, 0).is_none());
+}
+
+#[test]
+fn test_whisker() {
+    let result = |command: &str, times: Vec<Scalar>| BenchmarkResult {
+        command_with_unused_parameters: command.into(),
+        times: Some(times),
+        ..Default::default()
+    };
+    let results = vec![
+        result("a", vec![1.0, 1.1, 1.2, 1.3, 5.0]),
+        result("b", vec![2.0, 2.0, 2.0]),
+    ];
+
+    let svg = whisker(&results, Unit::Second).unwrap();
+    assert!(svg.contains("<title>a\nmedian: 1.200 s, quartiles: 1.100 s … 1.300 s</title>"));
+    assert!(svg.contains("<title>b\nmedian: 2.000 s, quartiles: 2.000 s … 2.000 s</title>"));
+
+    // The run with 5 s is an outlier
+    assert_eq!(svg.matches("<circle").count(), 1);
+}
+
+#[test]
+fn test_histogram_of_multiple_commands() {
+    let result = |command: &str, times: Vec<Scalar>| BenchmarkResult {
+        command_with_unused_parameters: command.into(),
+        times: Some(times),
+        ..Default::default()
+    };
+    let results = vec![
+        result("sleep 0.1", vec![0.1, 0.1, 0.2, 0.3]),
+        result("sleep 0.2", vec![0.2, 0.3]),
+    ];
+
+    let svg = histogram(&results, Unit::MilliSecond).unwrap();
+    assert!(svg.contains("Run time histogram"));
+    assert_eq!(svg.matches("<title>").count(), 2 * histogram_bins(4));
+    assert!(svg.contains("<title>sleep 0.2\n200.0 ms … 300.0 ms: 2</title>"));
+
+    // Legend with both commands
+    assert!(svg.contains(">sleep 0.1</text>"));
+    assert!(svg.contains(">sleep 0.2</text>"));
+}
+
+#[test]
+fn test_with_placeholder() {
+    assert_eq!(with_placeholder("sleep 0.1", "t", "0.1"), "sleep {t}");
+    assert_eq!(
+        with_placeholder("cp -r 1 10 1.5 x1 1", "n", "1"),
+        "cp -r {n} 10 1.5 x1 {n}"
+    );
+}
+
+#[test]
+fn test_parametrized() {
+    use std::collections::BTreeMap;
+
+    let result = |command: &str, value: &str, mean: Scalar| BenchmarkResult {
+        command: command.into(),
+        command_with_unused_parameters: command.into(),
+        mean,
+        stddev: Some(0.01),
+        parameters: BTreeMap::from([
+            ("mode".to_string(), "fast".to_string()),
+            ("t".to_string(), value.to_string()),
+        ]),
+        ..Default::default()
+    };
+    let results = vec![
+        result("sleep 0.1", "0.1", 0.1),
+        result("sleep 0.1; true", "0.1", 0.11),
+        result("sleep 0.2", "0.2", 0.2),
+        result("sleep 0.2; true", "0.2", 0.21),
+        result("sleep 0.3", "0.3", 0.3),
+        result("sleep 0.3; true", "0.3", 0.31),
+    ];
+
+    assert_eq!(x_parameter(&results), Some("t"));
+
+    // A reference command without parameters is not part of the chart
+    let reference = BenchmarkResult {
+        command: "true".into(),
+        mean: 0.001,
+        ..Default::default()
+    };
+    let with_reference: Vec<_> = std::iter::once(reference)
+        .chain(results.iter().cloned())
+        .collect();
+    assert_eq!(x_parameter(&with_reference), Some("t"));
+    assert_eq!(
+        parametrized(&with_reference, Unit::Second),
+        parametrized(&results, Unit::Second)
+    );
+
+    let svg = parametrized(&results, Unit::Second).unwrap();
+    assert!(svg.contains("Mean run time by &#39;t&#39;"));
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert_eq!(svg.matches("<circle").count(), 6);
+    assert!(svg.contains(">sleep {t}</text>"));
+    assert!(svg.contains(">sleep {t}; true</text>"));
+
+    // Values with a size suffix are numeric
+    let mut with_suffix = results.clone();
+    for (result, size) in with_suffix
+        .iter_mut()
+        .zip(["1K", "1K", "2K", "2K", "4K", "4K"])
+    {
+        result.parameters.insert("t".into(), size.into());
+    }
+    assert_eq!(x_parameter(&with_suffix), Some("t"));
+    assert_eq!(numeric_value("4K"), Some(4096.0));
+
+    // No numeric parameter
+    let mut without_numeric = results[0].clone();
+    without_numeric.parameters.remove("t");
+    assert!(parametrized(&[without_numeric], Unit::Second).is_none());
+    assert!(parametrized(&[], Unit::Second).is_none());
+}
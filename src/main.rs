@@ -4,15 +4,22 @@
 )]
 
 use std::env;
+use std::ffi::OsString;
 
+use benchmark::baseline::Baseline;
 use benchmark::scheduler::Scheduler;
 use cli::get_cli_arguments;
 use command::Commands;
-use export::ExportManager;
+use export::{read_json_results, ExportManager};
 use options::Options;
+use util::interrupt;
 
 use anyhow::Result;
+use clap::ArgMatches;
 use colored::*;
+use options::OutputStyleOption;
+use plan::Plan;
+use suite::Suite;
 
 pub mod benchmark;
 pub mod cli;
@@ -23,6 +30,8 @@ pub mod options;
 pub mod outlier_detection;
 pub mod output;
 pub mod parameter;
+pub mod plan;
+pub mod suite;
 pub mod timer;
 pub mod util;
 
@@ -32,17 +41,96 @@ fn run() -> Result<()> {
     colored::control::set_virtual_terminal(true).unwrap();
 
     let cli_arguments = get_cli_arguments(env::args_os());
-    let options = Options::from_cli_arguments(&cli_arguments)?;
-    let commands = Commands::from_cli_arguments(&cli_arguments)?;
-    let export_manager = ExportManager::from_cli_arguments(&cli_arguments, options.time_unit)?;
+
+    match cli_arguments.get_one::<String>("suite") {
+        Some(filename) => {
+            let suite = Suite::load(filename, &cli_arguments)?;
+
+            interrupt::install_handler()?;
+
+            for group in &suite.groups {
+                // Each group is run as if its settings had been passed on the command line
+                let group_arguments = get_cli_arguments(
+                    env::args_os().chain(
+                        group
+                            .arguments(&cli_arguments)
+                            .into_iter()
+                            .map(OsString::from),
+                    ),
+                );
+                run_benchmarks(&group_arguments, Some(&group.name))?;
+            }
+
+            Ok(())
+        }
+        None => {
+            interrupt::install_handler()?;
+            run_benchmarks(&cli_arguments, None)
+        }
+    }
+}
+
+/// Run the benchmarks for the given command line arguments, optionally as part of a named
+/// group of a benchmark suite
+fn run_benchmarks(cli_arguments: &ArgMatches, group: Option<&str>) -> Result<()> {
+    let options = Options::from_cli_arguments(cli_arguments)?;
+    let commands = Commands::from_cli_arguments(cli_arguments)?;
 
     options.validate_against_command_list(&commands)?;
 
+    if let Some(name) = group {
+        if options.output_style != OutputStyleOption::Disabled {
+            println!("{} {}\n", "Benchmark group:".bold(), name.bold());
+        }
+    }
+
+    if cli_arguments.get_flag("dry-run") {
+        return Plan::new(&commands, &options).print();
+    }
+
+    // The results need to be read before the export files are (re-)created
+    let resumed_results = options
+        .resume_file
+        .as_deref()
+        .map(read_json_results)
+        .transpose()?;
+    let baseline = options
+        .baseline_file
+        .as_deref()
+        .map(|filename| {
+            Baseline::load(
+                filename,
+                options.regression_threshold,
+                options.significance_test,
+            )
+        })
+        .transpose()?;
+
+    let export_manager = ExportManager::from_cli_arguments(
+        cli_arguments,
+        options.time_unit,
+        options.significance_test,
+        options.reference_command.is_some(),
+    )?;
+
     let mut scheduler = Scheduler::new(&commands, &options, &export_manager);
+    if let Some(results) = resumed_results {
+        scheduler.resume_from(results);
+    }
+    if let Some(baseline) = baseline {
+        scheduler.compare_with(baseline);
+    }
     scheduler.run_benchmarks()?;
     scheduler.print_relative_speed_comparison();
+    scheduler.print_baseline_comparison();
     scheduler.final_export()?;
 
+    if interrupt::is_interrupted() {
+        std::process::exit(interrupt::INTERRUPTED_EXIT_CODE);
+    }
+
+    scheduler.check_for_regressions()?;
+
     Ok(())
 }
 
@@ -19,6 +19,9 @@ pub const DEFAULT_SHELL: &str = "sh";
 #[cfg(windows)]
 pub const DEFAULT_SHELL: &str = "cmd.exe";
 
+/// Upper limit for `--bootstrap-resamples`, as all resampled estimates are kept in memory
+const MAX_BOOTSTRAP_RESAMPLES: u64 = 1_000_000;
+
 /// Shell to use for executing benchmarked commands
 #[derive(Debug, PartialEq)]
 pub enum Shell {
@@ -66,6 +69,29 @@ impl Shell {
     }
 }
 
+/// Parse the value of an `--env` option: one or more `KEY=VALUE` assignments, separated by
+/// whitespace and with shell-like quoting
+pub fn parse_environment<'a>(s: &str) -> Result<Vec<(String, String)>, OptionsError<'a>> {
+    shell_words::split(s)
+        .map_err(|e| OptionsError::EnvironmentParseError(s.into(), e))?
+        .into_iter()
+        .map(|assignment| match assignment.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.into(), value.into())),
+            _ => Err(OptionsError::InvalidEnvironmentVariable(assignment)),
+        })
+        .collect()
+}
+
+/// The value of an option like `--prepare` for the benchmark with the given number. These
+/// options can be given once for all commands, or once for each of them.
+pub fn value_for_command(values: &Option<Vec<String>>, number: usize) -> Option<&str> {
+    let values = values.as_ref()?;
+    values
+        .get(number)
+        .or_else(|| values.first())
+        .map(|v| v.as_str())
+}
+
 /// Action to take when an executed command fails.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CmdFailureAction {
@@ -101,6 +127,86 @@ pub enum SortOrder {
     MeanTime,
 }
 
+/// Statistical test that is used to decide whether two benchmark results differ significantly
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SignificanceTest {
+    /// Welch's t-test (compares the means, does not assume equal variances)
+    #[default]
+    Welch,
+
+    /// Mann-Whitney U test (rank-based, robust against outliers)
+    MannWhitney,
+}
+
+impl SignificanceTest {
+    pub fn name(self) -> &'static str {
+        match self {
+            SignificanceTest::Welch => "welch",
+            SignificanceTest::MannWhitney => "mann-whitney",
+        }
+    }
+}
+
+/// In which order the timing runs of the different commands are performed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionOrder {
+    /// Perform all timing runs of a command before moving on to the next one
+    #[default]
+    Sequential,
+
+    /// Alternate between the commands, performing one timing run at a time
+    RoundRobin,
+
+    /// Like `RoundRobin`, but with the order of the commands shuffled in each round
+    Random,
+}
+
+/// Statistic whose confidence interval decides when to stop adding timing runs
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PrecisionStatistic {
+    #[default]
+    Mean,
+    Median,
+}
+
+/// Settings for adaptive stopping: timing runs are added until the confidence interval
+/// of the chosen statistic is narrow enough
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetPrecision {
+    /// Maximum half width of the confidence interval, relative to the statistic (0.01 = ±1%)
+    pub relative_half_width: f64,
+
+    /// Whether to look at the confidence interval of the mean or of the median
+    pub statistic: PrecisionStatistic,
+
+    /// Maximum time to spend on the timing runs of a single command
+    pub time_budget: Second,
+}
+
+impl TargetPrecision {
+    /// Time budget that is used if none has been specified explicitly
+    pub const DEFAULT_TIME_BUDGET: Second = 60.0;
+
+    /// Parse a precision like "1%" or "1" (both meaning ±1%)
+    fn parse_relative_half_width<'a>(s: &str) -> Result<f64, OptionsError<'a>> {
+        let fraction = parse_percentage("target-precision", s)?;
+        if !(fraction.is_finite() && fraction > 0.0) {
+            return Err(OptionsError::InvalidTargetPrecision(s.to_string()));
+        }
+        Ok(fraction)
+    }
+}
+
+/// Parse a percentage like "5%" or "5" (both meaning 0.05) given to the option `option`
+fn parse_percentage<'a>(option: &'a str, value: &str) -> Result<f64, OptionsError<'a>> {
+    let percent = value
+        .trim()
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map_err(|e| OptionsError::FloatParsingError(option, e))?;
+    Ok(percent / 100.0)
+}
+
 /// Bounds for the number of benchmark runs
 pub struct RunBounds {
     /// Minimum number of benchmark runs
@@ -190,6 +296,27 @@ impl Default for ExecutorKind {
     }
 }
 
+impl ExecutorKind {
+    /// Select the executor from the '--shell', '-N' and '--debug-mode' options
+    pub fn from_cli_arguments<'a>(matches: &ArgMatches) -> Result<Self, OptionsError<'a>> {
+        Ok(if matches.get_flag("no-shell") {
+            ExecutorKind::Raw
+        } else {
+            match (
+                matches.get_flag("debug-mode"),
+                matches.get_one::<String>("shell"),
+            ) {
+                (false, Some(shell)) if shell == "default" => ExecutorKind::Shell(Shell::default()),
+                (false, Some(shell)) if shell == "none" => ExecutorKind::Raw,
+                (false, Some(shell)) => ExecutorKind::Shell(Shell::parse_from_str(shell)?),
+                (false, None) => ExecutorKind::Shell(Shell::default()),
+                (true, Some(shell)) => ExecutorKind::Mock(Some(shell.into())),
+                (true, None) => ExecutorKind::Mock(None),
+            }
+        })
+    }
+}
+
 /// The main settings for a hyperfine benchmark session
 pub struct Options {
     /// Upper and lower bound for the number of benchmark runs
@@ -201,9 +328,21 @@ pub struct Options {
     /// Minimum benchmarking time
     pub min_benchmarking_time: Second,
 
+    /// Keep adding runs until this precision is reached (instead of using a fixed number of runs)
+    pub target_precision: Option<TargetPrecision>,
+
+    /// Confidence level of all confidence intervals (0.95 = 95%)
+    pub confidence_level: f64,
+
+    /// Number of bootstrap resamples for the confidence intervals (0 = disabled)
+    pub bootstrap_resamples: u64,
+
     /// Whether or not to ignore non-zero exit codes
     pub command_failure_action: CmdFailureAction,
 
+    /// Whether to interleave the timing runs of the different commands
+    pub execution_order: ExecutionOrder,
+
     // Command to use as a reference for relative speed comparison
     pub reference_command: Option<String>,
 
@@ -219,6 +358,15 @@ pub struct Options {
     /// Command to run after each *batch* of timing runs, i.e. after each individual benchmark
     pub cleanup_command: Option<String>,
 
+    /// Environment variables (`KEY=VALUE` assignments) for the commands
+    pub environment: Option<Vec<String>>,
+
+    /// Whether to start the commands with an empty environment
+    pub clear_environment: bool,
+
+    /// Directory to run the commands in
+    pub working_directory: Option<Vec<String>>,
+
     /// What color mode to use for the terminal output
     pub output_style: OutputStyleOption,
 
@@ -228,6 +376,21 @@ pub struct Options {
     /// How to order benchmarks in the markup format exports
     pub sort_order_exports: SortOrder,
 
+    /// Whether or not to print percentiles, IQR and coefficient of variation
+    pub show_percentiles: bool,
+
+    /// Which test to use for deciding whether differences are statistically significant
+    pub significance_test: SignificanceTest,
+
+    /// JSON export of a previous run to compare the results with
+    pub baseline_file: Option<String>,
+
+    /// Relative slowdown compared to the baseline that counts as a regression (0.05 = 5%)
+    pub regression_threshold: f64,
+
+    /// JSON export of an interrupted run whose completed results should be reused
+    pub resume_file: Option<String>,
+
     /// Determines how we run commands
     pub executor_kind: ExecutorKind,
 
@@ -239,6 +402,9 @@ pub struct Options {
 
     /// Which time unit to use when displaying results
     pub time_unit: Option<Unit>,
+
+    /// Maximum time a single command invocation may take before it is terminated
+    pub timeout: Option<Second>,
 }
 
 impl Default for Options {
@@ -247,19 +413,32 @@ impl Default for Options {
             run_bounds: RunBounds::default(),
             warmup_count: 0,
             min_benchmarking_time: 3.0,
+            target_precision: None,
+            confidence_level: 0.95,
+            bootstrap_resamples: 10_000,
             command_failure_action: CmdFailureAction::RaiseError,
+            execution_order: ExecutionOrder::default(),
             reference_command: None,
             preparation_command: None,
             conclusion_command: None,
             setup_command: None,
             cleanup_command: None,
+            environment: None,
+            clear_environment: false,
+            working_directory: None,
             output_style: OutputStyleOption::Full,
             sort_order_speed_comparison: SortOrder::MeanTime,
             sort_order_exports: SortOrder::Command,
+            show_percentiles: false,
+            significance_test: SignificanceTest::default(),
+            baseline_file: None,
+            resume_file: None,
+            regression_threshold: 0.05,
             executor_kind: ExecutorKind::default(),
             command_output_policy: CommandOutputPolicy::Null,
             time_unit: None,
             command_input_policy: CommandInputPolicy::Null,
+            timeout: None,
         }
     }
 }
@@ -279,6 +458,14 @@ impl Options {
 
         options.warmup_count = param_to_u64("warmup")?.unwrap_or(options.warmup_count);
 
+        options.bootstrap_resamples =
+            param_to_u64("bootstrap-resamples")?.unwrap_or(options.bootstrap_resamples);
+        if options.bootstrap_resamples > MAX_BOOTSTRAP_RESAMPLES {
+            return Err(OptionsError::TooManyBootstrapResamples(
+                MAX_BOOTSTRAP_RESAMPLES,
+            ));
+        }
+
         let mut min_runs = param_to_u64("min-runs")?;
         let mut max_runs = param_to_u64("max-runs")?;
 
@@ -320,6 +507,18 @@ impl Options {
 
         options.cleanup_command = matches.get_one::<String>("cleanup").map(String::from);
 
+        options.environment = matches
+            .get_many::<String>("env")
+            .map(|values| values.map(String::from).collect::<Vec<String>>());
+        for environment in options.environment.iter().flatten() {
+            parse_environment(environment)?;
+        }
+        options.clear_environment = matches.get_flag("env-clear");
+
+        options.working_directory = matches
+            .get_many::<String>("cwd")
+            .map(|values| values.map(String::from).collect::<Vec<String>>());
+
         options.command_output_policy = if matches.get_flag("show-output") {
             CommandOutputPolicy::Inherit
         } else if let Some(output) = matches.get_one::<String>("output").map(|s| s.as_str()) {
@@ -384,22 +583,29 @@ impl Options {
             Some(_) => unreachable!("Unknown sort order"),
         };
 
-        options.executor_kind = if matches.get_flag("no-shell") {
-            ExecutorKind::Raw
-        } else {
-            match (
-                matches.get_flag("debug-mode"),
-                matches.get_one::<String>("shell"),
-            ) {
-                (false, Some(shell)) if shell == "default" => ExecutorKind::Shell(Shell::default()),
-                (false, Some(shell)) if shell == "none" => ExecutorKind::Raw,
-                (false, Some(shell)) => ExecutorKind::Shell(Shell::parse_from_str(shell)?),
-                (false, None) => ExecutorKind::Shell(Shell::default()),
-                (true, Some(shell)) => ExecutorKind::Mock(Some(shell.into())),
-                (true, None) => ExecutorKind::Mock(None),
-            }
+        options.show_percentiles = matches.get_flag("show-percentiles");
+
+        options.significance_test = match matches
+            .get_one::<String>("significance-test")
+            .map(|s| s.as_str())
+        {
+            None | Some("welch") => SignificanceTest::Welch,
+            Some("mann-whitney") => SignificanceTest::MannWhitney,
+            Some(_) => unreachable!("Unknown significance test"),
+        };
+
+        options.execution_order = match matches
+            .get_one::<String>("execution-order")
+            .map(|s| s.as_str())
+        {
+            None | Some("sequential") => ExecutionOrder::Sequential,
+            Some("round-robin") => ExecutionOrder::RoundRobin,
+            Some("random") => ExecutionOrder::Random,
+            Some(_) => unreachable!("Unknown execution order"),
         };
 
+        options.executor_kind = ExecutorKind::from_cli_arguments(matches)?;
+
         if matches.get_flag("ignore-failure") {
             options.command_failure_action = CmdFailureAction::Ignore;
         }
@@ -417,6 +623,64 @@ impl Options {
                 .map_err(|e| OptionsError::FloatParsingError("min-benchmarking-time", e))?;
         }
 
+        if let Some(level) = matches.get_one::<String>("confidence-level") {
+            let fraction = parse_percentage("confidence-level", level)?;
+            if !(fraction > 0.0 && fraction < 1.0) {
+                return Err(OptionsError::InvalidConfidenceLevel(level.to_string()));
+            }
+            options.confidence_level = fraction;
+        }
+
+        options.baseline_file = matches.get_one::<String>("baseline").cloned();
+        options.resume_file = matches.get_one::<String>("resume").cloned();
+
+        if let Some(threshold) = matches.get_one::<String>("regression-threshold") {
+            let fraction = parse_percentage("regression-threshold", threshold)?;
+            if !(fraction.is_finite() && fraction >= 0.0) {
+                return Err(OptionsError::InvalidRegressionThreshold(
+                    threshold.to_string(),
+                ));
+            }
+            options.regression_threshold = fraction;
+        }
+
+        if let Some(precision) = matches.get_one::<String>("target-precision") {
+            let time_budget = matches
+                .get_one::<String>("time-budget")
+                .map(|t| {
+                    t.parse::<f64>()
+                        .map_err(|e| OptionsError::FloatParsingError("time-budget", e))
+                })
+                .transpose()?
+                .unwrap_or(TargetPrecision::DEFAULT_TIME_BUDGET);
+            if !(time_budget.is_finite() && time_budget > 0.0) {
+                return Err(OptionsError::InvalidTimeBudget);
+            }
+
+            options.target_precision = Some(TargetPrecision {
+                relative_half_width: TargetPrecision::parse_relative_half_width(precision)?,
+                statistic: match matches
+                    .get_one::<String>("target-precision-statistic")
+                    .map(|s| s.as_str())
+                {
+                    None | Some("mean") => PrecisionStatistic::Mean,
+                    Some("median") => PrecisionStatistic::Median,
+                    Some(_) => unreachable!("Unknown statistic"),
+                },
+                time_budget,
+            });
+        }
+
+        if let Some(timeout) = matches.get_one::<String>("timeout") {
+            let timeout = timeout
+                .parse::<f64>()
+                .map_err(|e| OptionsError::FloatParsingError("timeout", e))?;
+            if !(timeout.is_finite() && timeout > 0.0) {
+                return Err(OptionsError::InvalidTimeout);
+            }
+            options.timeout = Some(timeout);
+        }
+
         options.command_input_policy = if let Some(path_str) = matches.get_one::<String>("input") {
             if path_str == "null" {
                 CommandInputPolicy::Null
@@ -443,26 +707,60 @@ impl Options {
             } else {
                 0
             };
-        if let Some(preparation_command) = &self.preparation_command {
-            ensure!(
-                preparation_command.len() <= 1 || num_commands == preparation_command.len(),
-                "The '--prepare' option has to be provided just once or N times, where N is the \
-             number of benchmark commands including a potential reference."
-            );
+        for (option, values) in [
+            ("--prepare", &self.preparation_command),
+            ("--conclude", &self.conclusion_command),
+            ("--env", &self.environment),
+            ("--cwd", &self.working_directory),
+        ] {
+            if let Some(values) = values {
+                ensure!(
+                    values.len() <= 1 || num_commands == values.len(),
+                    "The '{option}' option has to be provided just once or N times, where N is \
+                     the number of benchmark commands including a potential reference."
+                );
+            }
         }
 
-        if let Some(conclusion_command) = &self.conclusion_command {
-            ensure!(
-                conclusion_command.len() <= 1 || num_commands == conclusion_command.len(),
-                "The '--conclude' option has to be provided just once or N times, where N is the \
-             number of benchmark commands including a potential reference."
-            );
+        let first_number = num_commands - commands.num_commands();
+        for (number, command) in (first_number..).zip(commands.iter()) {
+            let templates = [
+                self.setup_command.as_deref(),
+                value_for_command(&self.preparation_command, number),
+                value_for_command(&self.conclusion_command, number),
+                self.cleanup_command.as_deref(),
+                value_for_command(&self.environment, number),
+                value_for_command(&self.working_directory, number),
+            ];
+            for template in templates.iter().flatten() {
+                command.check_placeholders_in(template)?;
+            }
         }
 
         Ok(())
     }
 }
 
+#[test]
+fn test_parse_target_precision() {
+    assert_eq!(
+        TargetPrecision::parse_relative_half_width("1%").unwrap(),
+        0.01
+    );
+    assert_eq!(
+        TargetPrecision::parse_relative_half_width("2.5").unwrap(),
+        0.025
+    );
+    assert!(matches!(
+        TargetPrecision::parse_relative_half_width("0%").unwrap_err(),
+        OptionsError::InvalidTargetPrecision(_)
+    ));
+    assert!(matches!(
+        TargetPrecision::parse_relative_half_width("abc").unwrap_err(),
+        OptionsError::FloatParsingError("target-precision", _)
+    ));
+}
+
 #[test]
 fn test_default_shell() {
     let shell = Shell::default();
@@ -506,3 +804,29 @@ fn test_can_parse_shell_command_line_from_str() {
         OptionsError::EmptyShell
     ));
 }
+
+#[test]
+fn test_parse_environment() {
+    assert_eq!(
+        parse_environment("A=1 B='x y' C= D=a=b").unwrap(),
+        vec![
+            ("A".to_string(), "1".to_string()),
+            ("B".to_string(), "x y".to_string()),
+            ("C".to_string(), "".to_string()),
+            ("D".to_string(), "a=b".to_string()),
+        ]
+    );
+
+    assert!(matches!(
+        parse_environment("A=1 B").unwrap_err(),
+        OptionsError::InvalidEnvironmentVariable(v) if v == "B"
+    ));
+    assert!(matches!(
+        parse_environment("=1").unwrap_err(),
+        OptionsError::InvalidEnvironmentVariable(_)
+    ));
+    assert!(matches!(
+        parse_environment("A='1").unwrap_err(),
+        OptionsError::EnvironmentParseError(_, _)
+    ));
+}
@@ -25,6 +25,59 @@ pub fn format_duration_value(duration: Second, unit: Option<Unit>) -> (String, U
     }
 }
 
+/// Format a duration that can be much longer than a single run, e.g. "2 h 5 min" or "42.0 s"
+pub fn format_long_duration(duration: Second) -> String {
+    let seconds = duration.round() as u64;
+    if seconds >= 3600 {
+        format!("{} h {} min", seconds / 3600, seconds % 3600 / 60)
+    } else if seconds >= 60 {
+        format!("{} min {} s", seconds / 60, seconds % 60)
+    } else {
+        format!("{duration:.1} s")
+    }
+}
+
+/// Format a fraction as a percentage, e.g. "95%" for 0.95 or "99.9%" for 0.999
+pub fn format_percentage(fraction: f64) -> String {
+    let percent = format!("{:.1}", fraction * 100.0);
+    format!("{}%", percent.trim_end_matches(".0"))
+}
+
+/// Format a fraction as a percentage with two decimal places, e.g. "1.25%" for 0.0125
+pub fn format_percentage_precise(fraction: f64) -> String {
+    format!("{:.2}%", fraction * 100.0)
+}
+
+/// Format a p-value, e.g. "p = 0.012" or "p < 0.001" for very small values
+pub fn format_p_value(p_value: f64) -> String {
+    if p_value < 0.001 {
+        "p < 0.001".into()
+    } else {
+        format!("p = {p_value:.3}")
+    }
+}
+
+/// Format the given number of bytes as a human readable string, using binary prefixes.
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024.0 {
+        return format!("{bytes:.0} B");
+    }
+
+    let mut value = bytes / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
 #[test]
 fn test_format_duration_unit_basic() {
     let (out_str, out_unit) = format_duration_unit(1.3, None);
@@ -75,3 +128,35 @@ fn test_format_duration_unit_with_unit() {
     assert_eq!("1300000.0 µs", out_str);
     assert_eq!(Unit::MicroSecond, out_unit);
 }
+
+#[test]
+fn test_format_long_duration() {
+    assert_eq!("42.0 s", format_long_duration(42.0));
+    assert_eq!("2 min 5 s", format_long_duration(125.2));
+    assert_eq!("4 h 0 min", format_long_duration(14_400.0));
+    assert_eq!("1 h 1 min", format_long_duration(3_690.0));
+}
+
+#[test]
+fn test_format_percentage() {
+    assert_eq!("95%", format_percentage(0.95));
+    assert_eq!("99.9%", format_percentage(0.999));
+    assert_eq!("0.5%", format_percentage(0.005));
+    assert_eq!("1.25%", format_percentage_precise(0.0125));
+}
+
+#[test]
+fn test_format_p_value() {
+    assert_eq!("p < 0.001", format_p_value(0.000_01));
+    assert_eq!("p = 0.001", format_p_value(0.001));
+    assert_eq!("p = 0.437", format_p_value(0.4372));
+}
+
+#[test]
+fn test_format_bytes() {
+    assert_eq!("0 B", format_bytes(0.0));
+    assert_eq!("1023 B", format_bytes(1023.0));
+    assert_eq!("1.0 KiB", format_bytes(1024.0));
+    assert_eq!("1.5 MiB", format_bytes(1.5 * 1024.0 * 1024.0));
+    assert_eq!("2.0 GiB", format_bytes(2.0 * 1024.0 * 1024.0 * 1024.0));
+}
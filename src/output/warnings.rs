@@ -13,6 +13,9 @@ pub struct OutlierWarningOptions {
 pub enum Warnings {
     FastExecutionTime,
     NonZeroExitCode,
+    TimedOut(usize),
+    TargetPrecisionNotReached(Option<f64>, f64),
+    Interrupted(u64, u64),
     SlowInitialRun(Second, OutlierWarningOptions),
     OutliersDetected(OutlierWarningOptions),
 }
@@ -29,6 +32,27 @@ impl fmt::Display for Warnings {
                 MIN_EXECUTION_TIME * 1e3
             ),
             Warnings::NonZeroExitCode => write!(f, "Ignoring non-zero exit code."),
+            Warnings::TimedOut(count) => write!(
+                f,
+                "{count} run(s) exceeded the timeout and have been terminated. The measured \
+                 times of these runs are included in the results."
+            ),
+            Warnings::TargetPrecisionNotReached(precision, target) => write!(
+                f,
+                "The target precision of ±{:.1}% has not been reached{}. Consider increasing \
+                 the '--time-budget' or '--max-runs', or re-running this benchmark on a quiet \
+                 system.",
+                target * 100.0,
+                match precision {
+                    Some(precision) => format!(" (achieved: ±{:.1}%)", precision * 100.0),
+                    None => String::new(),
+                }
+            ),
+            Warnings::Interrupted(runs, planned_runs) => write!(
+                f,
+                "The benchmark has been interrupted after {runs} of {planned_runs} runs. \
+                 The results are incomplete."
+            ),
             Warnings::SlowInitialRun(time_first_run, ref options) => write!(
                 f,
                 "The first benchmarking run for this command was significantly slower than the \
@@ -0,0 +1,458 @@
+//! A small expression language over parameter values, which is used for placeholders like
+//! `{n*2}` and for `--parameter-filter`. It supports numbers, strings in single or double
+//! quotes, parameter names, arithmetic (`+ - * / %`), comparisons (`== != < <= > >=`) and
+//! boolean logic (`&& || !`), with the usual precedence rules and parentheses.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use super::suffix::expand_size_suffix;
+use super::ParameterValue;
+
+use anyhow::{anyhow, bail, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(Decimal),
+    Text(String),
+    Parameter(String),
+    Negate(Box<Expression>),
+    Not(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    And,
+    Or,
+}
+
+/// The result of evaluating an expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(Decimal),
+    Text(String),
+    Bool(bool),
+}
+
+impl Value {
+    /// The numeric value, if this is a number or a text that can be parsed as one (possibly
+    /// with a size suffix like "4K")
+    pub fn as_number(&self) -> Option<Decimal> {
+        match self {
+            Value::Number(number) => Some(*number),
+            Value::Text(text) => Decimal::from_str(&expand_size_suffix(text.trim())).ok(),
+            Value::Bool(_) => None,
+        }
+    }
+
+    /// The numeric value, or an error if this is not a number
+    pub fn number(&self) -> Result<Decimal> {
+        self.as_number()
+            .ok_or_else(|| anyhow!("Expected a number, got '{self}'"))
+    }
+
+    fn bool(&self) -> Result<bool> {
+        match self {
+            Value::Bool(value) => Ok(*value),
+            _ => bail!("Expected a condition, got '{self}'"),
+        }
+    }
+}
+
+impl From<&ParameterValue> for Value {
+    fn from(value: &ParameterValue) -> Self {
+        match value {
+            ParameterValue::Text(text) => Value::Text(text.clone()),
+            ParameterValue::Numeric(_) => Value::Number(value.as_decimal().unwrap()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(number) => write!(f, "{number}"),
+            Value::Text(text) => write!(f, "{text}"),
+            Value::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl Expression {
+    pub fn parse(text: &str) -> Result<Expression> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let expression = parser.or()?;
+        match parser.peek() {
+            None => Ok(expression),
+            Some(token) => bail!("Unexpected '{token}'"),
+        }
+    }
+
+    /// Names of all parameters that the expression refers to
+    pub fn parameters(&self) -> Vec<&str> {
+        match self {
+            Expression::Number(_) | Expression::Text(_) => vec![],
+            Expression::Parameter(name) => vec![name.as_str()],
+            Expression::Negate(inner) | Expression::Not(inner) => inner.parameters(),
+            Expression::Binary(_, lhs, rhs) => {
+                let mut parameters = lhs.parameters();
+                parameters.extend(rhs.parameters());
+                parameters
+            }
+        }
+    }
+
+    /// Evaluate the expression with the given parameter values, which has to result in
+    /// `true` or `false`
+    pub fn evaluate_condition(&self, lookup: &dyn Fn(&str) -> Option<Value>) -> Result<bool> {
+        self.evaluate(lookup)?.bool()
+    }
+
+    /// Evaluate the expression with the given parameter values
+    pub fn evaluate(&self, lookup: &dyn Fn(&str) -> Option<Value>) -> Result<Value> {
+        Ok(match self {
+            Expression::Number(number) => Value::Number(*number),
+            Expression::Text(text) => Value::Text(text.clone()),
+            Expression::Parameter(name) => {
+                lookup(name).ok_or_else(|| anyhow!("Unknown parameter '{name}'"))?
+            }
+            Expression::Negate(inner) => Value::Number(-inner.evaluate(lookup)?.number()?),
+            Expression::Not(inner) => Value::Bool(!inner.evaluate(lookup)?.bool()?),
+            Expression::Binary(Operator::And, lhs, rhs) => {
+                Value::Bool(lhs.evaluate(lookup)?.bool()? && rhs.evaluate(lookup)?.bool()?)
+            }
+            Expression::Binary(Operator::Or, lhs, rhs) => {
+                Value::Bool(lhs.evaluate(lookup)?.bool()? || rhs.evaluate(lookup)?.bool()?)
+            }
+            Expression::Binary(operator, lhs, rhs) => {
+                let lhs = lhs.evaluate(lookup)?;
+                let rhs = rhs.evaluate(lookup)?;
+                match operator {
+                    Operator::Add => arithmetic(&lhs, &rhs, Decimal::checked_add)?,
+                    Operator::Subtract => arithmetic(&lhs, &rhs, Decimal::checked_sub)?,
+                    Operator::Multiply => arithmetic(&lhs, &rhs, Decimal::checked_mul)?,
+                    Operator::Divide => arithmetic(&lhs, &rhs, Decimal::checked_div)?,
+                    Operator::Remainder => arithmetic(&lhs, &rhs, Decimal::checked_rem)?,
+                    _ => {
+                        let ordering = compare(&lhs, &rhs);
+                        Value::Bool(match operator {
+                            Operator::Equal => ordering == Some(Ordering::Equal),
+                            Operator::NotEqual => ordering != Some(Ordering::Equal),
+                            _ => {
+                                let ordering = ordering.ok_or_else(|| {
+                                    anyhow!("Can not compare '{lhs}' and '{rhs}'")
+                                })?;
+                                match operator {
+                                    Operator::Less => ordering.is_lt(),
+                                    Operator::LessOrEqual => ordering.is_le(),
+                                    Operator::Greater => ordering.is_gt(),
+                                    _ => ordering.is_ge(),
+                                }
+                            }
+                        })
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn arithmetic(
+    lhs: &Value,
+    rhs: &Value,
+    operation: fn(Decimal, Decimal) -> Option<Decimal>,
+) -> Result<Value> {
+    operation(lhs.number()?, rhs.number()?)
+        .map(Value::Number)
+        .ok_or_else(|| anyhow!("Invalid arithmetic operation on '{lhs}' and '{rhs}'"))
+}
+
+/// Values are compared as numbers if both of them are numeric, and as strings otherwise
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Bool(lhs), Value::Bool(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Bool(_), _) | (_, Value::Bool(_)) => None,
+        _ => match (lhs.as_number(), rhs.as_number()) {
+            (Some(lhs), Some(rhs)) => Some(lhs.cmp(&rhs)),
+            _ => Some(lhs.to_string().cmp(&rhs.to_string())),
+        },
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Number(Decimal),
+    Text(String),
+    Identifier(&'a str),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Text(text) => write!(f, "'{text}'"),
+            Token::Identifier(name) => write!(f, "{name}"),
+            Token::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+/// Symbols, with the longer ones first such that e.g. `<=` is not read as `<`
+const SYMBOLS: [&str; 16] = [
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "+", "-", "*", "/", "%", "(", ")",
+];
+
+fn tokenize(text: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    let mut remaining = text.trim_start();
+
+    while let Some(c) = remaining.chars().next() {
+        let length = if c.is_ascii_digit() || c == '.' {
+            let length = remaining
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(remaining.len());
+            let number = &remaining[..length];
+            tokens.push(Token::Number(
+                Decimal::from_str(number).map_err(|_| anyhow!("Invalid number '{number}'"))?,
+            ));
+            length
+        } else if c.is_alphabetic() || c == '_' {
+            let length = remaining
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(remaining.len());
+            tokens.push(Token::Identifier(&remaining[..length]));
+            length
+        } else if c == '\'' || c == '"' {
+            let end = remaining[1..]
+                .find(c)
+                .ok_or_else(|| anyhow!("Unterminated string"))?;
+            tokens.push(Token::Text(remaining[1..=end].to_string()));
+            end + 2
+        } else {
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| remaining.starts_with(*symbol))
+                .ok_or_else(|| anyhow!("Unexpected '{c}'"))?;
+            tokens.push(Token::Symbol(symbol));
+            symbol.len()
+        };
+        remaining = remaining[length..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser, with one method for each level of precedence
+struct Parser<'t, 'a> {
+    tokens: &'t [Token<'a>],
+    position: usize,
+}
+
+impl Parser<'_, '_> {
+    fn peek(&self) -> Option<&Token<'_>> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<&Token<'_>> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| anyhow!("Unexpected end of expression"))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// Consumes the next token if it is one of the given symbols
+    fn symbol(&mut self, symbols: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Symbol(symbol)) if symbols.contains(symbol) => {
+                let symbol = *symbol;
+                self.position += 1;
+                Some(symbol)
+            }
+            _ => None,
+        }
+    }
+
+    fn binary(
+        &mut self,
+        symbols: &[&'static str],
+        operand: fn(&mut Self) -> Result<Expression>,
+    ) -> Result<Expression> {
+        let mut expression = operand(self)?;
+        while let Some(symbol) = self.symbol(symbols) {
+            let operator = match symbol {
+                "||" => Operator::Or,
+                "&&" => Operator::And,
+                "==" => Operator::Equal,
+                "!=" => Operator::NotEqual,
+                "<" => Operator::Less,
+                "<=" => Operator::LessOrEqual,
+                ">" => Operator::Greater,
+                ">=" => Operator::GreaterOrEqual,
+                "+" => Operator::Add,
+                "-" => Operator::Subtract,
+                "*" => Operator::Multiply,
+                "/" => Operator::Divide,
+                _ => Operator::Remainder,
+            };
+            expression =
+                Expression::Binary(operator, Box::new(expression), Box::new(operand(self)?));
+        }
+        Ok(expression)
+    }
+
+    fn or(&mut self) -> Result<Expression> {
+        self.binary(&["||"], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Expression> {
+        self.binary(&["&&"], Self::not)
+    }
+
+    fn not(&mut self) -> Result<Expression> {
+        if self.symbol(&["!"]).is_some() {
+            Ok(Expression::Not(Box::new(self.not()?)))
+        } else {
+            self.comparison()
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expression> {
+        let lhs = self.sum()?;
+        let operator = match self.symbol(&["==", "!=", "<=", ">=", "<", ">"]) {
+            Some("==") => Operator::Equal,
+            Some("!=") => Operator::NotEqual,
+            Some("<=") => Operator::LessOrEqual,
+            Some(">=") => Operator::GreaterOrEqual,
+            Some("<") => Operator::Less,
+            Some(_) => Operator::Greater,
+            None => return Ok(lhs),
+        };
+        Ok(Expression::Binary(
+            operator,
+            Box::new(lhs),
+            Box::new(self.sum()?),
+        ))
+    }
+
+    fn sum(&mut self) -> Result<Expression> {
+        self.binary(&["+", "-"], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Expression> {
+        self.binary(&["*", "/", "%"], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Expression> {
+        if self.symbol(&["-"]).is_some() {
+            Ok(Expression::Negate(Box::new(self.unary()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expression> {
+        match self.next()?.clone() {
+            Token::Number(number) => Ok(Expression::Number(number)),
+            Token::Text(text) => Ok(Expression::Text(text)),
+            Token::Identifier(name) => Ok(Expression::Parameter(name.to_string())),
+            Token::Symbol("(") => {
+                let expression = self.or()?;
+                match self.next()? {
+                    Token::Symbol(")") => Ok(expression),
+                    token => bail!("Expected ')', got '{token}'"),
+                }
+            }
+            token => bail!("Unexpected '{token}'"),
+        }
+    }
+}
+
+#[cfg(test)]
+fn evaluate(text: &str) -> Result<Value> {
+    let lookup = |name: &str| match name {
+        "threads" => Some(Value::Text("8".into())),
+        "cores" => Some(Value::Number(Decimal::from(4))),
+        "profile" => Some(Value::Text("release".into())),
+        _ => None,
+    };
+    Expression::parse(text)?.evaluate(&lookup)
+}
+
+#[cfg(test)]
+fn number(text: &str) -> Value {
+    Value::Number(Decimal::from_str(text).unwrap())
+}
+
+#[test]
+fn test_arithmetic() {
+    assert_eq!(evaluate("1 + 2 * 3").unwrap(), number("7"));
+    assert_eq!(evaluate("(1 + 2) * 3").unwrap(), number("9"));
+    assert_eq!(evaluate("threads / cores - -1").unwrap(), number("3"));
+    assert_eq!(evaluate("threads % 3").unwrap(), number("2"));
+    assert_eq!(evaluate("0.5 * 3").unwrap(), number("1.5"));
+}
+
+#[test]
+fn test_conditions() {
+    assert_eq!(evaluate("threads > cores").unwrap(), Value::Bool(true));
+    assert_eq!(evaluate("threads <= cores * 2").unwrap(), Value::Bool(true));
+    assert_eq!(evaluate("threads == '8'").unwrap(), Value::Bool(true));
+    assert_eq!(
+        evaluate("profile == \"release\"").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluate("!(profile == 'release' && cores < 2) || threads != 8").unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluate("profile != 'release' || !(threads > 4)").unwrap(),
+        Value::Bool(false)
+    );
+}
+
+#[test]
+fn test_errors() {
+    let error = |text| evaluate(text).unwrap_err().to_string();
+    assert_eq!(error("unknown + 1"), "Unknown parameter 'unknown'");
+    assert_eq!(error("profile * 2"), "Expected a number, got 'release'");
+    assert_eq!(error("cores && 1"), "Expected a condition, got '4'");
+    assert_eq!(
+        error("cores / 0"),
+        "Invalid arithmetic operation on '4' and '0'"
+    );
+    assert_eq!(error("cores +"), "Unexpected end of expression");
+    assert_eq!(error("(cores"), "Unexpected end of expression");
+    assert_eq!(error("cores cores"), "Unexpected 'cores'");
+    assert_eq!(error("$cores"), "Unexpected '$'");
+    assert_eq!(error("'cores"), "Unterminated string");
+    assert_eq!(error("1..2"), "Invalid number '1..2'");
+}
+
+#[test]
+fn test_parameters() {
+    let expression = Expression::parse("a * (b + 1) > -c").unwrap();
+    assert_eq!(expression.parameters(), vec!["a", "b", "c"]);
+}
@@ -1,7 +1,16 @@
 use crate::util::number::Number;
 use std::fmt::Display;
+use std::str::FromStr;
 
+use rust_decimal::Decimal;
+
+use self::suffix::expand_size_suffix;
+
+pub mod expression;
+pub mod placeholder;
 pub mod range_step;
+pub mod source;
+pub mod suffix;
 pub mod tokenize;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,4 +29,18 @@ impl Display for ParameterValue {
     }
 }
 
+impl ParameterValue {
+    /// The numeric value, if this is a number (or a text that can be parsed as one, possibly
+    /// with a size suffix like "4K")
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            ParameterValue::Text(value) => {
+                Decimal::from_str(&expand_size_suffix(value.trim())).ok()
+            }
+            ParameterValue::Numeric(Number::Int(value)) => Some(Decimal::from(*value)),
+            ParameterValue::Numeric(Number::Decimal(value)) => Some(*value),
+        }
+    }
+}
+
 pub type ParameterNameAndValue<'a> = (&'a str, ParameterValue);
@@ -0,0 +1,359 @@
+//! Placeholders for parameter values in commands. Besides the plain `{name}`, a placeholder can
+//! contain an arithmetic expression like `{n*2}` or `{size/1024}`, and a format specification
+//! like `{n:04}` (zero-padded to a width of four) or `{t*1.5:.2}` (two decimal places).
+//! Anything else in braces is left untouched, such that shell syntax like `${HOME}` or
+//! `awk '{print $1}'` keeps working. Braces that refer to a parameter but can not be evaluated,
+//! like `{n/0}`, are reported by `check_placeholders`.
+
+use anyhow::{anyhow, Error, Result};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use super::expression::{Expression, Value};
+use super::ParameterValue;
+
+/// Replaces all placeholders in `text` by the (formatted) parameter values. Substituted values
+/// are not scanned for placeholders again. Placeholders that can not be evaluated are left
+/// untouched; use `check_placeholders` to report them.
+pub fn replace_placeholders(text: &str, parameters: &[(&str, ParameterValue)]) -> String {
+    replace(text, parameters).0
+}
+
+/// Checks that all placeholders in `text` that refer to one of the `parameters` can be
+/// evaluated and formatted, e.g. that there is no `{n/0}` or `{n:x}`
+pub fn check_placeholders(text: &str, parameters: &[(&str, ParameterValue)]) -> Result<()> {
+    match replace(text, parameters).1 {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// The text with all placeholders replaced, and the error for the first placeholder that could
+/// not be evaluated (if any)
+fn replace(text: &str, parameters: &[(&str, ParameterValue)]) -> (String, Option<Error>) {
+    let mut result = String::new();
+    let mut first_error = None;
+    let mut remaining = text;
+
+    while let Some(start) = remaining.find('{') {
+        result.push_str(&remaining[..start]);
+        let after_brace = &remaining[start + 1..];
+
+        let substitution = after_brace.find('}').and_then(|end| {
+            let content = &after_brace[..end];
+            match substitute(content, parameters) {
+                Ok(value) => value.map(|value| (value, end)),
+                Err(error) => {
+                    first_error.get_or_insert_with(|| {
+                        error.context(format!("Invalid placeholder '{{{content}}}' in '{text}'"))
+                    });
+                    None
+                }
+            }
+        });
+        match substitution {
+            Some((value, end)) => {
+                result.push_str(&value);
+                remaining = &after_brace[end + 1..];
+            }
+            None => {
+                result.push('{');
+                remaining = after_brace;
+            }
+        }
+    }
+    result.push_str(remaining);
+
+    (result, first_error)
+}
+
+/// Whether any placeholder in `text` refers to the parameter `name`
+pub fn uses_parameter(text: &str, name: &str) -> bool {
+    let mut remaining = text;
+    while let Some(start) = remaining.find('{') {
+        remaining = &remaining[start + 1..];
+        let content = match remaining.find('}') {
+            Some(end) => &remaining[..end],
+            None => break,
+        };
+        if content == name {
+            return true;
+        }
+        let (expression, _) = split_format_spec(content);
+        if expression.trim() == name
+            || Expression::parse(expression)
+                .is_ok_and(|expression| expression.parameters().contains(&name))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Names of all parameters that the placeholders in `text` refer to, including names that are
+/// not in `parameters` (e.g. typos like `{thraeds}`). Braces that are preceded by a `$` are
+/// skipped, since they are most likely shell variables like `${HOME}`.
+pub fn referenced_parameters(text: &str, parameters: &[&str]) -> Vec<String> {
+    let mut names = vec![];
+    let mut remaining = text;
+    while let Some(start) = remaining.find('{') {
+        let is_shell_variable = remaining[..start].ends_with('$');
+        remaining = &remaining[start + 1..];
+        let content = match remaining.find('}') {
+            Some(end) => &remaining[..end],
+            None => break,
+        };
+        if is_shell_variable {
+            continue;
+        }
+
+        let (expression, _) = split_format_spec(content);
+        if let Some(name) = [content, expression.trim()]
+            .iter()
+            .copied()
+            .find(|name| parameters.contains(name))
+        {
+            names.push(name.to_string());
+        } else if let Ok(expression) = Expression::parse(expression) {
+            names.extend(expression.parameters().into_iter().map(String::from));
+        }
+    }
+    names
+}
+
+/// The value for the content of a placeholder (without the braces), if it is one. Braces that
+/// do not refer to any of the parameters are not a placeholder. For those that do, an error is
+/// returned if the expression can not be evaluated or the format specification is invalid.
+fn substitute(content: &str, parameters: &[(&str, ParameterValue)]) -> Result<Option<String>> {
+    let lookup = |name: &str| {
+        parameters
+            .iter()
+            .find(|(parameter, _)| *parameter == name)
+            .map(|(_, value)| value)
+    };
+
+    // Plain placeholders are substituted as they are, even if the parameter name
+    // contains characters that would otherwise be operators
+    if let Some(value) = lookup(content) {
+        return Ok(Some(value.to_string()));
+    }
+
+    let (expression, spec) = split_format_spec(content);
+    let value = match lookup(expression.trim()) {
+        Some(value) => Value::from(value),
+        None => {
+            let expression = match Expression::parse(expression) {
+                Ok(expression) => expression,
+                Err(_) => return Ok(None),
+            };
+            if !expression
+                .parameters()
+                .iter()
+                .any(|name| lookup(name).is_some())
+            {
+                return Ok(None);
+            }
+            expression.evaluate(&|name| lookup(name).map(Value::from))?
+        }
+    };
+    let value = value.number()?;
+
+    Ok(Some(match spec {
+        Some(spec) => FormatSpec::parse(spec)
+            .ok_or_else(|| anyhow!("Invalid format specification '{spec}'"))?
+            .format(value),
+        None => value.normalize().to_string(),
+    }))
+}
+
+fn split_format_spec(content: &str) -> (&str, Option<&str>) {
+    match content.split_once(':') {
+        Some((expression, spec)) => (expression, Some(spec)),
+        None => (content, None),
+    }
+}
+
+/// Format specification for numbers: an optional `0` for zero-padding, an optional minimum
+/// width and an optional number of decimal places, like `08.3`
+#[derive(Debug, PartialEq)]
+struct FormatSpec {
+    zero_pad: bool,
+    width: usize,
+    precision: Option<u32>,
+}
+
+impl FormatSpec {
+    fn parse(spec: &str) -> Option<FormatSpec> {
+        let (width, precision) = match spec.split_once('.') {
+            Some((width, precision)) => (width, Some(precision)),
+            None => (spec, None),
+        };
+        if width.is_empty() && precision.is_none() {
+            return None;
+        }
+
+        let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if !width.is_empty() && !is_number(width) {
+            return None;
+        }
+        let precision = match precision {
+            Some(precision) if is_number(precision) => Some(precision.parse().ok()?),
+            Some(_) => return None,
+            None => None,
+        };
+
+        Some(FormatSpec {
+            zero_pad: width.starts_with('0'),
+            width: if width.is_empty() {
+                0
+            } else {
+                width.parse().ok()?
+            },
+            precision,
+        })
+    }
+
+    fn format(&self, value: Decimal) -> String {
+        let formatted = match self.precision {
+            Some(precision) => {
+                let rounded =
+                    value.round_dp_with_strategy(precision, RoundingStrategy::MidpointAwayFromZero);
+                format!("{:.*}", precision as usize, rounded)
+            }
+            None => value.normalize().to_string(),
+        };
+
+        let padding = self.width.saturating_sub(formatted.chars().count());
+        if padding == 0 {
+            formatted
+        } else if self.zero_pad {
+            let (sign, digits) = match formatted.strip_prefix('-') {
+                Some(digits) => ("-", digits),
+                None => ("", formatted.as_str()),
+            };
+            format!("{sign}{}{digits}", "0".repeat(padding))
+        } else {
+            format!("{}{formatted}", " ".repeat(padding))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::number::Number;
+    use std::str::FromStr;
+
+    fn parameters() -> Vec<(&'static str, ParameterValue)> {
+        vec![
+            ("n", ParameterValue::Numeric(Number::Int(7))),
+            ("size", ParameterValue::Text("4096".into())),
+            (
+                "t",
+                ParameterValue::Numeric(Number::Decimal(Decimal::from_str("0.25").unwrap())),
+            ),
+            ("num-threads", ParameterValue::Text("8".into())),
+            ("name", ParameterValue::Text("gcc".into())),
+        ]
+    }
+
+    fn replace(text: &str) -> String {
+        replace_placeholders(text, &parameters())
+    }
+
+    #[test]
+    fn test_plain_placeholders() {
+        assert_eq!(replace("echo {n} {t} {name}"), "echo 7 0.25 gcc");
+        assert_eq!(replace("make -j {num-threads}"), "make -j 8");
+        assert_eq!(replace("{unknown} {n"), "{unknown} {n");
+        assert_eq!(replace("{{n}}"), "{7}");
+    }
+
+    #[test]
+    fn test_expressions() {
+        assert_eq!(replace("{n*2}"), "14");
+        assert_eq!(replace("{size/1024}"), "4");
+        assert_eq!(replace("{size / 1000}"), "4.096");
+        assert_eq!(replace("{(n + 1) * -t}"), "-2");
+        assert_eq!(replace("{n % 4}"), "3");
+        assert_eq!(replace("{t*4}"), "1");
+
+        // Braces that are not a valid expression are left untouched
+        assert_eq!(replace("{n+}"), "{n+}");
+        assert_eq!(replace("{n m}"), "{n m}");
+        assert_eq!(replace("{unknown*2}"), "{unknown*2}");
+    }
+
+    #[test]
+    fn test_invalid_placeholders() {
+        let check = |text| check_placeholders(text, &parameters()).map_err(|e| format!("{e:#}"));
+
+        assert!(check("echo {n*2:04} ${HOME} {n+} {unknown*2}").is_ok());
+        assert_eq!(
+            check("echo {n/0}").unwrap_err(),
+            "Invalid placeholder '{n/0}' in 'echo {n/0}': \
+             Invalid arithmetic operation on '7' and '0'"
+        );
+        assert_eq!(
+            check("{name*2}").unwrap_err(),
+            "Invalid placeholder '{name*2}' in '{name*2}': Expected a number, got 'gcc'"
+        );
+        assert_eq!(
+            check("{n*unknown}").unwrap_err(),
+            "Invalid placeholder '{n*unknown}' in '{n*unknown}': Unknown parameter 'unknown'"
+        );
+        assert_eq!(
+            check("{n:x}").unwrap_err(),
+            "Invalid placeholder '{n:x}' in '{n:x}': Invalid format specification 'x'"
+        );
+        assert_eq!(
+            check("{name:4}").unwrap_err(),
+            "Invalid placeholder '{name:4}' in '{name:4}': Expected a number, got 'gcc'"
+        );
+
+        // The invalid placeholders are left untouched by the (lenient) replacement
+        assert_eq!(replace("{n/0} {n}"), "{n/0} 7");
+    }
+
+    #[test]
+    fn test_format_specs() {
+        assert_eq!(replace("{n:04}"), "0007");
+        assert_eq!(replace("{n:4}"), "   7");
+        assert_eq!(replace("{t:.1}"), "0.3");
+        assert_eq!(replace("{n/3:.3}"), "2.333");
+        assert_eq!(replace("{n*-1:04}"), "-007");
+        assert_eq!(replace("{n:06.2}"), "007.00");
+    }
+
+    #[test]
+    fn test_shell_syntax_is_preserved() {
+        assert_eq!(replace("echo ${HOME}"), "echo ${HOME}");
+        assert_eq!(replace("awk '{print $1}'"), "awk '{print $1}'");
+        assert_eq!(replace("echo {1..3}"), "echo {1..3}");
+        assert_eq!(replace("f() { sleep 1; }"), "f() { sleep 1; }");
+        assert_eq!(replace("echo {2*3}"), "echo {2*3}");
+    }
+
+    #[test]
+    fn test_uses_parameter() {
+        assert!(uses_parameter("echo {n}", "n"));
+        assert!(uses_parameter("echo {n*2}", "n"));
+        assert!(uses_parameter("echo {n:04}", "n"));
+        assert!(uses_parameter("make -j {num-threads}", "num-threads"));
+        assert!(!uses_parameter("echo {nn}", "n"));
+        assert!(!uses_parameter("echo n", "n"));
+    }
+
+    #[test]
+    fn test_referenced_parameters() {
+        let parameters = ["n", "num-threads"];
+        assert_eq!(
+            referenced_parameters("run {n} -j {num-threads} {thraeds:02}", &parameters),
+            vec!["n", "num-threads", "thraeds"]
+        );
+        assert_eq!(
+            referenced_parameters("echo {n*size} {1..3} ${HOME}", &parameters),
+            vec!["n", "size"]
+        );
+        assert!(referenced_parameters("awk '{print $1}'", &parameters).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use std::convert::TryInto;
-use std::ops::{Add, AddAssign, Div, Sub};
+use std::ops::{Add, AddAssign, Div, Mul, Sub};
 
 use crate::error::ParameterScanError;
 use crate::util::number::Number;
@@ -7,6 +7,7 @@ use crate::util::number::Number;
 pub trait Numeric:
     Add<Output = Self>
     + Sub<Output = Self>
+    + Mul<Output = Self>
     + Div<Output = Self>
     + AddAssign
     + PartialOrd
@@ -19,6 +20,7 @@ pub trait Numeric:
 impl<
         T: Add<Output = Self>
             + Sub<Output = Self>
+            + Mul<Output = Self>
             + Div<Output = Self>
             + AddAssign
             + PartialOrd
@@ -30,6 +32,9 @@ impl<
 {
 }
 
+/// Maximum number of values in a parameter range, and of combinations of parameter values
+pub const MAX_PARAMETERS: usize = 100_000;
+
 #[derive(Debug)]
 pub struct RangeStep<T> {
     state: T,
@@ -47,7 +52,6 @@ impl<T: Numeric> RangeStep<T> {
             return Err(ParameterScanError::ZeroStep);
         }
 
-        const MAX_PARAMETERS: usize = 100_000;
         match range_step_size_hint(start, end, step) {
             (_, Some(size)) if size <= MAX_PARAMETERS => Ok(Self {
                 state: start,
@@ -89,6 +93,57 @@ fn range_step_size_hint<T: Numeric>(start: T, end: T, step: T) -> (usize, Option
         .map_or((usize::MAX, None), |u| (u, Some(u)))
 }
 
+/// A range with multiplicative steps, e.g. 1, 2, 4, 8, … for a factor of 2
+#[derive(Debug, Clone)]
+pub struct GeometricRange<T> {
+    state: Option<T>,
+    end: T,
+    factor: T,
+}
+
+impl<T: Numeric> GeometricRange<T> {
+    pub fn new(start: T, end: T, factor: T) -> Result<Self, ParameterScanError> {
+        if start <= T::from(0) {
+            return Err(ParameterScanError::NonPositiveStart);
+        }
+
+        if end < start {
+            return Err(ParameterScanError::EmptyRange);
+        }
+
+        if factor <= T::from(1) {
+            return Err(ParameterScanError::InvalidFactor);
+        }
+
+        let range = Self {
+            state: Some(start),
+            end,
+            factor,
+        };
+        if range.clone().nth(MAX_PARAMETERS).is_some() {
+            return Err(ParameterScanError::TooLarge);
+        }
+
+        Ok(range)
+    }
+}
+
+impl<T: Numeric> Iterator for GeometricRange<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.state?;
+        // Compare with end / factor instead of multiplying first, which could overflow
+        self.state = if value <= self.end / self.factor {
+            Some(value * self.factor)
+        } else {
+            None
+        };
+
+        Some(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,6 +175,59 @@ mod tests {
         assert_eq!(param_range[10], Decimal::from(1));
     }
 
+    #[test]
+    fn test_geometric_range() {
+        let param_range: Vec<i32> = GeometricRange::new(1024, 1 << 30, 2).unwrap().collect();
+        assert_eq!(param_range.len(), 21);
+        assert_eq!(param_range[1], 2048);
+        assert_eq!(param_range[20], 1 << 30);
+
+        let param_range: Vec<i32> = GeometricRange::new(1, 100, 10).unwrap().collect();
+        assert_eq!(param_range, vec![1, 10, 100]);
+
+        // The last value does not overflow
+        let param_range: Vec<i32> = GeometricRange::new(1, i32::MAX, 2).unwrap().collect();
+        assert_eq!(param_range.last(), Some(&(1 << 30)));
+
+        let param_range: Vec<Decimal> = GeometricRange::new(
+            Decimal::from(1),
+            Decimal::from(3),
+            Decimal::from_str("1.5").unwrap(),
+        )
+        .unwrap()
+        .collect();
+        assert_eq!(param_range.len(), 3);
+        assert_eq!(param_range[2], Decimal::from_str("2.25").unwrap());
+    }
+
+    #[test]
+    fn test_geometric_range_validate() {
+        let result = GeometricRange::new(0, 10, 2);
+        assert_eq!(
+            format!("{}", result.unwrap_err()),
+            "The start of a geometric parameter range has to be positive"
+        );
+
+        let result = GeometricRange::new(11, 10, 2);
+        assert_eq!(format!("{}", result.unwrap_err()), "Empty parameter range");
+
+        let result = GeometricRange::new(1, 10, 1);
+        assert_eq!(
+            format!("{}", result.unwrap_err()),
+            "The factor of a geometric parameter range has to be larger than 1"
+        );
+
+        let result = GeometricRange::new(
+            Decimal::from(1),
+            Decimal::from(1_000_000),
+            Decimal::from_str("1.0001").unwrap(),
+        );
+        assert_eq!(
+            format!("{}", result.unwrap_err()),
+            "Parameter range is too large"
+        );
+    }
+
     #[test]
     fn test_range_step_validate() {
         let result = RangeStep::new(0, 10, 3);
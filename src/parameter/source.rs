@@ -0,0 +1,79 @@
+//! Parameter values that are read from a file (`--parameter-list-file`) or from the output of a
+//! command (`--parameter-list-cmd`), one value per line
+
+use std::fs;
+use std::process::{self, Stdio};
+
+use crate::command::Command;
+use crate::options::{ExecutorKind, Shell};
+
+use anyhow::{bail, Context, Result};
+
+/// Reads the parameter values from a file or command
+pub type Reader<'a> = &'a dyn Fn(&str) -> Result<Vec<String>>;
+
+/// Read the parameter values from the lines of a file
+pub fn values_from_file(path: &str) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Could not read the parameter values from '{path}'"))?;
+
+    let values = lines(&content);
+    if values.is_empty() {
+        bail!("The file '{path}' for '--parameter-list-file' does not contain any values");
+    }
+    Ok(values)
+}
+
+/// Run a command like the benchmarked commands, i.e. with the shell from '--shell' or without
+/// a shell for '-N', and use the lines of its output as parameter values
+pub fn values_from_command(
+    command_line: &str,
+    executor_kind: &ExecutorKind,
+) -> Result<Vec<String>> {
+    let mut command = match executor_kind {
+        ExecutorKind::Raw => Command::new(None, command_line).get_command()?,
+        ExecutorKind::Shell(shell) => shell_command(shell, command_line),
+        // The benchmarks are not executed in debug mode, but the values are needed nevertheless
+        ExecutorKind::Mock(_) => shell_command(&Shell::default(), command_line),
+    };
+
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("Failed to run '{command_line}' for '--parameter-list-cmd'"))?;
+    if !output.status.success() {
+        bail!(
+            "The command '{command_line}' for '--parameter-list-cmd' failed ({})",
+            output.status
+        );
+    }
+
+    let values = lines(&String::from_utf8_lossy(&output.stdout));
+    if values.is_empty() {
+        bail!("The command '{command_line}' for '--parameter-list-cmd' did not output any values");
+    }
+    Ok(values)
+}
+
+fn shell_command(shell: &Shell, command_line: &str) -> process::Command {
+    let on_windows_cmd = cfg!(windows) && *shell == Shell::Default("cmd.exe");
+    let mut command = shell.command();
+    command.arg(if on_windows_cmd { "/C" } else { "-c" });
+    command.arg(command_line);
+    command
+}
+
+/// Non-empty lines, without line endings
+fn lines(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[test]
+fn test_lines() {
+    assert_eq!(lines("a\nb c\r\n\nd\n"), vec!["a", "b c", "d"]);
+    assert!(lines("\n\n").is_empty());
+}
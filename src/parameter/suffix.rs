@@ -0,0 +1,38 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+/// Replaces a binary size suffix (K, M or G, for multiples of 1024) in a numeric parameter
+/// value by the plain number, e.g. "4K" by "4096". Other values are returned unchanged.
+pub fn expand_size_suffix(value: &str) -> String {
+    let exponent = match value.chars().last() {
+        Some('K') | Some('k') => 1,
+        Some('M') | Some('m') => 2,
+        Some('G') | Some('g') => 3,
+        _ => return value.to_string(),
+    };
+
+    let number = &value[..value.len() - 1];
+    match Decimal::from_str(number) {
+        Ok(number) => {
+            let multiplier = Decimal::from(1024i64.pow(exponent));
+            number
+                .checked_mul(multiplier)
+                .map_or_else(|| value.to_string(), |n| n.normalize().to_string())
+        }
+        Err(_) => value.to_string(),
+    }
+}
+
+#[test]
+fn test_expand_size_suffix() {
+    assert_eq!(expand_size_suffix("1K"), "1024");
+    assert_eq!(expand_size_suffix("4k"), "4096");
+    assert_eq!(expand_size_suffix("1.5K"), "1536");
+    assert_eq!(expand_size_suffix("2M"), "2097152");
+    assert_eq!(expand_size_suffix("1G"), "1073741824");
+    assert_eq!(expand_size_suffix("0.5"), "0.5");
+    assert_eq!(expand_size_suffix("-3"), "-3");
+    assert_eq!(expand_size_suffix("K"), "K");
+    assert_eq!(expand_size_suffix("xK"), "xK");
+}
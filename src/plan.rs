@@ -0,0 +1,389 @@
+//! The benchmark plan that is printed by `--dry-run`: every benchmark with its expanded
+//! commands, a few sanity checks and an estimate of the total run time.
+
+use std::cmp;
+use std::collections::BTreeMap;
+
+use colored::*;
+
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::command::{Command, Commands};
+use crate::export::read_json_results;
+use crate::options::{value_for_command, ExecutorKind, Options};
+use crate::output::format::{format_long_duration, format_percentage};
+use crate::parameter::placeholder::referenced_parameters;
+use crate::util::units::Second;
+
+use anyhow::Result;
+
+pub struct Plan<'a> {
+    commands: &'a Commands<'a>,
+    options: &'a Options,
+
+    /// All benchmarks in the order in which they would be run, including the reference
+    benchmarks: Vec<Command<'a>>,
+}
+
+impl<'a> Plan<'a> {
+    pub fn new(commands: &'a Commands<'a>, options: &'a Options) -> Self {
+        let reference = options
+            .reference_command
+            .as_deref()
+            .map(|cmd| Command::new(None, cmd));
+        Plan {
+            commands,
+            options,
+            benchmarks: reference
+                .into_iter()
+                .chain(commands.iter().cloned())
+                .enumerate()
+                .map(|(number, command)| {
+                    command.with_environment(
+                        value_for_command(&options.environment, number),
+                        value_for_command(&options.working_directory, number),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Print the plan, followed by the warnings from `lint`
+    pub fn print(&self) -> Result<()> {
+        println!(
+            "{} {} benchmark(s), nothing is executed.",
+            "Dry run:".bold(),
+            self.benchmarks.len()
+        );
+        println!("  Shell: {}", self.shell());
+        println!("  Runs:  {}", self.runs());
+        if self.options.clear_environment {
+            println!("  Env:   cleared, only the variables from '--env' are set");
+        }
+        println!();
+
+        for (number, command) in self.benchmarks.iter().enumerate() {
+            println!(
+                "{}{}: {}",
+                "Benchmark ".bold(),
+                (number + 1).to_string().bold(),
+                command.get_name_with_unused_parameters()
+            );
+            if !command.get_parameters().is_empty() {
+                let parameters: Vec<_> = command
+                    .get_parameters()
+                    .iter()
+                    .map(|(name, value)| format!("{name} = {value}"))
+                    .collect();
+                println!("  Parameters: {}", parameters.join(", "));
+            }
+            println!("  Command:    {}", command.get_command_line());
+            let environment = command.get_environment()?;
+            if !environment.is_empty() {
+                let environment: Vec<_> = environment
+                    .iter()
+                    .map(|(key, value)| format!("{key}={}", shell_words::quote(value)))
+                    .collect();
+                println!("  Env:        {}", environment.join(" "));
+            }
+            if let Some(directory) = command.get_working_directory() {
+                println!("  Directory:  {directory}");
+            }
+            for (label, intermediate) in self.intermediate_commands(number, command) {
+                println!("  {:<11} {}", label, intermediate.get_command_line());
+            }
+            println!();
+        }
+
+        // Previous results are used like in a real run: `--resume` takes precedence
+        let previous_file = self
+            .options
+            .resume_file
+            .as_deref()
+            .or(self.options.baseline_file.as_deref());
+        let previous_results = previous_file.map(read_json_results).transpose()?;
+        println!(
+            "{}",
+            self.describe_estimate(previous_file, previous_results)
+        );
+
+        for warning in self.lint() {
+            eprintln!("{}: {}", "Warning".yellow(), warning);
+        }
+
+        Ok(())
+    }
+
+    /// Check the plan for likely mistakes: placeholders that do not match any parameter,
+    /// parameters that are not used anywhere and benchmarks with the same name
+    pub fn lint(&self) -> Vec<String> {
+        let mut warnings = vec![];
+
+        let names: Vec<&str> = self.commands.iter().next().map_or(vec![], |command| {
+            command
+                .get_parameters()
+                .iter()
+                .map(|(name, _)| *name)
+                .collect()
+        });
+
+        let templates: Vec<&str> = self
+            .commands
+            .iter()
+            .flat_map(|command| command.get_templates())
+            .chain(self.options.setup_command.as_deref())
+            .chain(
+                [
+                    &self.options.preparation_command,
+                    &self.options.conclusion_command,
+                    &self.options.environment,
+                    &self.options.working_directory,
+                ]
+                .iter()
+                .flat_map(|values| values.iter().flatten())
+                .map(|value| value.as_str()),
+            )
+            .chain(self.options.cleanup_command.as_deref())
+            .collect();
+
+        let mut used = vec![];
+        for template in templates {
+            for name in referenced_parameters(template, &names) {
+                if !names.contains(&name.as_str()) {
+                    let warning = format!("Unknown parameter '{name}' in '{template}'");
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+                used.push(name);
+            }
+        }
+        for name in names {
+            if !used.iter().any(|used| used == name) {
+                warnings.push(format!("The parameter '{name}' is not used in any command"));
+            }
+        }
+
+        let mut counts = BTreeMap::<String, usize>::new();
+        for command in &self.benchmarks {
+            *counts
+                .entry(command.get_name_with_unused_parameters())
+                .or_default() += 1;
+        }
+        for (name, count) in counts {
+            if count > 1 {
+                warnings.push(format!("{count} benchmarks have the same name '{name}'"));
+            }
+        }
+
+        warnings
+    }
+
+    fn shell(&self) -> String {
+        match &self.options.executor_kind {
+            ExecutorKind::Shell(shell) => shell.to_string(),
+            ExecutorKind::Raw => "none (commands are executed directly)".into(),
+            ExecutorKind::Mock(_) => "none (debug mode, commands are not executed)".into(),
+        }
+    }
+
+    fn runs(&self) -> String {
+        let bounds = &self.options.run_bounds;
+        let mut runs = if let Some(target) = self.options.target_precision {
+            format!(
+                "until the target precision of ±{} is reached",
+                format_percentage(target.relative_half_width)
+            )
+        } else if bounds.max == Some(bounds.min) {
+            format!("{} per benchmark", bounds.min)
+        } else {
+            format!(
+                "at least {} per benchmark, and enough to take at least {} s",
+                bounds.min, self.options.min_benchmarking_time
+            )
+        };
+        if let Some(max) = bounds.max.filter(|max| *max != bounds.min) {
+            runs += &format!(", at most {max}");
+        }
+        if self.options.warmup_count > 0 {
+            runs += &format!(" (plus {} warmup runs)", self.options.warmup_count);
+        }
+        runs
+    }
+
+    /// The setup, prepare, conclude and cleanup commands of the given benchmark
+    fn intermediate_commands(
+        &self,
+        number: usize,
+        command: &Command<'a>,
+    ) -> Vec<(&'static str, Command<'a>)> {
+        let environment = value_for_command(&self.options.environment, number);
+        let working_directory = value_for_command(&self.options.working_directory, number);
+        let parametrized = |value: &'a str| {
+            Command::new_parametrized(None, value, command.get_parameters().to_vec())
+                .with_environment(environment, working_directory)
+        };
+        let select = |values: &'a Option<Vec<String>>| value_for_command(values, number);
+
+        let options = self.options;
+        vec![
+            ("Setup:", options.setup_command.as_deref().map(parametrized)),
+            (
+                "Prepare:",
+                select(&options.preparation_command).map(parametrized),
+            ),
+            (
+                "Conclude:",
+                select(&options.conclusion_command).map(parametrized),
+            ),
+            (
+                "Cleanup:",
+                options.cleanup_command.as_deref().map(parametrized),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(label, command)| command.map(|command| (label, command)))
+        .collect()
+    }
+
+    /// Expected number of timing runs for a command with the given mean run time
+    fn expected_runs(&self, mean: Second) -> u64 {
+        let bounds = &self.options.run_bounds;
+        let runs = match self.options.target_precision {
+            Some(target) => cmp::max((target.time_budget / mean) as u64, cmp::max(bounds.min, 2)),
+            None => cmp::max(
+                (self.options.min_benchmarking_time / mean) as u64,
+                bounds.min,
+            ),
+        };
+        bounds.max.map_or(runs, |max| cmp::min(runs, max))
+    }
+
+    /// Lower bound for the duration of a benchmark with unknown run time
+    fn min_duration(&self) -> Second {
+        if self.options.target_precision.is_none() && self.options.run_bounds.max.is_none() {
+            self.options.min_benchmarking_time
+        } else {
+            0.0
+        }
+    }
+
+    /// Estimate the total run time from the mean run times of previous results. Returns the
+    /// estimate and the number of benchmarks without a previous result, which are counted
+    /// with their minimum duration.
+    fn estimate_duration(&self, previous_results: &[BenchmarkResult]) -> (Second, usize) {
+        let mut duration = 0.0;
+        let mut missing = 0;
+        for command in &self.benchmarks {
+            let name = command.get_name();
+            let parameters = command.get_parameters();
+            let previous = previous_results.iter().find(|result| {
+                result.command == name
+                    && result.parameters.len() == parameters.len()
+                    && parameters.iter().all(|(parameter, value)| {
+                        result.parameters.get(*parameter) == Some(&value.to_string())
+                    })
+            });
+            match previous {
+                Some(result) if result.mean > 0.0 => {
+                    let runs = self.expected_runs(result.mean) + self.options.warmup_count;
+                    duration += result.mean * runs as Second;
+                }
+                _ => {
+                    duration += self.min_duration();
+                    missing += 1;
+                }
+            }
+        }
+        (duration, missing)
+    }
+
+    fn describe_estimate(
+        &self,
+        previous_file: Option<&str>,
+        previous_results: Option<Vec<BenchmarkResult>>,
+    ) -> String {
+        let label = "Estimated duration:".bold();
+        let (duration, missing) = self.estimate_duration(&previous_results.unwrap_or_default());
+        let duration = format_long_duration(duration);
+
+        match previous_file {
+            Some(file) if missing == 0 => {
+                format!("{label} {duration} (based on the results in '{file}')")
+            }
+            Some(file) => format!(
+                "{label} at least {duration} ({missing} of {} benchmarks are not in '{file}')",
+                self.benchmarks.len()
+            ),
+            None if self.min_duration() > 0.0 => format!("{label} at least {duration}"),
+            None => format!(
+                "{label} unknown (use '--baseline' with the JSON export of a previous run for \
+                 an estimate)"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+fn lint(args: Vec<&str>) -> Vec<String> {
+    use crate::cli::get_cli_arguments;
+
+    let matches = get_cli_arguments(vec!["hyperfine"].into_iter().chain(args));
+    let options = Options::from_cli_arguments(&matches).unwrap();
+    let commands = Commands::from_cli_arguments(&matches).unwrap();
+    Plan::new(&commands, &options).lint()
+}
+
+#[test]
+fn test_lint() {
+    assert!(lint(vec!["-P", "n", "1", "2", "--prepare", "make {n}", "run"]).is_empty());
+    assert!(lint(vec!["-P", "n", "1", "2", "--cwd", "build-{n}", "run"]).is_empty());
+
+    assert_eq!(
+        lint(vec![
+            "-L",
+            "threads",
+            "1,2",
+            "-L",
+            "unused",
+            "a,b",
+            "run -t {thraeds} ${HOME}",
+        ]),
+        vec![
+            "Unknown parameter 'thraeds' in 'run -t {thraeds} ${HOME}'",
+            "The parameter 'threads' is not used in any command",
+            "The parameter 'unused' is not used in any command",
+        ]
+    );
+
+    assert_eq!(
+        lint(vec![
+            "-L",
+            "threads",
+            "1,2",
+            "-n",
+            "run",
+            "run -t {threads}"
+        ]),
+        vec!["2 benchmarks have the same name 'run'"]
+    );
+}
+
+#[test]
+fn test_estimate_duration() {
+    use crate::cli::get_cli_arguments;
+
+    let matches = get_cli_arguments(vec!["hyperfine", "--warmup=2", "a", "b"]);
+    let options = Options::from_cli_arguments(&matches).unwrap();
+    let commands = Commands::from_cli_arguments(&matches).unwrap();
+    let plan = Plan::new(&commands, &options);
+
+    let previous = BenchmarkResult {
+        command: "a".into(),
+        mean: 1.0,
+        ..Default::default()
+    };
+
+    // 'a': 2 warmup runs and 10 timing runs of 1 s, 'b': at least 3 s
+    assert_eq!(plan.estimate_duration(&[previous]), (15.0, 1));
+    assert_eq!(plan.estimate_duration(&[]), (6.0, 2));
+}
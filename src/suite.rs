@@ -0,0 +1,524 @@
+//! Declarative benchmark suites (see `--suite`). A suite is a TOML file with a list of
+//! `[[benchmark]]` groups. Each group is translated into the command line options that would
+//! be needed to run it on its own, such that it can be handled exactly like a regular
+//! invocation of hyperfine.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use serde::Deserialize;
+
+use anyhow::{bail, Context, Result};
+
+/// Command line options that write an export file. These can not be used with suites that
+/// contain more than one group, as the groups would overwrite each other's files.
+const EXPORT_OPTIONS: [&str; 11] = [
+    "export-asciidoc",
+    "export-csv",
+    "export-csv-long",
+    "export-events",
+    "export-html",
+    "export-json",
+    "export-junit",
+    "export-markdown",
+    "export-orgmode",
+    "export-prometheus",
+    "export-svg",
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    #[serde(rename = "benchmark", default)]
+    pub groups: Vec<BenchmarkGroup>,
+}
+
+/// A group of commands that are benchmarked and exported together. Apart from the name, all
+/// fields correspond to a command line option.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BenchmarkGroup {
+    pub name: String,
+    commands: Vec<String>,
+    #[serde(default)]
+    command_names: Vec<String>,
+
+    /// Parameter lists, with the parameter name as key
+    #[serde(default)]
+    parameters: BTreeMap<String, Vec<Value>>,
+    /// Files with parameter values, with the parameter name as key
+    #[serde(default)]
+    parameter_list_file: BTreeMap<String, String>,
+    /// Commands that output parameter values, with the parameter name as key
+    #[serde(default)]
+    parameter_list_cmd: BTreeMap<String, String>,
+    /// Names of parameter lists that are zipped, like for `--parameter-zip`
+    #[serde(default)]
+    parameter_zip: Vec<Vec<String>>,
+    /// Conditions for the parameter combinations, like for `--parameter-filter`
+    #[serde(default)]
+    parameter_filter: Vec<String>,
+    parameter_scan: Option<OneOrMany<ParameterScan>>,
+
+    setup: Option<String>,
+    prepare: Option<OneOrMany<String>>,
+    conclude: Option<OneOrMany<String>>,
+    cleanup: Option<String>,
+
+    shell: Option<String>,
+    runs: Option<u64>,
+    min_runs: Option<u64>,
+    max_runs: Option<u64>,
+    warmup: Option<u64>,
+    input: Option<String>,
+    output: Option<String>,
+    #[serde(default)]
+    ignore_failure: bool,
+
+    #[serde(default)]
+    export: Exports,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ParameterScan {
+    name: String,
+    min: Value,
+    max: Value,
+    step: Option<Value>,
+    /// Makes this a geometric scan, like `--parameter-scan-geometric`
+    factor: Option<Value>,
+}
+
+/// Export files of a group, by format
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Exports {
+    asciidoc: Option<String>,
+    csv: Option<String>,
+    csv_long: Option<String>,
+    events: Option<String>,
+    html: Option<String>,
+    json: Option<String>,
+    junit: Option<String>,
+    markdown: Option<String>,
+    orgmode: Option<String>,
+    prometheus: Option<String>,
+    /// Charts in the form `KIND=FILE`, like for `--export-svg`
+    #[serde(default)]
+    svg: Vec<String>,
+}
+
+/// A parameter value, which can be written as a number or as a string
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(i) => write!(f, "{i}"),
+            Value::Float(x) => write!(f, "{x}"),
+            Value::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Options like `prepare` that can be given once or multiple times
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn values(&self) -> &[T] {
+        match self {
+            OneOrMany::One(value) => std::slice::from_ref(value),
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+impl Suite {
+    /// Read a suite from a TOML file and check that it can be combined with the options on
+    /// the command line
+    pub fn load(filename: &str, cli_arguments: &ArgMatches) -> Result<Suite> {
+        let content = fs::read_to_string(filename)
+            .with_context(|| format!("Could not read the benchmark suite '{filename}'"))?;
+        let suite = Suite::parse(&content)
+            .with_context(|| format!("Could not parse the benchmark suite '{filename}'"))?;
+
+        if cli_arguments.contains_id("command") {
+            bail!("Commands can not be passed on the command line when using --suite");
+        }
+        if suite.groups.len() > 1 {
+            if let Some(option) = EXPORT_OPTIONS
+                .iter()
+                .find(|id| is_given_on_command_line(cli_arguments, id))
+            {
+                bail!(
+                    "The option --{option} can not be used with a suite that contains more \
+                     than one benchmark group. Use the 'export' table of each group instead."
+                );
+            }
+        }
+
+        Ok(suite)
+    }
+
+    fn parse(content: &str) -> Result<Suite> {
+        let suite: Suite = toml::from_str(content)?;
+
+        if suite.groups.is_empty() {
+            bail!("The suite does not define any [[benchmark]] groups");
+        }
+        for group in &suite.groups {
+            if group.commands.is_empty() {
+                bail!(
+                    "The benchmark group '{}' does not define any commands",
+                    group.name
+                );
+            }
+            let scans = group
+                .parameter_scan
+                .as_ref()
+                .map_or(&[][..], |s| s.values());
+            if let Some(scan) = scans
+                .iter()
+                .find(|scan| scan.step.is_some() && scan.factor.is_some())
+            {
+                bail!(
+                    "The parameter scan '{}' can not have both a 'step' and a 'factor'",
+                    scan.name
+                );
+            }
+        }
+
+        Ok(suite)
+    }
+}
+
+impl BenchmarkGroup {
+    /// Command line arguments for this group, which are appended to the original command line.
+    /// Settings are skipped if the original command line already contains the corresponding
+    /// (or a conflicting) option, such that the command line takes precedence.
+    pub fn arguments(&self, cli_arguments: &ArgMatches) -> Vec<String> {
+        let mut arguments = vec![];
+        let mut add = |overridden_by: &[&str], values: Vec<String>| {
+            if !overridden_by
+                .iter()
+                .any(|id| is_given_on_command_line(cli_arguments, id))
+            {
+                arguments.extend(values);
+            }
+        };
+
+        if let Some(warmup) = self.warmup {
+            add(&["warmup"], vec![format!("--warmup={warmup}")]);
+        }
+        if let Some(runs) = self.runs {
+            add(
+                &["runs", "min-runs", "max-runs", "target-precision"],
+                vec![format!("--runs={runs}")],
+            );
+        }
+        if let Some(min_runs) = self.min_runs {
+            add(
+                &["runs", "min-runs"],
+                vec![format!("--min-runs={min_runs}")],
+            );
+        }
+        if let Some(max_runs) = self.max_runs {
+            add(
+                &["runs", "max-runs"],
+                vec![format!("--max-runs={max_runs}")],
+            );
+        }
+
+        if let Some(setup) = &self.setup {
+            add(&["setup"], vec![format!("--setup={setup}")]);
+        }
+        if let Some(prepare) = &self.prepare {
+            add(
+                &["prepare"],
+                prepare
+                    .values()
+                    .iter()
+                    .map(|p| format!("--prepare={p}"))
+                    .collect(),
+            );
+        }
+        if let Some(conclude) = &self.conclude {
+            add(
+                &["conclude"],
+                conclude
+                    .values()
+                    .iter()
+                    .map(|c| format!("--conclude={c}"))
+                    .collect(),
+            );
+        }
+        if let Some(cleanup) = &self.cleanup {
+            add(&["cleanup"], vec![format!("--cleanup={cleanup}")]);
+        }
+
+        let parameter_options = [
+            "parameter-scan",
+            "parameter-scan-geometric",
+            "parameter-list",
+            "parameter-list-file",
+            "parameter-list-cmd",
+        ];
+        if let Some(scans) = &self.parameter_scan {
+            let (geometric, arithmetic): (Vec<_>, Vec<_>) = scans
+                .values()
+                .iter()
+                .partition(|scan| scan.factor.is_some());
+            // The step sizes are matched with the scans by position, so either all
+            // or none of them need one
+            let has_step_size = arithmetic.iter().any(|scan| scan.step.is_some());
+            let mut values = vec![];
+            for scan in arithmetic {
+                values.extend(vec![
+                    "--parameter-scan".to_string(),
+                    scan.name.clone(),
+                    scan.min.to_string(),
+                    scan.max.to_string(),
+                ]);
+                if has_step_size {
+                    let step = scan.step.as_ref().map_or("1".into(), |s| s.to_string());
+                    values.push(format!("--parameter-step-size={step}"));
+                }
+            }
+            for scan in geometric {
+                values.extend(vec![
+                    "--parameter-scan-geometric".to_string(),
+                    scan.name.clone(),
+                    scan.min.to_string(),
+                    scan.max.to_string(),
+                ]);
+                if let Some(factor) = &scan.factor {
+                    values.push(format!("--factor={factor}"));
+                }
+            }
+            add(&parameter_options, values);
+        }
+        for (name, values) in &self.parameters {
+            let values: Vec<String> = values
+                .iter()
+                .map(|v| v.to_string().replace('\\', "\\\\").replace(',', "\\,"))
+                .collect();
+            add(
+                &parameter_options,
+                vec!["--parameter-list".into(), name.clone(), values.join(",")],
+            );
+        }
+
+        for (option, sources) in [
+            ("--parameter-list-file", &self.parameter_list_file),
+            ("--parameter-list-cmd", &self.parameter_list_cmd),
+        ] {
+            for (name, source) in sources {
+                add(
+                    &parameter_options,
+                    vec![option.into(), name.clone(), source.clone()],
+                );
+            }
+        }
+
+        add(
+            &parameter_options,
+            self.parameter_zip
+                .iter()
+                .map(|names| format!("--parameter-zip={}", names.join(",")))
+                .collect(),
+        );
+        add(
+            &parameter_options,
+            self.parameter_filter
+                .iter()
+                .map(|filter| format!("--parameter-filter={filter}"))
+                .collect(),
+        );
+
+        if let Some(shell) = &self.shell {
+            add(&["shell", "no-shell"], vec![format!("--shell={shell}")]);
+        }
+        if self.ignore_failure {
+            add(&[], vec!["--ignore-failure".into()]);
+        }
+        if let Some(input) = &self.input {
+            add(&["input"], vec![format!("--input={input}")]);
+        }
+        if let Some(output) = &self.output {
+            add(
+                &["output", "show-output"],
+                vec![format!("--output={output}")],
+            );
+        }
+
+        let exports = [
+            ("export-asciidoc", &self.export.asciidoc),
+            ("export-csv", &self.export.csv),
+            ("export-csv-long", &self.export.csv_long),
+            ("export-events", &self.export.events),
+            ("export-html", &self.export.html),
+            ("export-json", &self.export.json),
+            ("export-junit", &self.export.junit),
+            ("export-markdown", &self.export.markdown),
+            ("export-orgmode", &self.export.orgmode),
+            ("export-prometheus", &self.export.prometheus),
+        ];
+        for (option, filename) in exports {
+            if let Some(filename) = filename {
+                add(&[option], vec![format!("--{option}={filename}")]);
+            }
+        }
+        if !self.export.svg.is_empty() {
+            add(
+                &["export-svg"],
+                self.export
+                    .svg
+                    .iter()
+                    .map(|chart| format!("--export-svg={chart}"))
+                    .collect(),
+            );
+        }
+
+        add(
+            &["command-name"],
+            self.command_names
+                .iter()
+                .map(|name| format!("--command-name={name}"))
+                .collect(),
+        );
+
+        arguments.push("--".into());
+        arguments.extend(self.commands.iter().cloned());
+
+        arguments
+    }
+}
+
+fn is_given_on_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+#[test]
+fn test_parse_suite() {
+    let suite = Suite::parse(
+        r#"
+        [[benchmark]]
+        name = "sleep"
+        commands = ["sleep {t}"]
+        parameters = { t = [0.1, "0.2"] }
+        prepare = "echo prepare"
+        runs = 3
+
+        [[benchmark]]
+        name = "scan"
+        commands = ["sleep 0.{n}", "sleep 0.{n}1"]
+        command_names = ["a", "b"]
+        parameter_scan = { name = "n", min = 1, max = 3 }
+        export = { json = "scan.json", svg = ["whisker=scan.svg"] }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(suite.groups.len(), 2);
+    assert_eq!(suite.groups[0].name, "sleep");
+    assert_eq!(suite.groups[1].commands.len(), 2);
+
+    let unknown_field = Suite::parse(
+        r#"
+        [[benchmark]]
+        name = "sleep"
+        commands = ["sleep 0.1"]
+        warmups = 3
+        "#,
+    );
+    assert!(format!("{:#}", unknown_field.unwrap_err()).contains("unknown field `warmups`"));
+
+    let no_commands = Suite::parse(
+        r#"
+        [[benchmark]]
+        name = "empty"
+        commands = []
+        "#,
+    );
+    assert_eq!(
+        no_commands.unwrap_err().to_string(),
+        "The benchmark group 'empty' does not define any commands"
+    );
+
+    assert!(Suite::parse("").is_err());
+}
+
+#[test]
+fn test_group_arguments() {
+    use crate::cli::get_cli_arguments;
+
+    let suite = Suite::parse(
+        r#"
+        [[benchmark]]
+        name = "sleep"
+        commands = ["sleep {t}"]
+        parameters = { t = [0.1, "a,b"] }
+        prepare = ["echo 1", "echo 2"]
+        runs = 3
+        warmup = 1
+        shell = "bash"
+        export = { json = "sleep.json" }
+        "#,
+    )
+    .unwrap();
+    let group = &suite.groups[0];
+
+    let cli_arguments = get_cli_arguments(vec!["hyperfine", "--suite=bench.toml"]);
+    assert_eq!(
+        group.arguments(&cli_arguments),
+        vec![
+            "--warmup=1",
+            "--runs=3",
+            "--prepare=echo 1",
+            "--prepare=echo 2",
+            "--parameter-list",
+            "t",
+            "0.1,a\\,b",
+            "--shell=bash",
+            "--export-json=sleep.json",
+            "--",
+            "sleep {t}",
+        ]
+    );
+
+    // Options on the command line take precedence
+    let cli_arguments = get_cli_arguments(vec![
+        "hyperfine",
+        "--suite=bench.toml",
+        "--min-runs=5",
+        "-N",
+        "--prepare=true",
+        "--export-json=all.json",
+    ]);
+    assert_eq!(
+        group.arguments(&cli_arguments),
+        vec![
+            "--warmup=1",
+            "--parameter-list",
+            "t",
+            "0.1,a\\,b",
+            "--",
+            "sleep {t}",
+        ]
+    );
+}
@@ -1,4 +1,5 @@
 mod wall_clock_timer;
+mod watchdog;
 
 #[cfg(windows)]
 mod windows_timer;
@@ -16,8 +17,11 @@ use std::os::unix::io::AsRawFd;
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::System::Threading::CREATE_SUSPENDED;
 
+#[cfg(not(windows))]
+use crate::util::interrupt;
 use crate::util::units::Second;
 use wall_clock_timer::WallClockTimer;
+use watchdog::Watchdog;
 
 use std::io::Read;
 use std::process::{ChildStdout, Command, ExitStatus};
@@ -34,6 +38,31 @@ struct CPUTimes {
     pub system_usec: i64,
 }
 
+/// Resource usage of a single process, as reported by the operating system
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceUsage {
+    /// Peak resident set size in bytes
+    pub max_rss_byte: u64,
+
+    /// Number of page faults serviced without any I/O activity
+    pub minor_page_faults: u64,
+
+    /// Number of page faults that required I/O activity
+    pub major_page_faults: u64,
+
+    /// Number of times the process gave up the CPU voluntarily (e.g. to wait for I/O)
+    pub voluntary_context_switches: u64,
+
+    /// Number of times the process was preempted by the scheduler
+    pub involuntary_context_switches: u64,
+
+    /// Number of block input operations
+    pub block_input_operations: u64,
+
+    /// Number of block output operations
+    pub block_output_operations: u64,
+}
+
 /// Used to indicate the result of running a command
 #[derive(Debug, Copy, Clone)]
 pub struct TimerResult {
@@ -41,6 +70,12 @@ pub struct TimerResult {
     pub time_user: Second,
     pub time_system: Second,
 
+    /// Resource usage of the process (not available on Windows)
+    pub resource_usage: ResourceUsage,
+
+    /// Whether the process has been terminated because it exceeded the timeout
+    pub timed_out: bool,
+
     /// The exit status of the process
     pub status: ExitStatus,
 }
@@ -76,8 +111,17 @@ fn discard(output: ChildStdout) {
     }
 }
 
-/// Execute the given command and return a timing summary
-pub fn execute_and_measure(mut command: Command) -> Result<TimerResult> {
+/// Execute the given command and return a timing summary. If a timeout is given, the command
+/// (including all of its child processes) is terminated once it runs longer than that.
+pub fn execute_and_measure(mut command: Command, timeout: Option<Second>) -> Result<TimerResult> {
+    #[cfg(not(windows))]
+    if timeout.is_some() {
+        use std::os::unix::process::CommandExt;
+
+        // Start a new process group, such that we can terminate the whole process tree
+        command.process_group(0);
+    }
+
     #[cfg(not(windows))]
     let cpu_timer = self::unix_timer::CPUTimer::start();
 
@@ -98,20 +142,50 @@ pub fn execute_and_measure(mut command: Command) -> Result<TimerResult> {
         unsafe { self::windows_timer::CPUTimer::start_suspended_process(&child) }
     };
 
+    #[cfg(not(windows))]
+    interrupt::set_running_process(child.id(), timeout.is_some());
+
+    #[cfg(not(windows))]
+    let watchdog = timeout.map(|timeout| {
+        Watchdog::start(timeout, self::unix_timer::process_group_terminator(&child))
+    });
+
+    #[cfg(windows)]
+    let watchdog = timeout.map(|timeout| Watchdog::start(timeout, cpu_timer.terminator()));
+
     if let Some(output) = child.stdout.take() {
         // Handle CommandOutputPolicy::Pipe
         discard(output);
     }
 
-    let status = child.wait()?;
+    #[cfg(not(windows))]
+    let (status, resource_usage, timed_out) = {
+        // Stop the watchdog and the forwarding of interrupts before the command is reaped.
+        // Until then, its process (group) ID can not be reused, such that neither the final
+        // SIGKILL nor a forwarded Ctrl-C can hit an unrelated process.
+        let exited = self::unix_timer::wait_for_exit(&child);
+        interrupt::clear_running_process();
+        let timed_out = watchdog.is_some_and(|watchdog| watchdog.stop());
+        let (status, resource_usage) =
+            exited.and_then(|()| self::unix_timer::wait_with_resource_usage(&child))?;
+        (status, resource_usage, timed_out)
+    };
+
+    #[cfg(windows)]
+    let (status, resource_usage) = (child.wait()?, ResourceUsage::default());
 
     let time_real = wallclock_timer.stop();
     let (time_user, time_system) = cpu_timer.stop();
 
+    #[cfg(windows)]
+    let timed_out = watchdog.is_some_and(|watchdog| watchdog.stop());
+
     Ok(TimerResult {
         time_real,
         time_user,
         time_system,
+        resource_usage,
+        timed_out,
         status,
     })
 }
@@ -1,8 +1,12 @@
 #![cfg(not(windows))]
 
+use std::convert::TryFrom;
+use std::io;
 use std::mem;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, ExitStatus};
 
-use crate::timer::CPUTimes;
+use crate::timer::{CPUTimes, ResourceUsage};
 use crate::util::units::Second;
 
 #[derive(Debug, Copy, Clone)]
@@ -54,6 +58,88 @@ fn get_cpu_times() -> CPUTimes {
     }
 }
 
+/// Wait for the given child process to exit and collect its resource usage. The child is reaped
+/// via `wait4`, so the returned numbers belong to this very process (and its waited-for
+/// descendants) instead of being accumulated over all children of hyperfine.
+pub fn wait_with_resource_usage(child: &Child) -> io::Result<(ExitStatus, ResourceUsage)> {
+    use libc::{pid_t, rusage, wait4};
+
+    let pid = child.id() as pid_t;
+    let mut status = 0;
+    let mut usage: rusage = unsafe { mem::zeroed() };
+
+    loop {
+        let ret = unsafe { wait4(pid, &mut status, 0, &mut usage) };
+        if ret != -1 {
+            break;
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+
+    Ok((
+        ExitStatus::from_raw(status),
+        resource_usage_from_rusage(&usage),
+    ))
+}
+
+/// Wait for the given child process to exit, without reaping it. The child stays a zombie until
+/// `wait_with_resource_usage` is called, such that its PID and process group ID can not be
+/// reused in the meantime.
+pub fn wait_for_exit(child: &Child) -> io::Result<()> {
+    use libc::{id_t, siginfo_t, waitid, P_PID, WEXITED, WNOWAIT};
+
+    let mut info: siginfo_t = unsafe { mem::zeroed() };
+
+    loop {
+        let ret = unsafe { waitid(P_PID, child.id() as id_t, &mut info, WEXITED | WNOWAIT) };
+        if ret != -1 {
+            return Ok(());
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// Returns a function that sends SIGTERM (or SIGKILL, if `force` is set) to all processes in
+/// the process group of the given child. The child needs to be the leader of its own group,
+/// and it must not be reaped while the function may still be called.
+pub fn process_group_terminator(child: &Child) -> impl Fn(bool) + Send + 'static {
+    use libc::{kill, pid_t, SIGKILL, SIGTERM};
+
+    let pgid = child.id() as pid_t;
+    move |force| {
+        let signal = if force { SIGKILL } else { SIGTERM };
+        unsafe { kill(-pgid, signal) };
+    }
+}
+
+/// Extract the interesting fields from a `rusage` struct
+#[allow(clippy::useless_conversion)]
+fn resource_usage_from_rusage(usage: &libc::rusage) -> ResourceUsage {
+    // `ru_maxrss` is given in bytes on macOS, but in kilobytes everywhere else
+    #[cfg(target_os = "macos")]
+    const MAX_RSS_UNIT: u64 = 1;
+    #[cfg(not(target_os = "macos"))]
+    const MAX_RSS_UNIT: u64 = 1024;
+
+    let to_u64 = |v: libc::c_long| u64::try_from(i64::from(v)).unwrap_or(0);
+
+    ResourceUsage {
+        max_rss_byte: to_u64(usage.ru_maxrss) * MAX_RSS_UNIT,
+        minor_page_faults: to_u64(usage.ru_minflt),
+        major_page_faults: to_u64(usage.ru_majflt),
+        voluntary_context_switches: to_u64(usage.ru_nvcsw),
+        involuntary_context_switches: to_u64(usage.ru_nivcsw),
+        block_input_operations: to_u64(usage.ru_inblock),
+        block_output_operations: to_u64(usage.ru_oublock),
+    }
+}
+
 /// Compute the time intervals in between two `CPUTimes` snapshots
 fn cpu_time_interval(start: &CPUTimes, end: &CPUTimes) -> CPUInterval {
     CPUInterval {
@@ -65,6 +151,26 @@ fn cpu_time_interval(start: &CPUTimes, end: &CPUTimes) -> CPUInterval {
 #[cfg(test)]
 use approx::assert_relative_eq;
 
+#[test]
+fn test_wait_with_resource_usage() {
+    let result =
+        crate::timer::execute_and_measure(std::process::Command::new("true"), None).unwrap();
+
+    assert!(result.status.success());
+    assert!(result.resource_usage.max_rss_byte > 0);
+}
+
+#[test]
+fn test_process_group_is_terminated_on_timeout() {
+    let mut command = std::process::Command::new("sh");
+    command.args(["-c", "sleep 10; sleep 10"]);
+    let result = crate::timer::execute_and_measure(command, Some(0.1)).unwrap();
+
+    assert!(result.timed_out);
+    assert!(!result.status.success());
+    assert!(result.time_real < 5.0);
+}
+
 #[test]
 fn test_cpu_time_interval() {
     let t_a = CPUTimes {
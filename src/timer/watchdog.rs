@@ -0,0 +1,72 @@
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::util::units::Second;
+
+/// Time between the polite termination request and the forced termination of a command
+/// that exceeded its timeout.
+const GRACE_PERIOD: Duration = Duration::from_millis(500);
+
+/// A background thread that terminates a running command once a given timeout expires.
+pub struct Watchdog {
+    cancel: Sender<()>,
+    handle: JoinHandle<bool>,
+}
+
+impl Watchdog {
+    /// Start watching. If the timeout expires before `stop` is called, `terminate` is called
+    /// with `force = false` first (e.g. SIGTERM), and with `force = true` (e.g. SIGKILL) after
+    /// a short grace period.
+    pub fn start<F>(timeout: Second, terminate: F) -> Self
+    where
+        F: Fn(bool) + Send + 'static,
+    {
+        let (cancel, cancelled) = mpsc::channel();
+        let timeout = Duration::from_secs_f64(timeout);
+
+        let handle = thread::spawn(move || match cancelled.recv_timeout(timeout) {
+            Err(RecvTimeoutError::Timeout) => {
+                terminate(false);
+                let _ = cancelled.recv_timeout(GRACE_PERIOD);
+
+                // Always send the second signal in order to clean up all remaining
+                // processes, even if the main process reacted to the first one.
+                terminate(true);
+                true
+            }
+            _ => false,
+        });
+
+        Watchdog { cancel, handle }
+    }
+
+    /// Stop watching. Returns whether the command had to be terminated.
+    pub fn stop(self) -> bool {
+        let _ = self.cancel.send(());
+        self.handle.join().unwrap_or(false)
+    }
+}
+
+#[test]
+fn test_watchdog() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let calls_clone = calls.clone();
+    let watchdog = Watchdog::start(10.0, move |_| {
+        calls_clone.fetch_add(1, Ordering::SeqCst);
+    });
+    assert!(!watchdog.stop());
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    let calls_clone = calls.clone();
+    let watchdog = Watchdog::start(0.01, move |_| {
+        calls_clone.fetch_add(1, Ordering::SeqCst);
+    });
+    thread::sleep(Duration::from_millis(50));
+    assert!(watchdog.stop());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
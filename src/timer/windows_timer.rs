@@ -7,7 +7,7 @@ use windows_sys::Win32::{
     Foundation::{CloseHandle, HANDLE},
     System::JobObjects::{
         AssignProcessToJobObject, CreateJobObjectW, JobObjectBasicAccountingInformation,
-        QueryInformationJobObject, JOBOBJECT_BASIC_ACCOUNTING_INFORMATION,
+        QueryInformationJobObject, TerminateJobObject, JOBOBJECT_BASIC_ACCOUNTING_INFORMATION,
     },
 };
 
@@ -83,6 +83,16 @@ impl CPUTimer {
         Self { job_object }
     }
 
+    /// Returns a function that terminates all processes in the job object. Windows has no
+    /// equivalent of SIGTERM, so the processes are terminated immediately.
+    pub fn terminator(&self) -> impl Fn(bool) + Send + 'static {
+        let job_object = self.job_object;
+        move |_force| {
+            // SAFETY: The job object is only closed after the watchdog has been stopped
+            unsafe { TerminateJobObject(job_object, 1) };
+        }
+    }
+
     pub fn stop(&self) -> (Second, Second) {
         let mut job_object_info =
             mem::MaybeUninit::<JOBOBJECT_BASIC_ACCOUNTING_INFORMATION>::uninit();
@@ -0,0 +1,83 @@
+//! Handling of Ctrl-C (SIGINT). The first interrupt lets hyperfine finish gracefully: the
+//! running command is terminated, and the benchmarks stop after the current run such that the
+//! results collected so far can still be summarized and exported. A second interrupt aborts
+//! immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(not(windows))]
+use std::sync::Mutex;
+
+use anyhow::Result;
+
+/// Exit code of hyperfine after it has been interrupted (128 + SIGINT)
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Process ID of the currently running command, or the negated process group ID if the
+/// command runs in its own process group. Zero if no command is running. The lock is held
+/// while a signal is sent, such that the command can not be reaped in the meantime.
+#[cfg(not(windows))]
+static RUNNING_PROCESS: Mutex<i32> = Mutex::new(0);
+
+/// Install the Ctrl-C handler
+pub fn install_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            terminate_running_process(true);
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+
+        terminate_running_process(false);
+    })?;
+
+    Ok(())
+}
+
+/// Whether hyperfine has been interrupted
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Register the command that is currently running, such that it can be terminated on an
+/// interrupt. `process_group` specifies whether it is the leader of its own process group.
+#[cfg(not(windows))]
+pub fn set_running_process(pid: u32, process_group: bool) {
+    let pid = pid as i32;
+    let target = if process_group { -pid } else { pid };
+    *running_process() = target;
+}
+
+/// Unregister the command once it has exited. This has to be called before the command is
+/// reaped: afterwards, its process (group) ID could be reused by an unrelated process.
+#[cfg(not(windows))]
+pub fn clear_running_process() {
+    *running_process() = 0;
+}
+
+#[cfg(not(windows))]
+fn running_process() -> std::sync::MutexGuard<'static, i32> {
+    RUNNING_PROCESS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Send SIGTERM (or SIGKILL, if `force` is set) to the running command, if any. This is
+/// needed because the command does not necessarily receive the SIGINT itself, e.g. if it runs
+/// in its own process group or if the signal was only sent to hyperfine.
+#[cfg(not(windows))]
+fn terminate_running_process(force: bool) {
+    use libc::{kill, SIGKILL, SIGTERM};
+
+    let target = running_process();
+    if *target != 0 {
+        let signal = if force { SIGKILL } else { SIGTERM };
+        unsafe { kill(*target, signal) };
+    }
+}
+
+/// On Windows, Ctrl-C is delivered to all processes attached to the console, including the
+/// running command.
+#[cfg(windows)]
+fn terminate_running_process(_force: bool) {}
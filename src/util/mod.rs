@@ -1,5 +1,8 @@
 pub mod exit_code;
+pub mod interrupt;
 pub mod min_max;
 pub mod number;
 pub mod randomized_environment_offset;
+pub mod statistics;
+pub mod timestamp;
 pub mod units;
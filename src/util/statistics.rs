@@ -0,0 +1,610 @@
+//! Statistical helper functions that are not provided by the `statistical` crate.
+
+use std::f64::consts::PI;
+
+use rand::distributions::{Distribution, Uniform};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use statistical::{mean, median, standard_deviation, variance};
+
+/// Fixed seed for the bootstrap resampling, such that the same measurements always
+/// lead to the same confidence intervals. The resampling uses the fast `SmallRng`, since
+/// the thousands of resamples would otherwise dominate the run time of short benchmarks.
+const BOOTSTRAP_SEED: u64 = 0x6879_7065_7266_696e;
+
+/// Natural logarithm of the gamma function (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |acc, (j, c)| {
+            acc + c / (x + 1.0 + j as f64)
+        });
+
+    -tmp + (2.0 * PI).sqrt().ln() + (series / x).ln()
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz's method)
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 300;
+    const EPSILON: f64 = 3e-14;
+    const TINY: f64 = 1e-300;
+
+    let qab = a + b;
+    let qap = a + 1.0;
+    let qam = a - 1.0;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Cumulative distribution function of Student's t-distribution with `df` degrees of freedom
+pub fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let tail = 0.5 * incomplete_beta(df / 2.0, 0.5, df / (df + t * t));
+    if t > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// Find `x` such that `cdf(x) = p` by bisection. `cdf` needs to be monotonically increasing.
+fn invert_cdf(p: f64, cdf: impl Fn(f64) -> f64) -> f64 {
+    let (mut lo, mut hi) = (-1.0, 1.0);
+    while cdf(lo) > p {
+        lo *= 2.0;
+    }
+    while cdf(hi) < p {
+        hi *= 2.0;
+    }
+
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if cdf(mid) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    0.5 * (lo + hi)
+}
+
+/// Quantile function of Student's t-distribution with `df` degrees of freedom
+pub fn student_t_quantile(p: f64, df: f64) -> f64 {
+    invert_cdf(p, |t| student_t_cdf(t, df))
+}
+
+/// Cumulative distribution function of the standard normal distribution
+pub fn normal_cdf(x: f64) -> f64 {
+    // Complementary error function with fractional error below 1.2e-7, see
+    // "Numerical Recipes in C", section 6.2
+    let erfc = |x: f64| {
+        let z = x.abs();
+        let t = 1.0 / (1.0 + 0.5 * z);
+        let r = t
+            * (-z * z - 1.265_512_23
+                + t * (1.000_023_68
+                    + t * (0.374_091_96
+                        + t * (0.096_784_18
+                            + t * (-0.186_288_06
+                                + t * (0.278_868_07
+                                    + t * (-1.135_203_98
+                                        + t * (1.488_515_87
+                                            + t * (-0.822_152_23 + t * 0.170_872_77)))))))))
+                .exp();
+        if x >= 0.0 {
+            r
+        } else {
+            2.0 - r
+        }
+    };
+
+    0.5 * erfc(-x / 2.0_f64.sqrt())
+}
+
+/// Quantile function of the standard normal distribution
+pub fn normal_quantile(p: f64) -> f64 {
+    invert_cdf(p, normal_cdf)
+}
+
+/// Half width of the confidence interval of the mean of the given sample, relative to the mean.
+/// Returns `None` if it can not be computed (fewer than two values or zero mean).
+pub fn relative_mean_ci_half_width(xs: &[f64], confidence: f64) -> Option<f64> {
+    if xs.len() < 2 {
+        return None;
+    }
+
+    let x_mean = mean(xs);
+    if x_mean == 0.0 {
+        return None;
+    }
+
+    let n = xs.len() as f64;
+    let t = student_t_quantile(0.5 + confidence / 2.0, n - 1.0);
+    let half_width = t * standard_deviation(xs, Some(x_mean)) / n.sqrt();
+
+    Some(half_width / x_mean)
+}
+
+/// Half width of the (distribution-free) confidence interval of the median of the given sample,
+/// relative to the median. It is based on the order statistics of the sample. Returns `None` if
+/// it can not be computed (fewer than two values or zero median).
+pub fn relative_median_ci_half_width(xs: &[f64], confidence: f64) -> Option<f64> {
+    if xs.len() < 2 {
+        return None;
+    }
+
+    let x_median = median(xs);
+    if x_median == 0.0 {
+        return None;
+    }
+
+    let mut sorted = xs.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n = sorted.len() as f64;
+    let z = normal_quantile(0.5 + confidence / 2.0);
+    let offset = z * n.sqrt() / 2.0;
+    let lower = ((n / 2.0 - offset).floor() as usize).clamp(1, sorted.len());
+    let upper = ((1.0 + n / 2.0 + offset).ceil() as usize).clamp(1, sorted.len());
+
+    let half_width = (sorted[upper - 1] - sorted[lower - 1]) / 2.0;
+
+    Some(half_width / x_median)
+}
+
+/// Two-sided p-value of Welch's t-test for the null hypothesis that both samples have the
+/// same mean. Returns `None` if one of the samples has fewer than two values.
+pub fn welch_t_test(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() < 2 || ys.len() < 2 {
+        return None;
+    }
+
+    let (n_x, n_y) = (xs.len() as f64, ys.len() as f64);
+    let (mean_x, mean_y) = (mean(xs), mean(ys));
+    let se_x = variance(xs, Some(mean_x)) / n_x;
+    let se_y = variance(ys, Some(mean_y)) / n_y;
+
+    let se = se_x + se_y;
+    if se == 0.0 {
+        // Both samples are constant
+        return Some(if mean_x == mean_y { 1.0 } else { 0.0 });
+    }
+
+    let t = (mean_x - mean_y) / se.sqrt();
+
+    // Welch–Satterthwaite equation
+    let df = se.powi(2) / (se_x.powi(2) / (n_x - 1.0) + se_y.powi(2) / (n_y - 1.0));
+
+    Some(2.0 * student_t_cdf(-t.abs(), df))
+}
+
+/// Two-sided p-value of the Mann-Whitney U test for the null hypothesis that both samples come
+/// from the same distribution. Uses the normal approximation with tie correction. Returns `None`
+/// if one of the samples is empty.
+pub fn mann_whitney_u_test(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.is_empty() || ys.is_empty() {
+        return None;
+    }
+
+    let mut values: Vec<(f64, bool)> = xs
+        .iter()
+        .map(|&x| (x, true))
+        .chain(ys.iter().map(|&y| (y, false)))
+        .collect();
+    values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    // Assign mid-ranks to tied values
+    let mut rank_sum_x = 0.0;
+    let mut tie_correction = 0.0;
+    let mut i = 0;
+    while i < values.len() {
+        let mut j = i;
+        while j + 1 < values.len() && values[j + 1].0 == values[i].0 {
+            j += 1;
+        }
+
+        let ties = (j - i + 1) as f64;
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        rank_sum_x += rank * values[i..=j].iter().filter(|v| v.1).count() as f64;
+        tie_correction += ties.powi(3) - ties;
+
+        i = j + 1;
+    }
+
+    let (n_x, n_y) = (xs.len() as f64, ys.len() as f64);
+    let n = n_x + n_y;
+    let u = rank_sum_x - n_x * (n_x + 1.0) / 2.0;
+    let mean_u = n_x * n_y / 2.0;
+    let sigma_u = (n_x * n_y / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)))).sqrt();
+
+    if sigma_u == 0.0 || !sigma_u.is_finite() {
+        // All values are identical
+        return Some(1.0);
+    }
+
+    // Continuity correction
+    let z = ((u - mean_u).abs() - 0.5).max(0.0) / sigma_u;
+
+    Some((2.0 * normal_cdf(-z)).min(1.0))
+}
+
+/// The `p`-th percentile (0 ≤ p ≤ 100) of the given sorted values, using linear
+/// interpolation between the closest ranks (same as numpy's default method).
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Bootstrap resampling of a sample. A resample is represented by how often each of the
+/// (sorted) values has been drawn, which makes its mean and median cheap to compute.
+struct Resampler {
+    sorted: Vec<f64>,
+    counts: Vec<usize>,
+    index: Uniform<usize>,
+}
+
+impl Resampler {
+    fn new(xs: &[f64]) -> Self {
+        let mut sorted = xs.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Resampler {
+            counts: vec![0; sorted.len()],
+            index: Uniform::new(0, sorted.len()),
+            sorted,
+        }
+    }
+
+    /// Draw a new resample (with replacement) and return its mean
+    fn draw(&mut self, rng: &mut SmallRng) -> f64 {
+        self.counts.fill(0);
+        for _ in 0..self.sorted.len() {
+            self.counts[self.index.sample(rng)] += 1;
+        }
+        let sum: f64 = self
+            .counts
+            .iter()
+            .zip(&self.sorted)
+            .map(|(count, x)| *count as f64 * x)
+            .sum();
+        sum / self.sorted.len() as f64
+    }
+
+    /// Median of the current resample
+    fn median(&self) -> f64 {
+        let n = self.sorted.len();
+        if n % 2 == 1 {
+            self.value_at(n / 2)
+        } else {
+            (self.value_at(n / 2 - 1) + self.value_at(n / 2)) / 2.0
+        }
+    }
+
+    /// The value at the given position of the sorted resample
+    fn value_at(&self, rank: usize) -> f64 {
+        let mut drawn = 0;
+        for (count, x) in self.counts.iter().zip(&self.sorted) {
+            drawn += count;
+            if drawn > rank {
+                return *x;
+            }
+        }
+        self.sorted[self.sorted.len() - 1]
+    }
+}
+
+/// The `(1 - confidence) / 2` and `(1 + confidence) / 2` percentiles of the given estimates
+fn percentile_interval(mut estimates: Vec<f64>, confidence: f64) -> (f64, f64) {
+    estimates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let last = (estimates.len() - 1) as f64;
+    let lower = ((1.0 - confidence) / 2.0 * last).round() as usize;
+    let upper = ((1.0 + confidence) / 2.0 * last).round() as usize;
+
+    (estimates[lower], estimates[upper])
+}
+
+/// Percentile bootstrap confidence intervals of the mean and of the median of the given
+/// sample. Returns `None` if there are fewer than two values or no resamples are requested.
+pub fn bootstrap_mean_median(
+    xs: &[f64],
+    confidence: f64,
+    resamples: u64,
+) -> Option<((f64, f64), (f64, f64))> {
+    if xs.len() < 2 || resamples == 0 {
+        return None;
+    }
+
+    let mut rng = SmallRng::seed_from_u64(BOOTSTRAP_SEED);
+    let mut resampler = Resampler::new(xs);
+    let mut means = Vec::with_capacity(resamples as usize);
+    let mut medians = Vec::with_capacity(resamples as usize);
+
+    for _ in 0..resamples {
+        means.push(resampler.draw(&mut rng));
+        medians.push(resampler.median());
+    }
+
+    Some((
+        percentile_interval(means, confidence),
+        percentile_interval(medians, confidence),
+    ))
+}
+
+/// Percentile bootstrap confidence interval of the ratio `mean(xs) / mean(ys)`, resampling
+/// both samples independently. Returns `None` if one of the samples has fewer than two
+/// values or no resamples are requested.
+pub fn bootstrap_ratio_of_means(
+    xs: &[f64],
+    ys: &[f64],
+    confidence: f64,
+    resamples: u64,
+) -> Option<(f64, f64)> {
+    if xs.len() < 2 || ys.len() < 2 || resamples == 0 {
+        return None;
+    }
+
+    let mut rng = SmallRng::seed_from_u64(BOOTSTRAP_SEED);
+    let mut resampler_x = Resampler::new(xs);
+    let mut resampler_y = Resampler::new(ys);
+    let mut ratios = Vec::with_capacity(resamples as usize);
+
+    for _ in 0..resamples {
+        let mean_x = resampler_x.draw(&mut rng);
+        let mean_y = resampler_y.draw(&mut rng);
+        if mean_y == 0.0 {
+            return None;
+        }
+        ratios.push(mean_x / mean_y);
+    }
+
+    Some(percentile_interval(ratios, confidence))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_student_t_cdf() {
+        assert_relative_eq!(student_t_cdf(0.0, 5.0), 0.5);
+        assert_relative_eq!(student_t_cdf(2.015_048, 5.0), 0.95, epsilon = 1e-6);
+        assert_relative_eq!(student_t_cdf(-2.015_048, 5.0), 0.05, epsilon = 1e-6);
+        assert_relative_eq!(student_t_cdf(1.0, 1.0), 0.75, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_student_t_quantile() {
+        assert_relative_eq!(student_t_quantile(0.975, 1.0), 12.706_205, epsilon = 1e-5);
+        assert_relative_eq!(student_t_quantile(0.975, 9.0), 2.262_157, epsilon = 1e-5);
+        assert_relative_eq!(student_t_quantile(0.975, 1000.0), 1.962_339, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_normal() {
+        assert_relative_eq!(normal_cdf(0.0), 0.5, epsilon = 1e-7);
+        assert_relative_eq!(normal_cdf(1.959_964), 0.975, epsilon = 1e-6);
+        assert_relative_eq!(normal_quantile(0.975), 1.959_964, epsilon = 1e-5);
+        assert_relative_eq!(normal_quantile(0.5), 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_welch_t_test() {
+        assert!(welch_t_test(&[1.0], &[1.0, 2.0]).is_none());
+
+        // t = -4.932 with 8 degrees of freedom
+        let xs = [10.1, 10.3, 9.8, 10.0, 10.2];
+        let ys = [10.6, 10.9, 10.4, 10.8, 10.7];
+        assert_relative_eq!(welch_t_test(&xs, &ys).unwrap(), 0.001_146_6, epsilon = 1e-6);
+        assert_relative_eq!(welch_t_test(&xs, &xs).unwrap(), 1.0);
+
+        assert_eq!(welch_t_test(&[1.0, 1.0], &[2.0, 2.0]), Some(0.0));
+        assert_eq!(welch_t_test(&[1.0, 1.0], &[1.0, 1.0]), Some(1.0));
+    }
+
+    #[test]
+    fn test_mann_whitney_u_test() {
+        assert!(mann_whitney_u_test(&[], &[1.0]).is_none());
+
+        // U = 4.5, z = 2.842 (with tie and continuity correction)
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let ys = [6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0];
+        assert_relative_eq!(
+            mann_whitney_u_test(&xs, &ys).unwrap(),
+            0.004_485,
+            epsilon = 1e-5
+        );
+        assert_relative_eq!(mann_whitney_u_test(&xs, &xs).unwrap(), 1.0);
+        assert_eq!(mann_whitney_u_test(&[1.0, 1.0], &[1.0]), Some(1.0));
+    }
+
+    #[test]
+    fn test_percentile() {
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(percentile(&xs, 0.0), 1.0);
+        assert_eq!(percentile(&xs, 25.0), 2.0);
+        assert_eq!(percentile(&xs, 50.0), 3.0);
+        assert_eq!(percentile(&xs, 95.0), 4.8);
+        assert_eq!(percentile(&xs, 100.0), 5.0);
+        assert_eq!(percentile(&[7.0], 99.0), 7.0);
+    }
+
+    #[test]
+    fn test_resampler() {
+        let median_of = |xs: &[f64], counts: Vec<usize>| {
+            let mut resampler = Resampler::new(xs);
+            resampler.counts = counts;
+            resampler.median()
+        };
+        // Sorted values: 1, 2, 3, 4
+        assert_eq!(median_of(&[3.0, 1.0, 4.0, 2.0], vec![1, 1, 1, 1]), 2.5);
+        assert_eq!(median_of(&[3.0, 1.0, 4.0, 2.0], vec![0, 0, 3, 1]), 3.0);
+        assert_eq!(median_of(&[3.0, 1.0, 4.0, 2.0], vec![2, 0, 0, 2]), 2.5);
+        assert_eq!(median_of(&[3.0, 1.0, 2.0], vec![1, 2, 0]), 2.0);
+        assert_eq!(median_of(&[5.0], vec![1]), 5.0);
+
+        // Every resample has as many values as the sample
+        let mut rng = SmallRng::seed_from_u64(BOOTSTRAP_SEED);
+        let mut resampler = Resampler::new(&[1.0, 2.0, 3.0]);
+        let mean = resampler.draw(&mut rng);
+        assert_eq!(resampler.counts.iter().sum::<usize>(), 3);
+        assert!((1.0..=3.0).contains(&mean));
+    }
+
+    #[test]
+    fn test_bootstrap_mean_median() {
+        assert!(bootstrap_mean_median(&[1.0], 0.95, 1000).is_none());
+        assert!(bootstrap_mean_median(&[1.0, 2.0], 0.95, 0).is_none());
+
+        let xs: Vec<f64> = (1..=100).map(|x| x as f64).collect();
+        let (mean_ci, median_ci) = bootstrap_mean_median(&xs, 0.95, 2000).unwrap();
+
+        // The standard error of the mean is 29.01 / sqrt(100) = 2.9
+        assert!(mean_ci.0 < 50.5 && mean_ci.0 > 50.5 - 2.0 * 2.9);
+        assert!(mean_ci.1 > 50.5 && mean_ci.1 < 50.5 + 2.0 * 2.9);
+        assert!(median_ci.0 < 50.5 && median_ci.1 > 50.5);
+
+        // Same data, same result
+        assert_eq!(
+            bootstrap_mean_median(&xs, 0.95, 2000).unwrap(),
+            (mean_ci, median_ci)
+        );
+
+        // Narrower intervals for lower confidence levels
+        let (mean_ci_50, _) = bootstrap_mean_median(&xs, 0.5, 2000).unwrap();
+        assert!(mean_ci_50.0 > mean_ci.0 && mean_ci_50.1 < mean_ci.1);
+
+        let constant = [2.0; 10];
+        assert_eq!(
+            bootstrap_mean_median(&constant, 0.95, 100),
+            Some(((2.0, 2.0), (2.0, 2.0)))
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_ratio_of_means() {
+        assert!(bootstrap_ratio_of_means(&[1.0], &[1.0, 2.0], 0.95, 100).is_none());
+        assert!(bootstrap_ratio_of_means(&[1.0, 2.0], &[0.0, 0.0], 0.95, 100).is_none());
+
+        assert_eq!(
+            bootstrap_ratio_of_means(&[4.0, 4.0], &[2.0, 2.0], 0.95, 100),
+            Some((2.0, 2.0))
+        );
+
+        let xs: Vec<f64> = (0..50).map(|i| 2.0 + 0.01 * (i % 10) as f64).collect();
+        let ys: Vec<f64> = (0..50).map(|i| 1.0 + 0.01 * (i % 10) as f64).collect();
+        let (lower, upper) = bootstrap_ratio_of_means(&xs, &ys, 0.95, 2000).unwrap();
+        let ratio = mean(&xs) / mean(&ys);
+        assert!(lower < ratio && ratio < upper);
+        assert!(upper - lower < 0.05);
+    }
+
+    #[test]
+    fn test_relative_ci_half_widths() {
+        assert!(relative_mean_ci_half_width(&[1.0], 0.95).is_none());
+        assert!(relative_median_ci_half_width(&[0.0, 0.0], 0.95).is_none());
+
+        // mean = 10, stddev = 1, t(0.975, 9) = 2.262157
+        let xs = [
+            9.0,
+            11.0,
+            9.0,
+            11.0,
+            9.0,
+            11.0,
+            9.0,
+            11.0,
+            10.0 - 0.5_f64.sqrt(),
+            10.0 + 0.5_f64.sqrt(),
+        ];
+        assert_relative_eq!(
+            relative_mean_ci_half_width(&xs, 0.95).unwrap(),
+            2.262_157 / 10.0_f64.sqrt() / 10.0,
+            epsilon = 1e-5
+        );
+
+        let xs: Vec<f64> = (1..=100).map(|x| x as f64).collect();
+        let width = relative_median_ci_half_width(&xs, 0.95).unwrap();
+        assert_relative_eq!(width, 10.5 / 50.5, epsilon = 1e-9);
+
+        let constant = [5.0; 20];
+        assert_eq!(relative_median_ci_half_width(&constant, 0.95), Some(0.0));
+    }
+}
@@ -561,3 +561,60 @@ fn setup_separate_prepare_reference_separate_conclude_cleanup_combined() {
         .expect_output("cleanup")
         .run();
 }
+
+#[test]
+fn round_robin_timing_runs_alternate_between_commands() {
+    ExecutionOrderTest::new()
+        .arg("--runs=3")
+        .arg("--execution-order=round-robin")
+        .command("command1")
+        .command("command2")
+        .expect_output("command1")
+        .expect_output("command2")
+        .expect_output("command1")
+        .expect_output("command2")
+        .expect_output("command1")
+        .expect_output("command2")
+        .run();
+}
+
+#[test]
+fn round_robin_setup_prepare_conclude_cleanup_combined() {
+    ExecutionOrderTest::new()
+        .arg("--warmup=1")
+        .arg("--runs=2")
+        .arg("--execution-order=round-robin")
+        .setup("setup")
+        .prepare("prepare")
+        .command("command1")
+        .command("command2")
+        .conclude("conclude")
+        .cleanup("cleanup")
+        // Setup, warmup and initial run of 1
+        .expect_output("setup")
+        .expect_output("prepare")
+        .expect_output("command1")
+        .expect_output("conclude")
+        .expect_output("prepare")
+        .expect_output("command1")
+        .expect_output("conclude")
+        // Setup, warmup and initial run of 2
+        .expect_output("setup")
+        .expect_output("prepare")
+        .expect_output("command2")
+        .expect_output("conclude")
+        .expect_output("prepare")
+        .expect_output("command2")
+        .expect_output("conclude")
+        // Interleaved runs
+        .expect_output("prepare")
+        .expect_output("command1")
+        .expect_output("conclude")
+        .expect_output("prepare")
+        .expect_output("command2")
+        .expect_output("conclude")
+        // Cleanup of 1 and 2
+        .expect_output("cleanup")
+        .expect_output("cleanup")
+        .run();
+}
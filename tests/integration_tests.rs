@@ -271,6 +271,31 @@ fn can_run_failing_commands_with_ignore_failure_option() {
         .success();
 }
 
+#[cfg(unix)]
+#[test]
+fn terminates_commands_that_exceed_the_timeout() {
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--timeout=0.1")
+        .arg("sleep 10")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "has been terminated after exceeding the timeout",
+        ));
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--timeout=0.1")
+        .arg("--ignore-failure")
+        .arg("sleep 10")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "2 run(s) exceeded the timeout and have been terminated",
+        ));
+}
+
 #[test]
 fn shows_output_of_benchmarked_command() {
     hyperfine()
@@ -514,6 +539,115 @@ fn performs_all_benchmarks_in_parameter_scan() {
         );
 }
 
+#[test]
+fn combines_parameter_scans_and_lists() {
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-list")
+        .arg("impl")
+        .arg("a,b")
+        .arg("--parameter-scan")
+        .arg("threads")
+        .arg("1")
+        .arg("2")
+        .arg("--parameter-scan")
+        .arg("size")
+        .arg("0.5")
+        .arg("1")
+        .arg("--parameter-step-size")
+        .arg("1")
+        .arg("--parameter-step-size")
+        .arg("0.5")
+        .arg("--command-name")
+        .arg("{impl}-{threads}-{size}")
+        .arg("sleep {size}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Benchmark 1: a-1-0.5")
+                .and(predicate::str::contains("Benchmark 2: b-1-0.5"))
+                .and(predicate::str::contains("Benchmark 3: a-2-0.5"))
+                .and(predicate::str::contains("Benchmark 8: b-2-1.0"))
+                .and(predicate::str::contains("Benchmark 9").not()),
+        );
+
+    hyperfine_debug()
+        .arg("--parameter-scan")
+        .arg("x")
+        .arg("1")
+        .arg("2")
+        .arg("--parameter-list")
+        .arg("x")
+        .arg("a,b")
+        .arg("echo {x}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Duplicate parameter names: x"));
+
+    hyperfine_debug()
+        .arg("--parameter-scan")
+        .arg("x")
+        .arg("1")
+        .arg("2")
+        .arg("--parameter-step-size")
+        .arg("1")
+        .arg("--parameter-step-size")
+        .arg("1")
+        .arg("echo {x}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'--parameter-step-size' has been specified 2 times",
+        ));
+}
+
+#[test]
+fn performs_geometric_parameter_scan() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("results.json");
+
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-scan-geometric")
+        .arg("size")
+        .arg("1K")
+        .arg("16K")
+        .arg("--factor")
+        .arg("4")
+        .arg("--command-name")
+        .arg("size {size}")
+        .arg("--export-json")
+        .arg(&export_path)
+        .arg("sleep 0.{size}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Benchmark 1: size 1024")
+                .and(predicate::str::contains("Benchmark 2: size 4096"))
+                .and(predicate::str::contains("Benchmark 3: size 16384"))
+                .and(predicate::str::contains("Benchmark 4").not()),
+        );
+
+    // Parameter values are plain numbers
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(json["results"][2]["parameters"]["size"], "16384");
+
+    hyperfine_debug()
+        .arg("--parameter-scan-geometric")
+        .arg("size")
+        .arg("0")
+        .arg("16")
+        .arg("sleep 0.{size}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The start of a geometric parameter range has to be positive",
+        ));
+}
+
 #[test]
 fn performs_reference_and_all_benchmarks_in_parameter_scan() {
     hyperfine_debug()
@@ -547,9 +681,10 @@ fn intermediate_results_are_not_exported_to_stdout() {
         .arg("sleep 2")
         .assert()
         .success()
+        // Each command appears once in the main table and once in the percentiles table
         .stdout(
-            (predicate::str::contains("sleep 1").count(1))
-                .and(predicate::str::contains("sleep 2").count(1)),
+            (predicate::str::contains("sleep 1").count(2))
+                .and(predicate::str::contains("sleep 2").count(2)),
         );
 }
 
@@ -574,6 +709,352 @@ fn exports_intermediate_results_to_file() {
     assert!(contents.contains("true"));
 }
 
+#[test]
+fn exports_html_report() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("report.html");
+
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--export-html")
+        .arg(&export_path)
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    assert!(contents.starts_with("<!DOCTYPE html>"));
+    assert!(contents.contains("<code>sleep 1</code>"));
+    assert!(contents.contains("<code>sleep 2</code>"));
+    assert!(contents.contains("<svg"));
+}
+
+#[test]
+fn exports_svg_charts() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let path = |kind: &str| tempdir.path().join(format!("{kind}.svg"));
+    let kinds = ["histogram", "whisker", "progression", "parametrized"];
+
+    let mut cmd = hyperfine_debug();
+    cmd.arg("--runs=5")
+        .arg("--parameter-scan")
+        .arg("time")
+        .arg("1")
+        .arg("3");
+    for kind in kinds {
+        cmd.arg("--export-svg")
+            .arg(format!("{kind}={}", path(kind).display()));
+    }
+    cmd.arg("sleep {time}").assert().success();
+
+    for kind in kinds {
+        let contents = std::fs::read_to_string(path(kind)).unwrap();
+        assert!(contents.starts_with("<svg"));
+        assert!(contents.ends_with("</svg>\n"));
+    }
+
+    // The parameter is shown on the x axis
+    let parametrized = std::fs::read_to_string(path("parametrized")).unwrap();
+    assert!(parametrized.contains(">time</text>"));
+    assert!(parametrized.contains(">sleep {time}</text>"));
+
+    // The reference command has no parameters and is left out of the chart
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--reference=sleep 0.5")
+        .arg("--parameter-scan")
+        .arg("time")
+        .arg("1")
+        .arg("2")
+        .arg("--export-svg")
+        .arg(format!("parametrized={}", path("reference").display()))
+        .arg("sleep {time}")
+        .assert()
+        .success();
+    let parametrized = std::fs::read_to_string(path("reference")).unwrap();
+    assert!(parametrized.contains(">sleep {time}</text>"));
+    assert!(!parametrized.contains(">sleep 0.5</text>"));
+
+    // Without a numeric parameter, all benchmarks are still performed and the error is only
+    // reported at the end
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-list")
+        .arg("mode")
+        .arg("fast,slow")
+        .arg("--export-svg")
+        .arg(format!("parametrized={}", path("text").display()))
+        .arg("--export-json")
+        .arg(tempdir.path().join("text.json"))
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Benchmark 2: sleep 1 (mode = slow)",
+        ))
+        .stderr(predicate::str::contains(
+            "The parametrized chart requires a numeric benchmark parameter",
+        ));
+    let json = std::fs::read_to_string(tempdir.path().join("text.json")).unwrap();
+    assert!(json.contains("\"slow\""));
+
+    // Values with a size suffix are numeric, also in placeholder expressions
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-list")
+        .arg("size")
+        .arg("1K,2K")
+        .arg("--export-svg")
+        .arg(format!("parametrized={}", path("suffix").display()))
+        .arg("sleep {size/1024}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Benchmark 1: sleep 1")
+                .and(predicate::str::contains("Benchmark 2: sleep 2")),
+        );
+    let parametrized = std::fs::read_to_string(path("suffix")).unwrap();
+    assert!(parametrized.contains(">size</text>"));
+}
+
+#[test]
+fn fails_with_invalid_export_svg_arguments() {
+    hyperfine_debug()
+        .arg("--export-svg=pie=chart.svg")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown chart type 'pie'"));
+
+    hyperfine_debug()
+        .arg("--export-svg=chart.svg")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'<KIND>=<FILE>'"));
+
+    hyperfine_debug()
+        .arg("--export-svg=parametrized=chart.svg")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "requires --parameter-scan, --parameter-scan-geometric or --parameter-list",
+        ));
+}
+
+#[test]
+fn exports_junit_report() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("report.xml");
+
+    hyperfine()
+        .arg("--runs=2")
+        .arg("--ignore-failure")
+        .arg("--export-junit")
+        .arg(&export_path)
+        .arg("echo a")
+        .arg("exit 1")
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    assert!(contents.contains("<testsuite name=\"hyperfine\" tests=\"2\" failures=\"1\""));
+    assert!(contents.contains("<testcase name=\"echo a\""));
+    assert!(contents.contains("<failure message=\"2 of 2 runs failed with a non-zero exit code\">"));
+}
+
+#[test]
+fn exports_prometheus_metrics() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("hyperfine.prom");
+
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--export-prometheus")
+        .arg(&export_path)
+        .arg("--parameter-list")
+        .arg("time")
+        .arg("1,2")
+        .arg("sleep {time}")
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    assert!(contents.contains("# TYPE hyperfine_mean_seconds gauge\n"));
+    assert!(contents.contains("hyperfine_mean_seconds{command=\"sleep 1\",time=\"1\"} 1\n"));
+    assert!(contents.contains("hyperfine_max_seconds{command=\"sleep 2\",time=\"2\"} 2\n"));
+}
+
+#[test]
+fn exports_event_log() {
+    let output = hyperfine_debug()
+        .arg("--style=none")
+        .arg("--runs=2")
+        .arg("--warmup=1")
+        .arg("--setup=sleep 0.1")
+        .arg("--conclude=sleep 0.2")
+        .arg("--export-events=-")
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let events: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let kinds: Vec<&str> = events
+        .iter()
+        .map(|event| event["event"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(
+        kinds[..9],
+        [
+            "calibration",
+            "benchmark_started",
+            "setup",
+            "warmup_run",
+            "conclude",
+            "timing_run",
+            "conclude",
+            "timing_run",
+            "conclude",
+        ]
+    );
+    assert_eq!(kinds[9], "benchmark_finished");
+    assert_eq!(kinds.len(), 19);
+
+    let timing_runs: Vec<_> = events
+        .iter()
+        .filter(|event| event["event"] == "timing_run")
+        .collect();
+    assert_eq!(timing_runs.len(), 4);
+    assert_eq!(timing_runs[3]["benchmark"], 2);
+    assert_eq!(timing_runs[3]["run"], 2);
+    assert_eq!(timing_runs[3]["time_real"], 2.0);
+    assert_eq!(timing_runs[3]["exit_code"], 0);
+
+    assert_eq!(events[18]["result"]["command"], "sleep 2");
+}
+
+#[test]
+fn interleaved_execution_order_reports_all_commands() {
+    for order in ["round-robin", "random"] {
+        hyperfine_debug()
+            .arg("--runs=3")
+            .arg(format!("--execution-order={order}"))
+            .arg("sleep 1")
+            .arg("sleep 2")
+            .assert()
+            .success()
+            .stdout(
+                predicate::str::contains("Benchmark 1: sleep 1")
+                    .and(predicate::str::contains("Benchmark 2: sleep 2"))
+                    .and(predicate::str::contains("2.00 ± 0.00 times faster")),
+            );
+    }
+}
+
+#[test]
+fn target_precision_stops_once_precision_is_reached() {
+    for statistic in ["mean", "median"] {
+        hyperfine_debug()
+            .arg("--target-precision=1%")
+            .arg(format!("--target-precision-statistic={statistic}"))
+            .arg("--min-runs=3")
+            .arg("sleep 0.5")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("3 runs, ±0.0%"));
+    }
+}
+
+#[test]
+fn target_precision_respects_max_runs() {
+    hyperfine_debug()
+        .arg("--target-precision=1%")
+        .arg("--max-runs=1")
+        .arg("sleep 0.5")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "The target precision of ±1.0% has not been reached",
+        ));
+}
+
+#[test]
+fn target_precision_performs_min_runs_despite_time_budget() {
+    // The time budget is measured in wall-clock time, so the command is actually executed
+    hyperfine()
+        .arg("--target-precision=0.001%")
+        .arg("--time-budget=0.001")
+        .arg("--min-runs=10")
+        .arg("sleep 0.01")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("10 runs, ±"));
+}
+
+#[test]
+fn target_precision_works_with_interleaved_execution() {
+    hyperfine_debug()
+        .arg("--target-precision=1%")
+        .arg("--execution-order=round-robin")
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("10 runs, ±0.0%")
+                .and(predicate::str::contains("2.00 ± 0.00 times faster")),
+        );
+}
+
+#[test]
+fn fails_with_invalid_target_precision() {
+    hyperfine()
+        .arg("--target-precision=-1%")
+        .arg("echo test")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid target precision"));
+
+    hyperfine()
+        .arg("--target-precision=1%")
+        .arg("--runs=10")
+        .arg("echo test")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    for time_budget in ["NaN", "inf", "-5", "0"] {
+        hyperfine()
+            .arg("--target-precision=1%")
+            .arg(format!("--time-budget={time_budget}"))
+            .arg("echo test")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "The time budget has to be a positive number of seconds",
+            ));
+    }
+}
+
 #[test]
 fn unused_parameters_are_shown_in_benchmark_name() {
     hyperfine()
@@ -611,18 +1092,557 @@ fn speed_comparison_sort_order() {
         .assert()
         .success()
         .stdout(predicate::str::contains(
-            "2.00 ±  0.00  sleep 2\n        1.00          sleep 1",
+            "2.00 ±  0.00  sleep 2 (95% CI: 2.00 … 2.00, p < 0.001)\n        1.00          sleep 1",
         ));
 }
 
-#[cfg(windows)]
 #[test]
-fn windows_quote_args() {
-    hyperfine()
-        .arg("more \"example_input_file.txt\"")
-        .assert()
-        .success();
-}
+fn speed_comparison_shows_significance() {
+    for test in ["welch", "mann-whitney"] {
+        hyperfine_debug()
+            .arg(format!("--significance-test={test}"))
+            .arg("sleep 1")
+            .arg("sleep 2")
+            .arg("sleep 1.0")
+            .assert()
+            .success()
+            .stdout(
+                predicate::str::contains(
+                    "times faster than sleep 2 (95% CI: 2.00 … 2.00, p < 0.001)",
+                )
+                .and(predicate::str::contains(
+                    "as sleep 1.0 (95% CI: 1.00 … 1.00, not significant, p = 1.000)",
+                )),
+            );
+    }
+}
+
+#[test]
+fn json_export_contains_significance() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("results.json");
+
+    hyperfine_debug()
+        .arg("--export-json")
+        .arg(&export_path)
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let comparison = &json["comparison"][0];
+    assert_eq!(comparison["command"], "sleep 2");
+    assert_eq!(comparison["reference"], "sleep 1");
+    assert_eq!(comparison["significance_test"], "welch");
+    assert_eq!(comparison["p_value"], 0.0);
+    assert_eq!(comparison["significant"], true);
+
+    let intervals = &json["results"][0]["confidence_intervals"];
+    assert_eq!(intervals["level"], 0.95);
+    assert_eq!(intervals["mean"]["lower"], 1.0);
+    assert_eq!(intervals["median"]["upper"], 1.0);
+}
+
+#[test]
+fn json_export_compares_with_reference_command() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("results.json");
+
+    hyperfine_debug()
+        .arg("--export-json")
+        .arg(&export_path)
+        .arg("--reference=sleep 2")
+        .arg("sleep 1")
+        .arg("sleep 3")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sleep 2 ran"));
+
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let comparison = json["comparison"].as_array().unwrap();
+    assert_eq!(comparison.len(), 2);
+    assert_eq!(comparison[0]["command"], "sleep 1");
+    assert_eq!(comparison[0]["reference"], "sleep 2");
+    assert_eq!(comparison[1]["command"], "sleep 3");
+    assert_eq!(comparison[1]["reference"], "sleep 2");
+}
+
+#[test]
+fn exports_individual_runs() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let json_path = tempdir.path().join("results.json");
+    let csv_path = tempdir.path().join("runs.csv");
+
+    hyperfine_debug()
+        .arg("--runs=3")
+        .arg("--export-json")
+        .arg(&json_path)
+        .arg("--export-csv-long")
+        .arg(&csv_path)
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success();
+
+    let contents = std::fs::read_to_string(json_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let result = &json["results"][1];
+    assert_eq!(result["times"].as_array().unwrap().len(), 3);
+    assert_eq!(result["times_user"].as_array().unwrap().len(), 3);
+    assert_eq!(result["times_system"].as_array().unwrap().len(), 3);
+    let timestamps: Vec<f64> = result["timestamps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t.as_f64().unwrap())
+        .collect();
+    assert_eq!(timestamps.len(), 3);
+    assert!(timestamps.windows(2).all(|w| w[0] <= w[1]));
+
+    let csv = std::fs::read_to_string(csv_path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "command,run,timestamp,time_real,time_user,time_system,exit_code,timed_out"
+    );
+    assert_eq!(lines.len(), 7);
+    assert!(lines[6].starts_with("sleep 2,3,"));
+    assert!(lines[6].ends_with(",2,0,0,0,false"));
+}
+
+#[test]
+fn shows_bootstrap_confidence_intervals() {
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("sleep 1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "95% CI (mean):        1.000 s …  1.000 s    [Median: 1.000 s … 1.000 s]",
+        ));
+
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--confidence-level=99.9%")
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("99.9% CI (mean):").and(predicate::str::contains(
+                "times faster than sleep 2 (99.9% CI: 2.00 … 2.00",
+            )),
+        );
+
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--bootstrap-resamples=0")
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("CI")
+                .not()
+                .and(predicate::str::contains(
+                    "times faster than sleep 2 (p < 0.001)",
+                )),
+        );
+
+    hyperfine()
+        .arg("--bootstrap-resamples=1000000000000")
+        .arg("echo test")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The number of bootstrap resamples can not be larger than 1000000",
+        ));
+}
+
+#[test]
+fn shows_percentiles() {
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("sleep 1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("P5 … P95").not());
+
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--show-percentiles")
+        .arg("sleep 1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("P5 … P95:             1.000 s …  1.000 s    [P99: 1.000 s]")
+                .and(predicate::str::contains(
+                    "P25 … P75:            1.000 s …  1.000 s    [IQR: 0.000 s, CoV: 0.00%]",
+                )),
+        );
+}
+
+#[test]
+fn baseline_comparison_detects_regressions() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let baseline_path = tempdir.path().join("baseline.json");
+    let export_path = tempdir.path().join("results.json");
+
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--export-json")
+        .arg(&baseline_path)
+        .arg("--command-name=a")
+        .arg("--command-name=b")
+        .arg("sleep 1")
+        .arg("sleep 2")
+        .assert()
+        .success();
+
+    // Command 'a' gets faster, command 'b' stays the same
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--baseline")
+        .arg(&baseline_path)
+        .arg("--command-name=a")
+        .arg("--command-name=b")
+        .arg("sleep 0.5")
+        .arg("sleep 2.02")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Comparison with baseline")
+                .and(predicate::str::contains("-50.0%"))
+                .and(predicate::str::contains("a (improvement, p < 0.001)"))
+                .and(predicate::str::contains("b (no significant change")),
+        );
+
+    // Command 'b' gets slower
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--baseline")
+        .arg(&baseline_path)
+        .arg("--export-json")
+        .arg(&export_path)
+        .arg("--command-name=a")
+        .arg("--command-name=b")
+        .arg("--command-name=c")
+        .arg("sleep 1")
+        .arg("sleep 3")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stdout(
+            predicate::str::contains("b (regression, p < 0.001)")
+                .and(predicate::str::contains("c: not found in baseline")),
+        )
+        .stderr(predicate::str::contains(
+            "Performance regression compared to the baseline (threshold: 5%): 'b'",
+        ));
+
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(json["results"][1]["baseline"]["verdict"], "regression");
+    assert_eq!(json["results"][1]["baseline"]["baseline_mean"], 2.0);
+    assert!(json["results"][2].get("baseline").is_none());
+
+    // A larger threshold accepts the slowdown
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--baseline")
+        .arg(&baseline_path)
+        .arg("--regression-threshold=60%")
+        .arg("--command-name=a")
+        .arg("--command-name=b")
+        .arg("sleep 1")
+        .arg("sleep 3")
+        .assert()
+        .success();
+}
+
+#[test]
+fn baseline_can_be_updated_in_place() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let results_path = tempdir.path().join("results.json");
+
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--export-json")
+        .arg(&results_path)
+        .arg("sleep 1")
+        .assert()
+        .success();
+
+    hyperfine_debug()
+        .arg("--runs=5")
+        .arg("--baseline")
+        .arg(&results_path)
+        .arg("--export-json")
+        .arg(&results_path)
+        .arg("sleep 2")
+        .arg("sleep 1")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("sleep 2: not found in baseline")
+                .and(predicate::str::contains("sleep 1 (no significant change")),
+        );
+
+    let contents = std::fs::read_to_string(results_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(json["results"].as_array().unwrap().len(), 2);
+    assert_eq!(json["results"][1]["baseline"]["baseline_mean"], 1.0);
+}
+
+#[test]
+fn fails_with_missing_baseline_file() {
+    hyperfine_debug()
+        .arg("--baseline=does-not-exist.json")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Could not open JSON export file 'does-not-exist.json'",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn interrupt_finishes_with_partial_results() {
+    use std::process::Stdio;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("results.json");
+
+    let child = common::hyperfine_raw_command()
+        .arg("--shell=none")
+        .arg("--runs=1000")
+        .arg("--style=basic")
+        .arg("--export-json")
+        .arg(&export_path)
+        .arg("sleep 0.05")
+        .arg("sleep 0.01")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(1000));
+    unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGINT) };
+
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(130));
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("Benchmark 1: sleep 0.05"));
+    assert!(!stdout.contains("Benchmark 2"));
+    assert!(stderr.contains("The benchmark has been interrupted after"));
+
+    let contents = std::fs::read_to_string(export_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["incomplete"], true);
+
+    let runs = results[0]["times"].as_array().unwrap().len();
+    assert!(runs > 0 && runs < 1000);
+}
+
+#[cfg(unix)]
+#[test]
+fn interrupt_during_warmup_skips_timing_runs() {
+    use std::process::Stdio;
+    use std::time::{Duration, Instant};
+
+    for order in ["sequential", "round-robin"] {
+        let start = Instant::now();
+        let child = common::hyperfine_raw_command()
+            .arg("--shell=none")
+            .arg("--warmup=1")
+            .arg("--runs=2")
+            .arg("--style=basic")
+            .arg(format!("--execution-order={order}"))
+            .arg("sleep 3")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        std::thread::sleep(Duration::from_millis(500));
+        unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGINT) };
+
+        let output = child.wait_with_output().unwrap();
+        assert_eq!(output.status.code(), Some(130));
+        assert!(start.elapsed() < Duration::from_millis(2500));
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.contains("Time (mean"));
+    }
+}
+
+#[test]
+fn resume_skips_completed_benchmarks() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("results.json");
+
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-scan")
+        .arg("t")
+        .arg("1")
+        .arg("2")
+        .arg("--export-json")
+        .arg(&export_path)
+        .arg("sleep {t}")
+        .assert()
+        .success();
+
+    // Simulate a session that was interrupted during the second benchmark, before
+    // the third one has been started
+    let contents = std::fs::read_to_string(&export_path).unwrap();
+    let mut json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    json["results"][1]["incomplete"] = true.into();
+    std::fs::write(&export_path, json.to_string()).unwrap();
+
+    for order in ["sequential", "round-robin"] {
+        hyperfine_debug()
+            .arg("--runs=2")
+            .arg(format!("--execution-order={order}"))
+            .arg("--parameter-scan")
+            .arg("t")
+            .arg("1")
+            .arg("3")
+            .arg("--resume")
+            .arg(&export_path)
+            .arg("--export-json")
+            .arg(&export_path)
+            .arg("sleep {t}")
+            .assert()
+            .success()
+            .stdout(
+                predicate::str::contains("Benchmark 1: sleep 1\n  Skipped, result taken from")
+                    .and(predicate::str::contains("Benchmark 2: sleep 2\n  Time"))
+                    .and(predicate::str::contains("Benchmark 3: sleep 3\n  Time")),
+            );
+
+        let contents = std::fs::read_to_string(&export_path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        let results = json["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        for (result, t) in results.iter().zip(["1", "2", "3"]) {
+            assert_eq!(result["parameters"]["t"], t);
+            assert!(result.get("incomplete").is_none());
+        }
+
+        // For the next iteration, restore the state of the interrupted session
+        let mut json = json;
+        json["results"].as_array_mut().unwrap().truncate(2);
+        json["results"][1]["incomplete"] = true.into();
+        std::fs::write(&export_path, json.to_string()).unwrap();
+    }
+}
+
+#[test]
+fn resume_matches_results_by_command_line() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("results.json");
+
+    let hyperfine_with_name = |name: &str| {
+        let mut cmd = hyperfine_debug();
+        cmd.arg("--runs=2")
+            .arg("--parameter-scan")
+            .arg("t")
+            .arg("1")
+            .arg("2")
+            .arg(format!("--command-name={name}"))
+            .arg("--resume")
+            .arg(&export_path)
+            .arg("--export-json")
+            .arg(&export_path)
+            .arg("sleep {t}")
+            .arg("sleep 1{t}");
+        cmd
+    };
+
+    // Only the first of the benchmarks that share a name has been completed
+    std::fs::write(
+        &export_path,
+        r#"{"results": [{"command": "job", "command_line": "sleep 1", "mean": 1.0,
+            "stddev": 0.0, "median": 1.0, "user": 0.0, "system": 0.0, "min": 1.0,
+            "max": 1.0, "times": [1.0, 1.0], "exit_codes": [0, 0],
+            "parameters": {"t": "1"}}]}"#,
+    )
+    .unwrap();
+
+    hyperfine_with_name("job").assert().success().stdout(
+        predicate::str::contains("Benchmark 1: job\n  Skipped, result taken from")
+            .and(predicate::str::contains("Benchmark 2: job\n  Time")),
+    );
+
+    // Renaming the commands does not affect the matching
+    hyperfine_with_name("renamed-{t}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Time").not());
+
+    let contents = std::fs::read_to_string(&export_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[1]["command"], "renamed-1");
+    assert_eq!(results[1]["command_line"], "sleep 11");
+}
+
+#[test]
+fn fails_with_missing_resume_file() {
+    hyperfine_debug()
+        .arg("--resume=does-not-exist.json")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Could not open JSON export file 'does-not-exist.json'",
+        ));
+}
+
+#[test]
+fn fails_with_invalid_confidence_level() {
+    hyperfine()
+        .arg("--confidence-level=100")
+        .arg("echo test")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid confidence level '100'"));
+}
+
+#[cfg(windows)]
+#[test]
+fn windows_quote_args() {
+    hyperfine()
+        .arg("more \"example_input_file.txt\"")
+        .assert()
+        .success();
+}
 
 #[cfg(windows)]
 #[test]
@@ -632,3 +1652,494 @@ fn windows_quote_before_quote_args() {
         .assert()
         .success();
 }
+
+#[test]
+fn reads_parameter_values_from_file_and_command() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let values_path = tempdir.path().join("values.txt");
+    std::fs::write(&values_path, "1\n\n2\n").unwrap();
+
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-list-file")
+        .arg("x")
+        .arg(&values_path)
+        .arg("--parameter-list-cmd")
+        .arg("y")
+        .arg("echo 3; echo 4")
+        .arg("--command-name")
+        .arg("{x}/{y}")
+        .arg("sleep {x}.{y}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Benchmark 1: 1/3")
+                .and(predicate::str::contains("Benchmark 2: 2/3"))
+                .and(predicate::str::contains("Benchmark 3: 1/4"))
+                .and(predicate::str::contains("Benchmark 4: 2/4"))
+                .and(predicate::str::contains("Benchmark 5").not()),
+        );
+
+    hyperfine_debug()
+        .arg("--parameter-list-cmd")
+        .arg("y")
+        .arg("exit 1")
+        .arg("sleep {y}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The command 'exit 1' for '--parameter-list-cmd' failed",
+        ));
+
+    hyperfine_debug()
+        .arg("--parameter-list-file")
+        .arg("x")
+        .arg(tempdir.path().join("missing.txt"))
+        .arg("sleep {x}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Could not read the parameter values from",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn runs_parameter_list_cmd_like_the_benchmarks() {
+    // The custom shell ignores the command and always prints 'custom'
+    hyperfine()
+        .arg("--dry-run")
+        .arg("--shell=sh -c 'echo custom' --")
+        .arg("--parameter-list-cmd")
+        .arg("v")
+        .arg("echo default")
+        .arg("run {v}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Parameters: v = custom"));
+
+    // Without a shell, the ';' is an argument of echo
+    hyperfine()
+        .arg("--dry-run")
+        .arg("-N")
+        .arg("--parameter-list-cmd")
+        .arg("v")
+        .arg("echo a; echo b")
+        .arg("run {v}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Parameters: v = a; echo b")
+                .and(predicate::str::contains("Benchmark 2").not()),
+        );
+}
+
+#[test]
+fn evaluates_expressions_in_placeholders() {
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-scan")
+        .arg("n")
+        .arg("1")
+        .arg("3")
+        .arg("--command-name")
+        .arg("run-{n:02}")
+        .arg("sleep {n*0.5:.1}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Benchmark 1: run-01")
+                .and(predicate::str::contains("Benchmark 3: run-03"))
+                .and(predicate::str::contains("Time (mean ± σ):      1.500 s"))
+                // The parameter is used in the expression, so it is not listed as unused
+                .and(predicate::str::contains("(n = ").not()),
+        );
+
+    for (args, placeholder) in [
+        (vec!["sleep {n/0}"], "{n/0}"),
+        (vec!["--command-name", "run-{n:x}", "sleep {n}"], "{n:x}"),
+        (
+            vec!["--prepare", "sleep {n*unknown}", "sleep {n}"],
+            "{n*unknown}",
+        ),
+    ] {
+        hyperfine_debug()
+            .arg("--parameter-scan")
+            .arg("n")
+            .arg("0")
+            .arg("1")
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(format!(
+                "Invalid placeholder '{placeholder}'"
+            )));
+    }
+}
+
+#[test]
+fn filters_parameter_combinations() {
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-list")
+        .arg("a")
+        .arg("1,2,3")
+        .arg("--parameter-list")
+        .arg("b")
+        .arg("1,2,3")
+        .arg("--parameter-filter")
+        .arg("a < b")
+        .arg("--command-name")
+        .arg("run-{a}-{b}")
+        .arg("sleep 0.{a}{b}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Benchmark 1: run-1-2")
+                .and(predicate::str::contains("Benchmark 2: run-1-3"))
+                .and(predicate::str::contains("Benchmark 3: run-2-3"))
+                .and(predicate::str::contains("Benchmark 4").not()),
+        );
+
+    hyperfine_debug()
+        .arg("--parameter-list")
+        .arg("a")
+        .arg("1,2")
+        .arg("--parameter-filter")
+        .arg("a >")
+        .arg("sleep {a}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid parameter filter 'a >'"));
+}
+
+#[test]
+fn zips_parameter_lists() {
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--parameter-list")
+        .arg("x")
+        .arg("1,2")
+        .arg("--parameter-list")
+        .arg("y")
+        .arg("3,4")
+        .arg("--parameter-zip")
+        .arg("x,y")
+        .arg("sleep {x}.{y}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Benchmark 1: sleep 1.3"))
+        .stdout(predicate::str::contains("Benchmark 2: sleep 2.4"))
+        .stdout(predicate::str::contains("Benchmark 3").not());
+
+    hyperfine_debug()
+        .arg("--parameter-list")
+        .arg("x")
+        .arg("1,2")
+        .arg("--parameter-list")
+        .arg("y")
+        .arg("3")
+        .arg("--parameter-zip")
+        .arg("x,y")
+        .arg("sleep {x}.{y}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The zipped parameters 'x' and 'y' have a different number of values (2 and 1)",
+        ));
+}
+
+#[test]
+fn runs_benchmark_suite() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    std::fs::write(
+        tempdir.path().join("bench.toml"),
+        r#"
+        [[benchmark]]
+        name = "fixed"
+        commands = ["sleep 1", "sleep 2"]
+        command_names = ["one", "two"]
+        runs = 5
+        export = { json = "fixed.json" }
+
+        [[benchmark]]
+        name = "parametrized"
+        commands = ["sleep {time}"]
+        parameters = { time = [1, 3] }
+        warmup = 1
+        export = { json = "parametrized.json" }
+        "#,
+    )
+    .unwrap();
+
+    hyperfine_debug()
+        .current_dir(tempdir.path())
+        .arg("--suite=bench.toml")
+        .arg("--runs=2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Benchmark group: fixed"))
+        .stdout(predicate::str::contains("Benchmark group: parametrized"))
+        .stdout(predicate::str::contains("Benchmark 1: one"))
+        .stdout(predicate::str::contains("Benchmark 2: sleep 3"));
+
+    let read_results = |filename: &str| -> serde_json::Value {
+        let contents = std::fs::read_to_string(tempdir.path().join(filename)).unwrap();
+        serde_json::from_str::<serde_json::Value>(&contents).unwrap()["results"].clone()
+    };
+
+    let fixed = read_results("fixed.json");
+    assert_eq!(fixed.as_array().unwrap().len(), 2);
+    assert_eq!(fixed[1]["command"], "two");
+    // --runs on the command line takes precedence over the suite
+    assert_eq!(fixed[0]["times"].as_array().unwrap().len(), 2);
+
+    let parametrized = read_results("parametrized.json");
+    assert_eq!(parametrized[1]["command"], "sleep 3");
+    assert_eq!(parametrized[1]["parameters"]["time"], "3");
+}
+
+#[test]
+fn fails_with_invalid_benchmark_suite() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let suite_path = tempdir.path().join("bench.toml");
+    std::fs::write(
+        &suite_path,
+        "[[benchmark]]\nname = \"a\"\ncommands = [\"sleep 1\"]\n\n\
+         [[benchmark]]\nname = \"b\"\ncommands = [\"sleep 2\"]\nwarmups = 1\n",
+    )
+    .unwrap();
+
+    hyperfine_debug()
+        .arg("--suite")
+        .arg(&suite_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Could not parse the benchmark suite",
+        ))
+        .stderr(predicate::str::contains("unknown field `warmups`"));
+
+    std::fs::write(
+        &suite_path,
+        "[[benchmark]]\nname = \"a\"\ncommands = [\"sleep 1\"]\n\n\
+         [[benchmark]]\nname = \"b\"\ncommands = [\"sleep 2\"]\n",
+    )
+    .unwrap();
+
+    hyperfine_debug()
+        .arg("--suite")
+        .arg(&suite_path)
+        .arg("--export-json=results.json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The option --export-json can not be used with a suite that contains more than one benchmark group",
+        ));
+
+    hyperfine_debug()
+        .arg("--suite")
+        .arg(&suite_path)
+        .arg("sleep 3")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Commands can not be passed on the command line when using --suite",
+        ));
+}
+
+#[test]
+fn dry_run_prints_plan_without_running_commands() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let export_path = tempdir.path().join("results.json");
+
+    hyperfine()
+        .arg("--dry-run")
+        .arg("--style=basic")
+        .arg("--export-json")
+        .arg(&export_path)
+        .arg("--parameter-list")
+        .arg("threads")
+        .arg("1,2")
+        .arg("--setup")
+        .arg("make -j {threads}")
+        .arg("--command-name")
+        .arg("run")
+        .arg("exit 1 && run -t {threads} {thraeds}")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Dry run: 2 benchmark(s)")
+                .and(predicate::str::contains("Benchmark 2: run"))
+                .and(predicate::str::contains("Parameters: threads = 2"))
+                .and(predicate::str::contains(
+                    "Command:    exit 1 && run -t 2 {thraeds}",
+                ))
+                .and(predicate::str::contains("Setup:      make -j 2"))
+                .and(predicate::str::contains(
+                    "Estimated duration: at least 6.0 s",
+                )),
+        )
+        .stderr(
+            predicate::str::contains(
+                "Unknown parameter 'thraeds' in 'exit 1 && run -t {threads} {thraeds}'",
+            )
+            .and(predicate::str::contains(
+                "2 benchmarks have the same name 'run'",
+            )),
+        );
+
+    assert!(!export_path.exists());
+}
+
+#[test]
+fn dry_run_estimates_duration_from_baseline() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    let baseline_path = tempdir.path().join("baseline.json");
+
+    hyperfine_debug()
+        .arg("--runs=2")
+        .arg("--export-json")
+        .arg(&baseline_path)
+        .arg("sleep 0.5")
+        .arg("sleep 2")
+        .assert()
+        .success();
+
+    hyperfine_debug()
+        .arg("--dry-run")
+        .arg("--runs=4")
+        .arg("--baseline")
+        .arg(&baseline_path)
+        .arg("sleep 0.5")
+        .arg("sleep 2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Estimated duration: 10.0 s (based on the results in",
+        ));
+
+    hyperfine_debug()
+        .arg("--dry-run")
+        .arg("--runs=4")
+        .arg("--baseline")
+        .arg(&baseline_path)
+        .arg("sleep 0.5")
+        .arg("sleep 3")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Estimated duration: at least 2.0 s (1 of 2 benchmarks are not in",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn runs_commands_with_environment_and_working_directory() {
+    use tempfile::tempdir;
+
+    let tempdir = tempdir().unwrap();
+    for version in ["1", "2"] {
+        let directory = tempdir.path().join(format!("build-{version}"));
+        std::fs::create_dir(&directory).unwrap();
+        std::fs::write(directory.join(format!("app-{version}")), "").unwrap();
+    }
+
+    // Parameters are substituted in '--env' and '--cwd', and both also apply to '--setup'
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--parameter-list")
+        .arg("v")
+        .arg("1,2")
+        .arg("--env")
+        .arg("VERSION={v} GREETING='hello world'")
+        .arg("--cwd")
+        .arg(tempdir.path().join("build-{v}"))
+        .arg("--setup")
+        .arg("test -f app-{v}")
+        .arg("test -f app-$VERSION && test \"$GREETING\" = 'hello world'")
+        .assert()
+        .success();
+
+    // The raw executor, with one value per command
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--shell=none")
+        .arg("--cwd")
+        .arg(tempdir.path().join("build-1"))
+        .arg("--cwd")
+        .arg(tempdir.path().join("build-2"))
+        .arg("--env=X=1")
+        .arg("--env=X=2")
+        .arg("sh -c 'test -f app-$X'")
+        .arg("sh -c 'test -f ../build-$X/app-2'")
+        .assert()
+        .success();
+
+    // With '-N', a relative program path is resolved against the '--cwd' directory, also
+    // if that is relative itself
+    let script = tempdir.path().join("build-1").join("run.sh");
+    std::fs::write(&script, "#!/bin/sh\ntest -f app-1\n").unwrap();
+    std::fs::set_permissions(&script, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+    hyperfine()
+        .current_dir(tempdir.path())
+        .arg("--runs=1")
+        .arg("-N")
+        .arg("--cwd=build-1")
+        .arg("./run.sh")
+        .assert()
+        .success();
+
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--cwd")
+        .arg(tempdir.path().join("missing"))
+        .arg("pwd")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "The working directory '{}' (see '--cwd') does not exist",
+            tempdir.path().join("missing").display()
+        )));
+
+    hyperfine()
+        .arg("--runs=1")
+        .arg("--env-clear")
+        .arg("--env=A=1")
+        .arg("test -z \"$HOME\" && test \"$A\" = 1")
+        .assert()
+        .success();
+}
+
+#[test]
+fn fails_with_invalid_environment_options() {
+    hyperfine_debug()
+        .arg("--env")
+        .arg("FOO")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid environment variable 'FOO' for '--env'. Expected the form KEY=VALUE",
+        ));
+
+    hyperfine_debug()
+        .arg("--cwd=a")
+        .arg("--cwd=b")
+        .arg("sleep 1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The '--cwd' option has to be provided just once or N times",
+        ));
+}